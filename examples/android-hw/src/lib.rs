@@ -1,13 +1,9 @@
-mod fft_shift;
-use fft_shift::FftShift;
-mod keep_1_in_n;
-use keep_1_in_n::Keep1InN;
-
 use std::env;
 
 use futuresdr::anyhow::Result;
 use futuresdr::blocks::Apply;
-use futuresdr::blocks::Fft;
+use futuresdr::blocks::FftBuilder;
+use futuresdr::blocks::Keep1InNBuilder;
 use futuresdr::blocks::SoapySourceBuilder;
 use futuresdr::blocks::WebsocketSinkBuilder;
 use futuresdr::blocks::WebsocketSinkMode;
@@ -43,18 +39,16 @@ pub fn run_fg() -> Result<()> {
         .build();
 
     let src = fg.add_block(src);
-    let fft = fg.add_block(Fft::new());
+    let fft = fg.add_block(FftBuilder::new().shift(true).build());
     let power = fg.add_block(Apply::new(|x: &Complex32| x.norm()));
     let log = fg.add_block(Apply::new(|x: &f32| 10.0 * x.log10()));
-    let shift = fg.add_block(FftShift::<f32>::new());
-    let keep = fg.add_block(Keep1InN::new(0.1, 10));
+    let keep = fg.add_block(Keep1InNBuilder::new(10).alpha(0.1).build());
     let snk = fg.add_block(snk);
 
     fg.connect_stream(src, "out", fft, "in")?;
     fg.connect_stream(fft, "out", power, "in")?;
     fg.connect_stream(power, "out", log, "in")?;
-    fg.connect_stream(log, "out", shift, "in")?;
-    fg.connect_stream(shift, "out", keep, "in")?;
+    fg.connect_stream(log, "out", keep, "in")?;
     fg.connect_stream(keep, "out", snk, "in")?;
 
     Runtime::new().run(fg)?;
@@ -0,0 +1,67 @@
+//! Broadcast FM receiver for IQ recordings.
+//!
+//! The file holds interleaved 32-bit float IQ samples (e.g., from `FileSink`), centered on the
+//! station. The audio is played or, given an output file, written to a WAV file.
+//!
+//! Usage: `fm-receiver <iq file> <sample rate> [<wav file>]`
+use std::env;
+
+use futuresdr::anyhow::{bail, Result};
+use futuresdr::blocks::audio::AudioSink;
+use futuresdr::blocks::ArbitraryResamplerBuilder;
+use futuresdr::blocks::FileSourceBuilder;
+use futuresdr::blocks::FirBuilder;
+use futuresdr::blocks::FmDeemphasis;
+use futuresdr::blocks::QuadratureDemod;
+use futuresdr::blocks::WavSinkBuilder;
+use futuresdr::dsp::design;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+const AUDIO_RATE: u32 = 48_000;
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        bail!("usage: {} <iq file> <sample rate> [<wav file>]", args[0]);
+    }
+    let file = args[1].clone();
+    let sample_rate: f64 = args[2].parse()?;
+
+    // channel filter, decimating to about 200 kHz
+    let decimation = ((sample_rate / 200e3) as usize).max(1);
+    let quad_rate = sample_rate / decimation as f64;
+    let (n, window) = design::kaiser_order(60.0, 25e3, sample_rate);
+    // stays below Nyquist, also if there is no decimation
+    let cutoff = f64::min(100e3, 0.45 * quad_rate);
+    let taps = design::lowpass(n, cutoff, sample_rate, window);
+
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(FileSourceBuilder::new(8, file).build());
+    let filter = fg.add_block(
+        FirBuilder::<Complex32, f32>::new(&taps)
+            .decimation(decimation)
+            .build(),
+    );
+    let demod = fg.add_block(QuadratureDemod::fm(75e3, quad_rate));
+    // 75 us in the Americas and South Korea, 50 us elsewhere
+    let deemph = fg.add_block(FmDeemphasis::new(quad_rate, 75e-6));
+    let resamp =
+        fg.add_block(ArbitraryResamplerBuilder::<f32>::new(AUDIO_RATE as f64 / quad_rate).build());
+    let snk = match args.get(3) {
+        Some(wav) => fg.add_block(WavSinkBuilder::new(wav, AUDIO_RATE).build()),
+        None => fg.add_block(AudioSink::new(AUDIO_RATE, 1)),
+    };
+
+    fg.connect_stream(src, "out", filter, "in")?;
+    fg.connect_stream(filter, "out", demod, "in")?;
+    fg.connect_stream(demod, "out", deemph, "in")?;
+    fg.connect_stream(deemph, "out", resamp, "in")?;
+    fg.connect_stream(resamp, "out", snk, "in")?;
+
+    Runtime::new().run(fg)?;
+
+    Ok(())
+}
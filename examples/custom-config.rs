@@ -18,8 +18,8 @@ fn main() {
     let c = config::config();
     println!("FutureSDR Config: {:?}", c);
 
-    if let Some(v) = config::get_value("my") {
-        if let Ok(v) = v.try_into::<MyConfig>() {
+    if config::get_value("my").is_some() {
+        if let Some(v) = c.get_typed::<MyConfig>("my") {
             println!("MyConfig: {:?}", &v);
         } else {
             println!("MyConfig could not be deserialized");
@@ -1,16 +1,14 @@
 use futuresdr::anyhow::Result;
-use futuresdr::blocks::Fft;
+use futuresdr::blocks::AverageMode;
+use futuresdr::blocks::FrameAverage;
+use futuresdr::blocks::LogPower;
 use futuresdr::blocks::SoapySourceBuilder;
 use futuresdr::blocks::WebsocketSinkBuilder;
 use futuresdr::blocks::WebsocketSinkMode;
+use futuresdr::blocks::WelchPsdBuilder;
 use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Runtime;
 
-use spectrum::lin2db_block;
-use spectrum::power_block;
-use spectrum::FftShift;
-use spectrum::Keep1InN;
-
 fn main() -> Result<()> {
     let mut fg = Flowgraph::new();
 
@@ -24,19 +22,21 @@ fn main() -> Result<()> {
         .build();
 
     let src = fg.add_block(src);
-    let fft = fg.add_block(Fft::new());
-    let power = fg.add_block(power_block());
-    let log = fg.add_block(lin2db_block());
-    let shift = fg.add_block(FftShift::<f32>::new());
-    let keep = fg.add_block(Keep1InN::new(0.1, 10));
+    let psd = fg.add_block(WelchPsdBuilder::new(2048).build());
+    // shift into the display range of the frontend
+    let log = fg.add_block(LogPower::<f32>::new(30.0));
+    let avg = fg.add_block(FrameAverage::new(
+        2048,
+        AverageMode::Exponential(0.1),
+        10,
+        false,
+    ));
     let snk = fg.add_block(snk);
 
-    fg.connect_stream(src, "out", fft, "in")?;
-    fg.connect_stream(fft, "out", power, "in")?;
-    fg.connect_stream(power, "out", log, "in")?;
-    fg.connect_stream(log, "out", shift, "in")?;
-    fg.connect_stream(shift, "out", keep, "in")?;
-    fg.connect_stream(keep, "out", snk, "in")?;
+    fg.connect_stream(src, "out", psd, "in")?;
+    fg.connect_stream(psd, "out", log, "in")?;
+    fg.connect_stream(log, "out", avg, "in")?;
+    fg.connect_stream(avg, "out", snk, "in")?;
 
     Runtime::new().run(fg)?;
     Ok(())
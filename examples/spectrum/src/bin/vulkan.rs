@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
 use futuresdr::anyhow::Result;
-use futuresdr::blocks::Fft;
+use futuresdr::blocks::FftBuilder;
+use futuresdr::blocks::Keep1InNBuilder;
 use futuresdr::blocks::SoapySourceBuilder;
 use futuresdr::blocks::WebsocketSinkBuilder;
 use futuresdr::blocks::WebsocketSinkMode;
@@ -11,8 +12,6 @@ use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Runtime;
 
 use spectrum::power_block;
-use spectrum::FftShift;
-use spectrum::Keep1InN;
 use spectrum::Vulkan;
 
 fn main() -> Result<()> {
@@ -29,18 +28,16 @@ fn main() -> Result<()> {
         .build();
 
     let src = fg.add_block(src);
-    let fft = fg.add_block(Fft::new());
-    let shift = fg.add_block(FftShift::<f32>::new());
+    let fft = fg.add_block(FftBuilder::new().shift(true).build());
     let power = fg.add_block(power_block());
     let log = fg.add_block(Vulkan::new(broker, 16384));
-    let keep = fg.add_block(Keep1InN::new(0.1, 10));
+    let keep = fg.add_block(Keep1InNBuilder::new(10).alpha(0.1).build());
     let snk = fg.add_block(snk);
 
     fg.connect_stream(src, "out", fft, "in")?;
     fg.connect_stream(fft, "out", power, "in")?;
     fg.connect_stream_with_type(power, "out", log, "in", vulkan::H2D::new())?;
-    fg.connect_stream_with_type(log, "out", shift, "in", vulkan::D2H::new())?;
-    fg.connect_stream(shift, "out", keep, "in")?;
+    fg.connect_stream_with_type(log, "out", keep, "in", vulkan::D2H::new())?;
     fg.connect_stream(keep, "out", snk, "in")?;
 
     Runtime::new().run(fg)?;
@@ -6,11 +6,6 @@ pub use vulkan::Vulkan;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 
-mod fft_shift;
-pub use fft_shift::FftShift;
-mod keep_1_in_n;
-pub use keep_1_in_n::Keep1InN;
-
 use futuresdr::blocks::Apply;
 use futuresdr::num_complex::Complex32;
 use futuresdr::runtime::Block;
@@ -1,5 +1,6 @@
 use futuresdr::anyhow::Result;
-use futuresdr::blocks::Fft;
+use futuresdr::blocks::FftBuilder;
+use futuresdr::blocks::Keep1InNBuilder;
 use futuresdr::blocks::WasmFreq;
 use futuresdr::blocks::WasmSdr;
 use futuresdr::blocks::Wgpu;
@@ -10,8 +11,6 @@ use wasm_bindgen::prelude::*;
 
 // use crate::lin2db_block;
 use crate::power_block;
-use crate::FftShift;
-use crate::Keep1InN;
 
 #[wasm_bindgen]
 pub async fn run_fg() {
@@ -22,19 +21,17 @@ async fn run() -> Result<()> {
     let mut fg = Flowgraph::new();
 
     let src = fg.add_block(WasmSdr::new());
-    let fft = fg.add_block(Fft::new());
+    let fft = fg.add_block(FftBuilder::new().shift(true).build());
     let power = fg.add_block(power_block());
     let broker = wgpu::Broker::new().await;
     let log = fg.add_block(Wgpu::new(broker, 2048));
-    let shift = fg.add_block(FftShift::<f32>::new());
-    let keep = fg.add_block(Keep1InN::new(0.1, 10));
+    let keep = fg.add_block(Keep1InNBuilder::new(10).alpha(0.1).build());
     let snk = fg.add_block(WasmFreq::new());
 
     fg.connect_stream(src, "out", fft, "in")?;
     fg.connect_stream(fft, "out", power, "in")?;
     fg.connect_stream_with_type(power, "out", log, "in", wgpu::H2D::new())?;
-    fg.connect_stream_with_type(log, "out", shift, "in", wgpu::D2H::new())?;
-    fg.connect_stream(shift, "out", keep, "in")?;
+    fg.connect_stream_with_type(log, "out", keep, "in", wgpu::D2H::new())?;
     fg.connect_stream(keep, "out", snk, "in")?;
 
     Runtime::new().run(fg).await?;
@@ -3,9 +3,11 @@ use std::time;
 
 use futuresdr::anyhow::{Context, Result};
 use futuresdr::blocks::CopyBuilder;
+use futuresdr::blocks::StreamToVector;
 use futuresdr::blocks::VectorSink;
 use futuresdr::blocks::VectorSinkBuilder;
 use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::blocks::VectorToStream;
 use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Runtime;
 
@@ -14,6 +16,7 @@ fn main() -> Result<()> {
 
     let n_items = 20_000;
     let n_copy = 1000;
+    let vector_len = 16;
 
     let orig: Vec<f32> = repeat_with(rand::random::<f32>).take(n_items).collect();
 
@@ -24,19 +27,20 @@ fn main() -> Result<()> {
             .build(),
     );
 
-    let mut prev = 0;
-    for i in 0..n_copy {
-        let t = fg.add_block(CopyBuilder::new(4).build());
+    // copy vectors of samples
+    let to_vector = fg.add_block(StreamToVector::new(4, vector_len));
+    let to_stream = fg.add_block(VectorToStream::new(4, vector_len));
+    fg.connect_stream(src, "out", to_vector, "in")?;
 
-        if i == 0 {
-            fg.connect_stream(src, "out", t, "in")?;
-        } else {
-            fg.connect_stream(prev, "out", t, "in")?;
-        }
+    let mut prev = to_vector;
+    for _ in 0..n_copy {
+        let t = fg.add_block(CopyBuilder::new(4 * vector_len).build());
+        fg.connect_stream(prev, "out", t, "in")?;
         prev = t;
     }
 
-    fg.connect_stream(prev, "out", snk, "in")?;
+    fg.connect_stream(prev, "out", to_stream, "in")?;
+    fg.connect_stream(to_stream, "out", snk, "in")?;
 
     let now = time::Instant::now();
     fg = Runtime::new().run(fg)?;
@@ -14,6 +14,8 @@ pub enum Msg {
 #[derive(Clone, Properties, Default, PartialEq)]
 pub struct Props {
     pub url: String,
+    #[prop_or_default]
+    pub flowgraph: u64,
     pub block: u64,
     pub callback: u64,
     pub interval_secs: f32,
@@ -26,8 +28,8 @@ pub struct PollPeriodic {
 impl PollPeriodic {
     fn endpoint(props: &Props) -> String {
         format!(
-            "{}/api/block/{}/call/{}/",
-            props.url, props.block, props.callback
+            "{}/api/fg/{}/block/{}/call/{}/",
+            props.url, props.flowgraph, props.block, props.callback
         )
     }
 
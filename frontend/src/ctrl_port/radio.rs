@@ -40,6 +40,8 @@ impl Component for RadioItem {
 pub struct Props {
     pub children: ChildrenWithProps<RadioItem>,
     pub url: String,
+    #[prop_or_default]
+    pub flowgraph: u64,
     pub block: u64,
     pub callback: u64,
 }
@@ -59,8 +61,8 @@ pub enum Msg {
 impl Radio {
     fn endpoint(props: &Props) -> String {
         format!(
-            "{}/api/block/{}/call/{}",
-            props.url, props.block, props.callback
+            "{}/api/fg/{}/block/{}/call/{}",
+            props.url, props.flowgraph, props.block, props.callback
         )
     }
 
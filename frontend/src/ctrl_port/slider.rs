@@ -11,6 +11,7 @@ use futuresdr_pmt::PmtKind;
 pub fn add_slider_u32(
     id: String,
     url: String,
+    flowgraph: u32,
     block: u32,
     callback: u32,
     min: f64,
@@ -24,6 +25,7 @@ pub fn add_slider_u32(
         div,
         Props {
             url,
+            flowgraph,
             block,
             callback,
             pmt_type: PmtKind::U32,
@@ -44,6 +46,8 @@ pub enum Msg {
 #[derive(Clone, Properties, PartialEq)]
 pub struct Props {
     pub url: String,
+    #[prop_or_default]
+    pub flowgraph: u32,
     pub block: u32,
     pub callback: u32,
     pub pmt_type: PmtKind,
@@ -62,8 +66,8 @@ pub struct Slider {
 impl Slider {
     fn endpoint(props: &Props) -> String {
         format!(
-            "{}/api/block/{}/call/{}",
-            props.url, props.block, props.callback
+            "{}/api/fg/{}/block/{}/call/{}",
+            props.url, props.flowgraph, props.block, props.callback
         )
     }
 
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Pmt {
@@ -9,6 +10,7 @@ pub enum Pmt {
     Double(f64),
     VecF32(Vec<f32>),
     Blob(Vec<u8>),
+    MapStrPmt(HashMap<String, Pmt>),
 }
 
 impl Pmt {
@@ -60,6 +62,7 @@ pub enum PmtKind {
     Double,
     VecF32,
     Blob,
+    MapStrPmt,
 }
 
 #[cfg(test)]
@@ -86,6 +89,18 @@ mod test {
         let p2 = Pmt::deserialize(r).unwrap();
 
         assert_eq!(p, p2);
+
+        let mut m = HashMap::new();
+        m.insert("offset".to_owned(), Pmt::U64(123));
+        m.insert("peak".to_owned(), Pmt::Double(0.9));
+        let p = Pmt::MapStrPmt(m);
+        let mut s = flexbuffers::FlexbufferSerializer::new();
+        p.serialize(&mut s).unwrap();
+
+        let r = flexbuffers::Reader::get_root(s.view()).unwrap();
+        let p2 = Pmt::deserialize(r).unwrap();
+
+        assert_eq!(p, p2);
     }
 
     #[allow(clippy::many_single_char_names)]
@@ -0,0 +1,83 @@
+use clap::{App, Arg};
+
+use futuresdr::anyhow::{bail, Result};
+use futuresdr::blocks;
+use futuresdr::runtime::config::SchedulerKind;
+#[cfg(feature = "flow_scheduler")]
+use futuresdr::runtime::scheduler::FlowScheduler;
+use futuresdr::runtime::scheduler::SmolScheduler;
+#[cfg(feature = "tpb_scheduler")]
+use futuresdr::runtime::scheduler::TpbScheduler;
+use futuresdr::runtime::FlowgraphDescription;
+use futuresdr::runtime::RuntimeBuilder;
+
+fn main() -> Result<()> {
+    let matches = App::new("futuresdr-run")
+        .about("Runs a flowgraph from a TOML, YAML, or JSON description.")
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .required_unless_present("list")
+                .help("Flowgraph description."),
+        )
+        .arg(
+            Arg::new("check")
+                .short('c')
+                .long("check")
+                .help("Only load and validate the flowgraph."),
+        )
+        .arg(
+            Arg::new("list")
+                .short('l')
+                .long("list-blocks")
+                .help("Lists the available block types."),
+        )
+        .get_matches();
+
+    let registry = blocks::registry();
+
+    if matches.is_present("list") {
+        for t in registry.type_names() {
+            println!("{}", t);
+        }
+        return Ok(());
+    }
+
+    let file = matches.value_of("file").unwrap();
+    let desc = FlowgraphDescription::from_file(file)?;
+    // the description may select the scheduler and its threads
+    let config = desc.config()?;
+    let fg = desc.build(&registry)?;
+
+    if matches.is_present("check") {
+        println!("{}: ok", file);
+        return Ok(());
+    }
+
+    match config.scheduler {
+        SchedulerKind::Smol => {
+            RuntimeBuilder::new(SmolScheduler::from_config(&config))
+                .config(config)
+                .build()
+                .run(fg)?;
+        }
+        #[cfg(feature = "flow_scheduler")]
+        SchedulerKind::Flow => {
+            RuntimeBuilder::new(FlowScheduler::from_config(&config))
+                .config(config)
+                .build()
+                .run(fg)?;
+        }
+        #[cfg(feature = "tpb_scheduler")]
+        SchedulerKind::Tpb => {
+            RuntimeBuilder::new(TpbScheduler::new())
+                .config(config)
+                .build()
+                .run(fg)?;
+        }
+        #[allow(unreachable_patterns)]
+        s => bail!("scheduler {:?} not enabled in this build", s),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use num_complex::Complex;
+use std::mem;
+use std::ops::Mul;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Items of [Agc] and [FeedForwardAgc](crate::blocks::FeedForwardAgc).
+pub trait AgcSample: Copy + Send + 'static + Mul<f32, Output = Self> {
+    /// Envelope of the sample, i.e., the absolute value.
+    fn magnitude(&self) -> f32;
+}
+
+impl AgcSample for f32 {
+    fn magnitude(&self) -> f32 {
+        self.abs()
+    }
+}
+
+impl AgcSample for Complex<f32> {
+    fn magnitude(&self) -> f32 {
+        self.norm()
+    }
+}
+
+// Sets the parameter for a `Double`, only queries it for `Null`.
+pub(crate) fn update(block: &str, name: &str, value: &mut f32, p: &Pmt) -> Pmt {
+    match p {
+        Pmt::Double(v) => *value = *v as f32,
+        Pmt::Null => {}
+        _ => warn!("{}: ignoring invalid {} {:?}", block, name, p),
+    }
+    Pmt::Double(*value as f64)
+}
+
+/// Automatic gain control with feedback, scaling the output envelope to a reference level.
+///
+/// The gain is adapted with the attack rate when the output is above the reference and with
+/// the decay rate when it is below. With a fast attack and a slow decay, strong signals are
+/// attenuated quickly without pumping the gain during short fades.
+///
+/// The message inputs `reference`, `attack`, `decay`, `max_gain`, and `gain` take a
+/// [Pmt::Double] to change the parameter while running. They return the current value, i.e.,
+/// a [Pmt::Null] to `gain` queries the current gain.
+pub struct Agc<A>
+where
+    A: AgcSample,
+{
+    reference: f32,
+    attack: f32,
+    decay: f32,
+    max_gain: f32,
+    gain: f32,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> Agc<A>
+where
+    A: AgcSample,
+{
+    pub fn new(reference: f32, attack: f32, decay: f32, max_gain: f32, gain: f32) -> Block {
+        assert!(max_gain > 0.0, "maximum gain has to be positive");
+        Block::new_sync(
+            BlockMetaBuilder::new("Agc").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<A>())
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::<Self>::new()
+                .add_sync_input("reference", Self::reference_handler)
+                .add_sync_input("attack", Self::attack_handler)
+                .add_sync_input("decay", Self::decay_handler)
+                .add_sync_input("max_gain", Self::max_gain_handler)
+                .add_sync_input("gain", Self::gain_handler)
+                .build(),
+            Agc {
+                reference,
+                attack,
+                decay,
+                max_gain,
+                gain: gain.min(max_gain),
+                _p: std::marker::PhantomData,
+            },
+        )
+    }
+
+    fn reference_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        Ok(update("Agc", "reference", &mut self.reference, &p))
+    }
+
+    fn attack_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        Ok(update("Agc", "attack", &mut self.attack, &p))
+    }
+
+    fn decay_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        Ok(update("Agc", "decay", &mut self.decay, &p))
+    }
+
+    fn max_gain_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        let r = update("Agc", "max_gain", &mut self.max_gain, &p);
+        self.gain = self.gain.min(self.max_gain);
+        Ok(r)
+    }
+
+    fn gain_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        let r = update("Agc", "gain", &mut self.gain, &p);
+        self.gain = self.gain.min(self.max_gain);
+        Ok(r)
+    }
+}
+
+#[async_trait]
+impl<A> SyncKernel for Agc<A>
+where
+    A: AgcSample,
+{
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        let o = sio.output(0).slice::<A>();
+
+        let n = std::cmp::min(i.len(), o.len());
+        for (x, y) in i[0..n].iter().zip(o[0..n].iter_mut()) {
+            *y = *x * self.gain;
+            let err = self.reference - y.magnitude();
+            let rate = if err < 0.0 { self.attack } else { self.decay };
+            self.gain = (self.gain + rate * err).clamp(0.0, self.max_gain);
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct AgcBuilder<A>
+where
+    A: AgcSample,
+{
+    reference: f32,
+    attack: f32,
+    decay: f32,
+    max_gain: f32,
+    gain: f32,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> AgcBuilder<A>
+where
+    A: AgcSample,
+{
+    /// Reference level and initial gain of one, attack rate 0.1, decay rate 0.01, and a
+    /// maximum gain of 65536.
+    pub fn new() -> AgcBuilder<A> {
+        AgcBuilder {
+            reference: 1.0,
+            attack: 0.1,
+            decay: 0.01,
+            max_gain: 65536.0,
+            gain: 1.0,
+            _p: std::marker::PhantomData,
+        }
+    }
+
+    /// Output envelope to which the gain is adapted.
+    #[must_use]
+    pub fn reference(mut self, reference: f32) -> AgcBuilder<A> {
+        self.reference = reference;
+        self
+    }
+
+    /// Adaptation rate when the output is above the reference.
+    #[must_use]
+    pub fn attack(mut self, attack: f32) -> AgcBuilder<A> {
+        self.attack = attack;
+        self
+    }
+
+    /// Adaptation rate when the output is below the reference.
+    #[must_use]
+    pub fn decay(mut self, decay: f32) -> AgcBuilder<A> {
+        self.decay = decay;
+        self
+    }
+
+    #[must_use]
+    pub fn max_gain(mut self, max_gain: f32) -> AgcBuilder<A> {
+        self.max_gain = max_gain;
+        self
+    }
+
+    /// Initial gain.
+    #[must_use]
+    pub fn gain(mut self, gain: f32) -> AgcBuilder<A> {
+        self.gain = gain;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        Agc::<A>::new(
+            self.reference,
+            self.attack,
+            self.decay,
+            self.max_gain,
+            self.gain,
+        )
+    }
+}
+
+impl<A> Default for AgcBuilder<A>
+where
+    A: AgcSample,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,61 @@
+use num_complex::Complex;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// AM envelope demodulator, outputting the magnitude of the complex baseband.
+///
+/// The output contains the carrier as DC offset, which can be removed with a
+/// [DcBlocker](crate::blocks::DcBlocker).
+pub struct AmDemod;
+
+impl AmDemod {
+    pub fn new() -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("AmDemod").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_output("out", mem::size_of::<f32>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            AmDemod,
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for AmDemod {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<f32>();
+
+        let n = std::cmp::min(i.len(), o.len());
+        for (x, y) in i[0..n].iter().zip(o[0..n].iter_mut()) {
+            *y = x.norm();
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::rational_resampler::polyphase;
+use crate::blocks::HasFirImpl;
+use crate::dsp::design;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Resampler with an arbitrary, e.g., irrational rate that can be adjusted while running.
+///
+/// It uses a polyphase filter bank and interpolates linearly between neighboring sub-filters.
+/// The `rate` message input takes the new rate (output over input) as [Pmt::Double], e.g., to
+/// correct clock drift.
+pub struct ArbitraryResampler<A>
+where
+    A: HasFirImpl<f32>,
+{
+    rate: f64,
+    branches: Vec<Vec<f32>>,
+    diff_branches: Vec<Vec<f32>>,
+    // position between input samples in units of sub-filters
+    phase: f64,
+    // input samples to skip before the next output
+    skip: usize,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> ArbitraryResampler<A>
+where
+    A: HasFirImpl<f32>,
+    ArbitraryResampler<A>: SyncKernel,
+{
+    pub fn new(rate: f64, phases: usize, taps: &[f32]) -> Block {
+        assert!(rate > 0.0, "rate has to be positive");
+        assert!(phases > 0, "resampler needs at least one phase");
+        assert!(!taps.is_empty(), "resampler needs at least one tap");
+
+        // difference of neighboring taps, i.e., between neighboring sub-filters
+        let diff: Vec<f32> = taps
+            .iter()
+            .zip(taps.iter().skip(1).chain(std::iter::once(&0.0)))
+            .map(|(a, b)| b - a)
+            .collect();
+
+        Block::new_sync(
+            BlockMetaBuilder::new("ArbitraryResampler").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<A>())
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::<Self>::new()
+                .add_sync_input("rate", Self::rate_handler)
+                .build(),
+            ArbitraryResampler {
+                rate,
+                branches: polyphase(taps, phases),
+                diff_branches: polyphase(&diff, phases),
+                phase: 0.0,
+                skip: 0,
+                _p: std::marker::PhantomData,
+            },
+        )
+    }
+
+    fn rate_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Double(r) if r > 0.0 => self.rate = r,
+            _ => warn!("ArbitraryResampler: ignoring invalid rate {:?}", p),
+        }
+        Ok(Pmt::Double(self.rate))
+    }
+}
+
+#[async_trait]
+impl<A> SyncKernel for ArbitraryResampler<A>
+where
+    A: HasFirImpl<f32> + std::ops::Add<Output = A> + std::ops::Mul<f32, Output = A>,
+{
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        let o = sio.output(0).slice::<A>();
+        let len = self.branches[0].len();
+        let phases = self.branches.len() as f64;
+        let step = phases / self.rate;
+
+        let mut j = self.skip;
+        let mut produced = 0;
+        while j + len <= i.len() && produced < o.len() {
+            let p = self.phase as usize;
+            let frac = (self.phase - p as f64) as f32;
+            let x = &i[j..];
+            o[produced] = A::dot(x, &self.branches[p]) + A::dot(x, &self.diff_branches[p]) * frac;
+            produced += 1;
+
+            self.phase += step;
+            let n = (self.phase / phases).floor();
+            j += n as usize;
+            self.phase -= n * phases;
+        }
+
+        let consumed = std::cmp::min(j, i.len());
+        self.skip = j - consumed;
+
+        if sio.input(0).finished() && j + len > i.len() {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(produced);
+
+        Ok(())
+    }
+}
+
+pub struct ArbitraryResamplerBuilder<A>
+where
+    A: HasFirImpl<f32>,
+{
+    rate: f64,
+    phases: usize,
+    taps: Option<Vec<f32>>,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> ArbitraryResamplerBuilder<A>
+where
+    A: HasFirImpl<f32>,
+    ArbitraryResampler<A>: SyncKernel,
+{
+    /// Resampler that changes the rate by `rate` (output over input).
+    pub fn new(rate: f64) -> ArbitraryResamplerBuilder<A> {
+        ArbitraryResamplerBuilder {
+            rate,
+            phases: 32,
+            taps: None,
+            _p: std::marker::PhantomData,
+        }
+    }
+
+    /// Number of sub-filters of the filter bank.
+    #[must_use]
+    pub fn phases(mut self, phases: usize) -> ArbitraryResamplerBuilder<A> {
+        self.phases = phases;
+        self
+    }
+
+    /// Prototype filter at `phases` times the input rate. By default, a filter is designed with
+    /// [design::resampler].
+    #[must_use]
+    pub fn taps(mut self, taps: &[f32]) -> ArbitraryResamplerBuilder<A> {
+        self.taps = Some(taps.to_vec());
+        self
+    }
+
+    pub fn build(self) -> Block {
+        let taps = self
+            .taps
+            .unwrap_or_else(|| design::resampler(self.phases, self.rate));
+        ArbitraryResampler::<A>::new(self.rate, self.phases, &taps)
+    }
+}
@@ -0,0 +1,315 @@
+use num_complex::Complex;
+use std::cmp;
+use std::marker::PhantomData;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Items of the arithmetic blocks.
+///
+/// `i16` arithmetic saturates, and division by zero yields zero.
+pub trait ArithmeticSample: Copy + Send + Sync + 'static {
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+    fn mul(self, other: Self) -> Self;
+    fn div(self, other: Self) -> Self;
+
+    /// Applies `op` element-wise to `y` and `x`, storing the result in `y`.
+    fn fold_into<F: Fn(Self, Self) -> Self>(y: &mut [Self], x: &[Self], op: F) {
+        for (y, x) in y.iter_mut().zip(x) {
+            *y = op(*y, *x);
+        }
+    }
+}
+
+impl ArithmeticSample for f32 {
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+    fn sub(self, other: Self) -> Self {
+        self - other
+    }
+    fn mul(self, other: Self) -> Self {
+        self * other
+    }
+    fn div(self, other: Self) -> Self {
+        self / other
+    }
+    fn fold_into<F: Fn(Self, Self) -> Self>(y: &mut [Self], x: &[Self], op: F) {
+        fold_lanes::<_, _, 8>(y, x, op)
+    }
+}
+
+impl ArithmeticSample for f64 {
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+    fn sub(self, other: Self) -> Self {
+        self - other
+    }
+    fn mul(self, other: Self) -> Self {
+        self * other
+    }
+    fn div(self, other: Self) -> Self {
+        self / other
+    }
+}
+
+impl ArithmeticSample for i16 {
+    fn add(self, other: Self) -> Self {
+        self.saturating_add(other)
+    }
+    fn sub(self, other: Self) -> Self {
+        self.saturating_sub(other)
+    }
+    fn mul(self, other: Self) -> Self {
+        self.saturating_mul(other)
+    }
+    fn div(self, other: Self) -> Self {
+        match self.checked_div(other) {
+            Some(q) => q,
+            None if other == 0 => 0,
+            // i16::MIN / -1
+            None => i16::MAX,
+        }
+    }
+}
+
+impl ArithmeticSample for Complex<f32> {
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+    fn sub(self, other: Self) -> Self {
+        self - other
+    }
+    fn mul(self, other: Self) -> Self {
+        self * other
+    }
+    fn div(self, other: Self) -> Self {
+        self / other
+    }
+    fn fold_into<F: Fn(Self, Self) -> Self>(y: &mut [Self], x: &[Self], op: F) {
+        fold_lanes::<_, _, 4>(y, x, op)
+    }
+}
+
+// Processes chunks of `L` items with a loop of fixed length, which LLVM turns into SIMD
+// instructions without bounds checks, like the accumulators of the FIR filter.
+#[inline(always)]
+fn fold_lanes<A, F, const L: usize>(y: &mut [A], x: &[A], op: F)
+where
+    A: Copy,
+    F: Fn(A, A) -> A,
+{
+    let n = cmp::min(y.len(), x.len());
+    let m = n / L * L;
+
+    for (y, x) in y[..m].chunks_exact_mut(L).zip(x[..m].chunks_exact(L)) {
+        for l in 0..L {
+            y[l] = op(y[l], x[l]);
+        }
+    }
+    for (y, x) in y[m..n].iter_mut().zip(&x[m..n]) {
+        *y = op(*y, *x);
+    }
+}
+
+// Folds the inputs `in0`, `in1`, ... element-wise with `op`. The operation is a type
+// parameter, so that the loops are monomorphized and can be vectorized by the compiler, see
+// [ArithmeticSample::fold_into].
+struct FoldKernel<A, F> {
+    n_inputs: usize,
+    op: F,
+    _p: PhantomData<A>,
+}
+
+fn fold_block<A, F>(name: &str, n_inputs: usize, op: F) -> Block
+where
+    A: ArithmeticSample,
+    F: Fn(A, A) -> A + Send + 'static,
+{
+    assert!(n_inputs > 0, "{} needs at least one input", name);
+    let mut sio = StreamIoBuilder::new();
+    for i in 0..n_inputs {
+        sio = sio.add_input(&format!("in{}", i), mem::size_of::<A>());
+    }
+    Block::new_sync(
+        BlockMetaBuilder::new(name).build(),
+        sio.add_output("out", mem::size_of::<A>()).build(),
+        MessageIoBuilder::<FoldKernel<A, F>>::new().build(),
+        FoldKernel {
+            n_inputs,
+            op,
+            _p: PhantomData,
+        },
+    )
+}
+
+#[async_trait]
+impl<A, F> SyncKernel for FoldKernel<A, F>
+where
+    A: ArithmeticSample,
+    F: Fn(A, A) -> A + Send + 'static,
+{
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = sio.output(0).slice::<A>();
+        let mut m = o.len();
+        for k in 0..self.n_inputs {
+            m = cmp::min(m, sio.input(k).slice::<A>().len());
+        }
+        let o = &mut o[0..m];
+
+        o.copy_from_slice(&sio.input(0).slice::<A>()[0..m]);
+        for k in 1..self.n_inputs {
+            A::fold_into(o, sio.input(k).slice::<A>(), &self.op);
+        }
+
+        for k in 0..self.n_inputs {
+            let i = sio.input(k);
+            if i.finished() && m == i.slice::<A>().len() {
+                io.finished = true;
+            }
+            i.consume(m);
+        }
+        sio.output(0).produce(m);
+
+        Ok(())
+    }
+}
+
+// Applies `f` to each item of `in`.
+struct MapKernel<A, B, F> {
+    f: F,
+    _p: PhantomData<(A, B)>,
+}
+
+pub(crate) fn map_block<A, B, F>(name: &str, f: F) -> Block
+where
+    A: Copy + Send + 'static,
+    B: Copy + Send + 'static,
+    F: Fn(A) -> B + Send + 'static,
+{
+    Block::new_sync(
+        BlockMetaBuilder::new(name).build(),
+        StreamIoBuilder::new()
+            .add_input("in", mem::size_of::<A>())
+            .add_output("out", mem::size_of::<B>())
+            .build(),
+        MessageIoBuilder::<MapKernel<A, B, F>>::new().build(),
+        MapKernel { f, _p: PhantomData },
+    )
+}
+
+#[async_trait]
+impl<A, B, F> SyncKernel for MapKernel<A, B, F>
+where
+    A: Copy + Send + 'static,
+    B: Copy + Send + 'static,
+    F: Fn(A) -> B + Send + 'static,
+{
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        let o = sio.output(0).slice::<B>();
+
+        let m = cmp::min(i.len(), o.len());
+        for (x, y) in i[0..m].iter().zip(o[0..m].iter_mut()) {
+            *y = (self.f)(*x);
+        }
+
+        if sio.input(0).finished() && m == i.len() {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(m);
+        sio.output(0).produce(m);
+
+        Ok(())
+    }
+}
+
+/// Sum of the inputs `in0`, `in1`, ...
+pub struct Add<A: ArithmeticSample> {
+    _p: PhantomData<A>,
+}
+
+impl<A: ArithmeticSample> Add<A> {
+    pub fn new(n_inputs: usize) -> Block {
+        fold_block("Add", n_inputs, A::add)
+    }
+}
+
+/// Difference `in0 - in1 - ...` of the inputs.
+pub struct Subtract<A: ArithmeticSample> {
+    _p: PhantomData<A>,
+}
+
+impl<A: ArithmeticSample> Subtract<A> {
+    pub fn new(n_inputs: usize) -> Block {
+        fold_block("Subtract", n_inputs, A::sub)
+    }
+}
+
+/// Product of the inputs `in0`, `in1`, ...
+pub struct Multiply<A: ArithmeticSample> {
+    _p: PhantomData<A>,
+}
+
+impl<A: ArithmeticSample> Multiply<A> {
+    pub fn new(n_inputs: usize) -> Block {
+        fold_block("Multiply", n_inputs, A::mul)
+    }
+}
+
+/// Quotient `in0 / in1 / ...` of the inputs.
+pub struct Divide<A: ArithmeticSample> {
+    _p: PhantomData<A>,
+}
+
+impl<A: ArithmeticSample> Divide<A> {
+    pub fn new(n_inputs: usize) -> Block {
+        fold_block("Divide", n_inputs, A::div)
+    }
+}
+
+/// Adds a constant to each item.
+pub struct AddConst<A: ArithmeticSample> {
+    _p: PhantomData<A>,
+}
+
+impl<A: ArithmeticSample> AddConst<A> {
+    pub fn new(value: A) -> Block {
+        map_block("AddConst", move |x: A| x.add(value))
+    }
+}
+
+/// Multiplies each item with a constant.
+pub struct MultiplyConst<A: ArithmeticSample> {
+    _p: PhantomData<A>,
+}
+
+impl<A: ArithmeticSample> MultiplyConst<A> {
+    pub fn new(value: A) -> Block {
+        map_block("MultiplyConst", move |x: A| x.mul(value))
+    }
+}
@@ -0,0 +1,267 @@
+use num_complex::Complex;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::f64::consts::PI;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::noise_source::gaussian;
+use crate::dsp::Nco;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Channel impairments for `Complex<f32>` streams.
+///
+/// The input passes, in this order, through
+/// - a multipath channel, i.e., an FIR filter with complex taps,
+/// - a sampling clock offset, resampling with linear interpolation,
+/// - a carrier frequency offset and a phase noise random walk, and
+/// - additive white Gaussian noise.
+///
+/// The message inputs `snr` (in dB) and `cfo` (in Hz) take a [Pmt::Double] to change the
+/// parameter while running and return the current value. Create it with a
+/// [ChannelModelBuilder].
+pub struct ChannelModel {
+    taps: Vec<Complex<f32>>,
+    history: Vec<Complex<f32>>,
+    step: f64,
+    delay: f64,
+    prev: Complex<f32>,
+    cur: Complex<f32>,
+    nco: Nco,
+    sample_rate: f64,
+    phase_noise: f32,
+    phase: f32,
+    snr: Option<f64>,
+    signal_power: f64,
+    noise_std: f32,
+    rng: StdRng,
+}
+
+impl ChannelModel {
+    fn snr_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Double(snr) => {
+                self.snr = Some(snr);
+                self.noise_std = noise_std(self.snr, self.signal_power);
+            }
+            Pmt::Null => {}
+            _ => warn!("ChannelModel: ignoring invalid snr {:?}", p),
+        }
+        Ok(self.snr.map_or(Pmt::Null, Pmt::Double))
+    }
+
+    fn cfo_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Double(f) => self.nco.set_freq(f, self.sample_rate),
+            Pmt::Null => {}
+            _ => warn!("ChannelModel: ignoring invalid cfo {:?}", p),
+        }
+        Ok(Pmt::Double(self.nco.freq(self.sample_rate)))
+    }
+
+    fn multipath(&mut self, x: Complex<f32>) -> Complex<f32> {
+        self.history.rotate_right(1);
+        self.history[0] = x;
+        self.history
+            .iter()
+            .zip(self.taps.iter())
+            .fold(Complex::new(0.0, 0.0), |acc, (x, t)| acc + x * t)
+    }
+
+    fn impair(&mut self, x: Complex<f32>) -> Complex<f32> {
+        let mut y = x * Complex::from_polar(1.0, self.nco.phase() as f32 + self.phase);
+        self.nco.step();
+        if self.phase_noise > 0.0 {
+            self.phase = (self.phase + self.phase_noise * gaussian(&mut self.rng))
+                .rem_euclid(2.0 * PI as f32);
+        }
+        if self.noise_std > 0.0 {
+            y += Complex::new(
+                self.noise_std * gaussian(&mut self.rng),
+                self.noise_std * gaussian(&mut self.rng),
+            );
+        }
+        y
+    }
+}
+
+// standard deviation of real and imaginary part of the noise
+fn noise_std(snr: Option<f64>, signal_power: f64) -> f32 {
+    snr.map_or(0.0, |snr| {
+        (signal_power / 10f64.powf(snr / 10.0) / 2.0).sqrt() as f32
+    })
+}
+
+#[async_trait]
+impl SyncKernel for ChannelModel {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<Complex<f32>>();
+
+        // `delay` is the distance of the next output from the newest input in input samples
+        let mut consumed = 0;
+        let mut produced = 0;
+        'outer: while produced < o.len() {
+            while self.delay < 0.0 {
+                if consumed == i.len() {
+                    break 'outer;
+                }
+                let x = self.multipath(i[consumed]);
+                consumed += 1;
+                self.prev = self.cur;
+                self.cur = x;
+                self.delay += 1.0;
+            }
+            let x = self.cur + (self.prev - self.cur) * self.delay as f32;
+            o[produced] = self.impair(x);
+            produced += 1;
+            self.delay -= self.step;
+        }
+
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(produced);
+
+        if sio.input(0).finished() && consumed == i.len() && self.delay < 0.0 {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct ChannelModelBuilder {
+    sample_rate: f64,
+    snr: Option<f64>,
+    signal_power: f64,
+    cfo: f64,
+    sco: f64,
+    taps: Vec<Complex<f32>>,
+    phase_noise: f32,
+    seed: Option<u64>,
+}
+
+impl ChannelModelBuilder {
+    /// Channel without impairments.
+    pub fn new(sample_rate: f64) -> ChannelModelBuilder {
+        ChannelModelBuilder {
+            sample_rate,
+            snr: None,
+            signal_power: 1.0,
+            cfo: 0.0,
+            sco: 0.0,
+            taps: vec![Complex::new(1.0, 0.0)],
+            phase_noise: 0.0,
+            seed: None,
+        }
+    }
+
+    /// Signal-to-noise ratio in dB, relative to the [signal power](Self::signal_power).
+    #[must_use]
+    pub fn snr(mut self, snr: f64) -> ChannelModelBuilder {
+        self.snr = Some(snr);
+        self
+    }
+
+    /// Power of the input signal that the SNR refers to, one by default.
+    #[must_use]
+    pub fn signal_power(mut self, signal_power: f64) -> ChannelModelBuilder {
+        self.signal_power = signal_power;
+        self
+    }
+
+    /// Carrier frequency offset in Hz.
+    #[must_use]
+    pub fn cfo(mut self, cfo: f64) -> ChannelModelBuilder {
+        self.cfo = cfo;
+        self
+    }
+
+    /// Relative sampling clock offset, e.g., `20e-6` for a receiver clock that is 20 ppm fast.
+    #[must_use]
+    pub fn sco(mut self, sco: f64) -> ChannelModelBuilder {
+        self.sco = sco;
+        self
+    }
+
+    /// Taps of the multipath channel, spaced by one sample.
+    #[must_use]
+    pub fn taps(mut self, taps: &[Complex<f32>]) -> ChannelModelBuilder {
+        self.taps = taps.to_vec();
+        self
+    }
+
+    /// Standard deviation of the phase increments of the phase noise in radians per sample.
+    #[must_use]
+    pub fn phase_noise(mut self, phase_noise: f32) -> ChannelModelBuilder {
+        self.phase_noise = phase_noise;
+        self
+    }
+
+    /// Seed for reproducible noise.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> ChannelModelBuilder {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Block {
+        assert!(self.sample_rate > 0.0, "sample rate has to be positive");
+        assert!(!self.taps.is_empty(), "channel needs at least one tap");
+        assert!(self.sco > -1.0, "sampling clock offset has to be > -1");
+
+        Block::new_sync(
+            BlockMetaBuilder::new("ChannelModel").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_output("out", mem::size_of::<Complex<f32>>())
+                .build(),
+            MessageIoBuilder::<ChannelModel>::new()
+                .add_sync_input("snr", ChannelModel::snr_handler)
+                .add_sync_input("cfo", ChannelModel::cfo_handler)
+                .build(),
+            ChannelModel {
+                history: vec![Complex::new(0.0, 0.0); self.taps.len()],
+                taps: self.taps,
+                step: 1.0 / (1.0 + self.sco),
+                delay: -1.0,
+                prev: Complex::new(0.0, 0.0),
+                cur: Complex::new(0.0, 0.0),
+                nco: Nco::new(self.cfo, self.sample_rate),
+                sample_rate: self.sample_rate,
+                phase_noise: self.phase_noise,
+                phase: 0.0,
+                snr: self.snr,
+                signal_power: self.signal_power,
+                noise_std: noise_std(self.snr, self.signal_power),
+                rng: self
+                    .seed
+                    .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64),
+            },
+        )
+    }
+}
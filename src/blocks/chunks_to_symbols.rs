@@ -0,0 +1,64 @@
+use num_complex::Complex;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::dsp::Constellation;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Maps symbols, i.e., chunks of bits given as `u8` items, to constellation points.
+///
+/// Chunks are taken modulo the number of points.
+pub struct ChunksToSymbols {
+    constellation: Constellation,
+}
+
+impl ChunksToSymbols {
+    pub fn new(constellation: Constellation) -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("ChunksToSymbols").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<u8>())
+                .add_output("out", mem::size_of::<Complex<f32>>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            ChunksToSymbols { constellation },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for ChunksToSymbols {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        let o = sio.output(0).slice::<Complex<f32>>();
+
+        let m = self.constellation.points().len();
+        let n = std::cmp::min(i.len(), o.len());
+        for (c, y) in i[0..n].iter().zip(o[0..n].iter_mut()) {
+            *y = self.constellation.map(*c as usize % m);
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use num_complex::Complex;
+use std::cmp;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::arithmetic::map_block;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Complex conjugate of each sample.
+pub struct Conjugate;
+
+impl Conjugate {
+    pub fn new() -> Block {
+        map_block("Conjugate", |x: Complex<f32>| x.conj())
+    }
+}
+
+/// Magnitude of each complex sample.
+pub struct ComplexToMag;
+
+impl ComplexToMag {
+    pub fn new() -> Block {
+        map_block("ComplexToMag", |x: Complex<f32>| x.norm())
+    }
+}
+
+/// Squared magnitude, i.e., power, of each complex sample.
+pub struct ComplexToMagSquared;
+
+impl ComplexToMagSquared {
+    pub fn new() -> Block {
+        map_block("ComplexToMagSquared", |x: Complex<f32>| x.norm_sqr())
+    }
+}
+
+/// Phase of each complex sample in `(-pi, pi]`.
+pub struct ComplexToArg;
+
+impl ComplexToArg {
+    pub fn new() -> Block {
+        map_block("ComplexToArg", |x: Complex<f32>| x.arg())
+    }
+}
+
+/// Real part of each complex sample.
+pub struct ComplexToReal;
+
+impl ComplexToReal {
+    pub fn new() -> Block {
+        map_block("ComplexToReal", |x: Complex<f32>| x.re)
+    }
+}
+
+/// Imaginary part of each complex sample.
+pub struct ComplexToImag;
+
+impl ComplexToImag {
+    pub fn new() -> Block {
+        map_block("ComplexToImag", |x: Complex<f32>| x.im)
+    }
+}
+
+/// Complex samples from the real parts on `re` and the imaginary parts on `im`.
+pub struct FloatToComplex;
+
+impl FloatToComplex {
+    pub fn new() -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("FloatToComplex").build(),
+            StreamIoBuilder::new()
+                .add_input("re", mem::size_of::<f32>())
+                .add_input("im", mem::size_of::<f32>())
+                .add_output("out", mem::size_of::<Complex<f32>>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            FloatToComplex,
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for FloatToComplex {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let re = sio.input(0).as_slice::<f32>();
+        let im = sio.input(1).as_slice::<f32>();
+        let o = sio.output(0).slice::<Complex<f32>>();
+
+        let m = cmp::min(cmp::min(re.len(), im.len()), o.len());
+        for ((y, r), i) in o[0..m].iter_mut().zip(re).zip(im) {
+            *y = Complex::new(*r, *i);
+        }
+
+        if (sio.input(0).finished() && m == re.len()) || (sio.input(1).finished() && m == im.len())
+        {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(m);
+        sio.input(1).consume(m);
+        sio.output(0).produce(m);
+
+        Ok(())
+    }
+}
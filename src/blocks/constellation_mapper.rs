@@ -0,0 +1,67 @@
+use num_complex::Complex;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::dsp::Constellation;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Maps bits, one per `u8` item, to constellation points.
+///
+/// Each symbol is formed by [bits_per_symbol](Constellation::bits_per_symbol) bits, most
+/// significant bit first. Only the least significant bit of the input items is used.
+pub struct ConstellationMapper {
+    constellation: Constellation,
+}
+
+impl ConstellationMapper {
+    pub fn new(constellation: Constellation) -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("ConstellationMapper").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<u8>())
+                .add_output("out", mem::size_of::<Complex<f32>>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            ConstellationMapper { constellation },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for ConstellationMapper {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        let o = sio.output(0).slice::<Complex<f32>>();
+
+        let k = self.constellation.bits_per_symbol();
+        let n = std::cmp::min(i.len() / k, o.len());
+        for (bits, y) in i.chunks_exact(k).zip(o[0..n].iter_mut()) {
+            let s = bits.iter().fold(0, |s, b| (s << 1) | (*b as usize & 1));
+            *y = self.constellation.map(s);
+        }
+
+        sio.input(0).consume(n * k);
+        sio.output(0).produce(n);
+
+        // an incomplete symbol at the end of the stream is dropped
+        if sio.input(0).finished() && i.len() - n * k < k {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use num_complex::Complex;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::dsp::Constellation;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Hard decision for the closest constellation point, outputting the bits of its symbol, one per
+/// `u8` item, most significant bit first.
+///
+/// It reverses the [ConstellationMapper](crate::blocks::ConstellationMapper).
+pub struct ConstellationSlicer {
+    constellation: Constellation,
+}
+
+impl ConstellationSlicer {
+    pub fn new(constellation: Constellation) -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("ConstellationSlicer").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_output("out", mem::size_of::<u8>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            ConstellationSlicer { constellation },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for ConstellationSlicer {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<u8>();
+
+        let k = self.constellation.bits_per_symbol();
+        let n = std::cmp::min(i.len(), o.len() / k);
+        for (x, bits) in i[0..n].iter().zip(o.chunks_exact_mut(k)) {
+            let s = self.constellation.slice(*x);
+            for (j, b) in bits.iter_mut().enumerate() {
+                *b = ((s >> (k - 1 - j)) & 1) as u8;
+            }
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n * k);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+use futures::FutureExt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::anyhow::Result;
+use crate::dsp::ConvolutionalCode;
+use crate::runtime::AsyncKernel;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIoBuilder;
+
+// bits of the bytes, most significant bit first
+fn unpack(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1))
+        .collect()
+}
+
+// bytes with the bits, most significant bit first, padding the last byte with zeros
+fn pack(bits: &[u8]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|c| {
+            c.iter()
+                .enumerate()
+                .fold(0, |b, (i, x)| b | ((x & 1) << (7 - i)))
+        })
+        .collect()
+}
+
+/// Encodes [Pmt::Blob] messages with a terminated [ConvolutionalCode].
+///
+/// Bytes are encoded most significant bit first. The coded bits are packed into bytes in the
+/// same order, padding the last byte with zeros.
+pub struct ConvolutionalEncoder {
+    code: ConvolutionalCode,
+}
+
+impl ConvolutionalEncoder {
+    pub fn new(code: ConvolutionalCode) -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("ConvolutionalEncoder").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_output("out")
+                .add_async_input("in", Self::handler)
+                .build(),
+            ConvolutionalEncoder { code },
+        )
+    }
+
+    fn handler<'a>(
+        &'a mut self,
+        mio: &'a mut MessageIo<Self>,
+        _meta: &'a mut BlockMeta,
+        p: Pmt,
+    ) -> Pin<Box<dyn Future<Output = Result<Pmt>> + Send + 'a>> {
+        async move {
+            match p {
+                Pmt::Blob(data) => {
+                    let coded = self.code.encode(&unpack(&data));
+                    mio.post(0, Pmt::Blob(pack(&coded))).await;
+                }
+                _ => warn!("ConvolutionalEncoder: ignoring invalid data {:?}", p),
+            }
+            Ok(Pmt::Null)
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for ConvolutionalEncoder {}
+
+/// Decodes [Pmt::Blob] messages from a [ConvolutionalEncoder] with the Viterbi algorithm.
+///
+/// The number of data bytes is derived from the message length, ignoring the padding.
+pub struct ViterbiDecoder {
+    code: ConvolutionalCode,
+}
+
+impl ViterbiDecoder {
+    pub fn new(code: ConvolutionalCode) -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("ViterbiDecoder").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_output("out")
+                .add_async_input("in", Self::handler)
+                .build(),
+            ViterbiDecoder { code },
+        )
+    }
+
+    fn handler<'a>(
+        &'a mut self,
+        mio: &'a mut MessageIo<Self>,
+        _meta: &'a mut BlockMeta,
+        p: Pmt,
+    ) -> Pin<Box<dyn Future<Output = Result<Pmt>> + Send + 'a>> {
+        async move {
+            match p {
+                Pmt::Blob(data) => {
+                    let bits = unpack(&data);
+                    let steps = bits.len() / self.code.polynomials().len();
+                    let tail = self.code.constraint_length() - 1;
+                    if steps < tail + 8 {
+                        debug!("ViterbiDecoder: dropping short message");
+                    } else {
+                        let n = (steps - tail) / 8;
+                        let decoded = self.code.decode(&bits[..self.code.coded_len(n * 8)]);
+                        mio.post(0, Pmt::Blob(pack(&decoded))).await;
+                    }
+                }
+                _ => warn!("ViterbiDecoder: ignoring invalid data {:?}", p),
+            }
+            Ok(Pmt::Null)
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for ViterbiDecoder {}
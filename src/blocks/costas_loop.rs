@@ -0,0 +1,125 @@
+use num_complex::Complex;
+use std::f32::consts::PI;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::dsp::LoopFilter;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Carrier phase and frequency recovery for BPSK (`order` 2) or QPSK (`order` 4) symbols.
+///
+/// The input is expected at one sample per symbol, e.g., from [SymbolSync](crate::blocks::SymbolSync),
+/// and is rotated onto the constellations of
+/// [Constellation::bpsk](crate::dsp::Constellation::bpsk) and
+/// [Constellation::qpsk](crate::dsp::Constellation::qpsk). As with any Costas loop, the phase
+/// is ambiguous by multiples of `2 * pi / order`.
+///
+/// The `bandwidth` message input sets the loop bandwidth in radians per symbol as [Pmt::Double]
+/// and `freq` the current frequency estimate in radians per symbol. Both return the current value.
+pub struct CostasLoop {
+    order: usize,
+    filter: LoopFilter,
+    phase: f32,
+}
+
+impl CostasLoop {
+    pub fn new(order: usize, bandwidth: f64) -> Block {
+        assert!(
+            order == 2 || order == 4,
+            "Costas loop supports orders 2 and 4"
+        );
+        Block::new_sync(
+            BlockMetaBuilder::new("CostasLoop").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_output("out", mem::size_of::<Complex<f32>>())
+                .build(),
+            MessageIoBuilder::<Self>::new()
+                .add_sync_input("bandwidth", Self::bandwidth_handler)
+                .add_sync_input("freq", Self::freq_handler)
+                .build(),
+            CostasLoop {
+                order,
+                filter: LoopFilter::critically_damped(bandwidth, 1.0),
+                phase: 0.0,
+            },
+        )
+    }
+
+    fn bandwidth_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Double(b) if b >= 0.0 => self.filter.set_bandwidth(b),
+            Pmt::Null => {}
+            _ => warn!("CostasLoop: ignoring invalid bandwidth {:?}", p),
+        }
+        Ok(Pmt::Double(self.filter.bandwidth()))
+    }
+
+    fn freq_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Double(f) => self.filter.set_freq(f),
+            Pmt::Null => {}
+            _ => warn!("CostasLoop: ignoring invalid frequency {:?}", p),
+        }
+        Ok(Pmt::Double(self.filter.freq()))
+    }
+
+    fn error(&self, y: Complex<f32>) -> f32 {
+        let e = if self.order == 2 {
+            y.re * y.im
+        } else {
+            y.re.signum() * y.im - y.im.signum() * y.re
+        };
+        e.clamp(-1.0, 1.0)
+    }
+}
+
+#[async_trait]
+impl SyncKernel for CostasLoop {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<Complex<f32>>();
+
+        let n = std::cmp::min(i.len(), o.len());
+        for (x, y) in i.iter().zip(o[0..n].iter_mut()) {
+            *y = x * Complex::from_polar(1.0, -self.phase);
+            let e = self.error(*y);
+            self.phase += self.filter.advance(e as f64) as f32;
+            self.phase = (self.phase + PI).rem_euclid(2.0 * PI) - PI;
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::IirSample;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Removes the DC component with `y[n] = x[n] - x[n-1] + pole * y[n-1]`.
+///
+/// The closer the pole is to one, the narrower the notch at DC.
+pub struct DcBlocker<A>
+where
+    A: IirSample,
+{
+    pole: f32,
+    x1: A,
+    y1: A,
+}
+
+impl<A> DcBlocker<A>
+where
+    A: IirSample,
+{
+    pub fn new(pole: f32) -> Block {
+        assert!(pole > 0.0 && pole < 1.0, "pole has to be in (0, 1)");
+        Block::new_sync(
+            BlockMetaBuilder::new("DcBlocker").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<A>())
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            DcBlocker {
+                pole,
+                x1: A::ZERO,
+                y1: A::ZERO,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl<A> SyncKernel for DcBlocker<A>
+where
+    A: IirSample,
+{
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        let o = sio.output(0).slice::<A>();
+
+        let n = std::cmp::min(i.len(), o.len());
+        for (x, y) in i[0..n].iter().zip(o[0..n].iter_mut()) {
+            self.y1 = (*x - self.x1 + self.y1 * self.pole).flush();
+            self.x1 = *x;
+            *y = self.y1;
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Distributes blocks of `block_len` items to the outputs `out0`, `out1`, ... in round-robin
+/// order.
+///
+/// Remaining items at the end of the stream that do not fill a round are dropped.
+pub struct Deinterleave {
+    n_outputs: usize,
+    item_size: usize,
+    block_len: usize,
+}
+
+impl Deinterleave {
+    pub fn new(item_size: usize, n_outputs: usize, block_len: usize) -> Block {
+        assert!(n_outputs > 0, "Deinterleave needs at least one output");
+        assert!(block_len > 0, "block length has to be positive");
+        let mut sio = StreamIoBuilder::new().add_input("in", item_size);
+        for i in 0..n_outputs {
+            sio = sio.add_output(&format!("out{}", i), item_size);
+        }
+        Block::new_sync(
+            BlockMetaBuilder::new("Deinterleave").build(),
+            sio.build(),
+            MessageIoBuilder::<Self>::new().build(),
+            Deinterleave {
+                n_outputs,
+                item_size,
+                block_len,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for Deinterleave {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let block_size = self.block_len * self.item_size;
+        let i = sio.input(0).slice::<u8>();
+
+        // number of rounds
+        let mut n = i.len() / (block_size * self.n_outputs);
+        for k in 0..self.n_outputs {
+            n = n.min(sio.output(k).slice::<u8>().len() / block_size);
+        }
+
+        for k in 0..self.n_outputs {
+            let o = sio.output(k).slice::<u8>();
+            for r in 0..n {
+                let start = (r * self.n_outputs + k) * block_size;
+                o[r * block_size..(r + 1) * block_size]
+                    .copy_from_slice(&i[start..start + block_size]);
+            }
+            sio.output(k).produce(n * self.block_len);
+        }
+
+        if sio.input(0).finished()
+            && i.len() - n * block_size * self.n_outputs < block_size * self.n_outputs
+        {
+            io.finished = true;
+        }
+        sio.input(0).consume(n * self.block_len * self.n_outputs);
+
+        Ok(())
+    }
+}
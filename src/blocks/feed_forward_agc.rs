@@ -0,0 +1,215 @@
+use std::collections::VecDeque;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::agc::update;
+use crate::blocks::AgcSample;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Automatic gain control that scales each sample by the peak envelope of the next `n`
+/// samples, i.e., the sample itself and `n - 1` samples of lookahead.
+///
+/// Unlike the feedback [Agc](crate::blocks::Agc), it reacts to bursts without overshoot.
+///
+/// The message inputs `reference` and `max_gain` take a [Pmt::Double] to change the parameter
+/// while running and return the current value. The message input `gain` returns the gain that
+/// was applied to the last output.
+pub struct FeedForwardAgc<A>
+where
+    A: AgcSample,
+{
+    n: usize,
+    reference: f32,
+    max_gain: f32,
+    gain: f32,
+    // sliding window maximum: absolute index and magnitude of the samples that can still
+    // become the peak, with decreasing magnitudes
+    peaks: VecDeque<(u64, f32)>,
+    // absolute index of the first input sample and of the next one to enter `peaks`
+    consumed: u64,
+    pushed: u64,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> FeedForwardAgc<A>
+where
+    A: AgcSample,
+{
+    pub fn new(n: usize, reference: f32, max_gain: f32) -> Block {
+        assert!(n > 0, "window has to contain at least one sample");
+        assert!(max_gain > 0.0, "maximum gain has to be positive");
+        Block::new_sync(
+            BlockMetaBuilder::new("FeedForwardAgc").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<A>())
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::<Self>::new()
+                .add_sync_input("reference", Self::reference_handler)
+                .add_sync_input("max_gain", Self::max_gain_handler)
+                .add_sync_input("gain", Self::gain_handler)
+                .build(),
+            FeedForwardAgc {
+                n,
+                reference,
+                max_gain,
+                gain: 1.0,
+                peaks: VecDeque::with_capacity(n),
+                consumed: 0,
+                pushed: 0,
+                _p: std::marker::PhantomData,
+            },
+        )
+    }
+
+    fn reference_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        Ok(update(
+            "FeedForwardAgc",
+            "reference",
+            &mut self.reference,
+            &p,
+        ))
+    }
+
+    fn max_gain_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        Ok(update("FeedForwardAgc", "max_gain", &mut self.max_gain, &p))
+    }
+
+    fn gain_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        _p: Pmt,
+    ) -> Result<Pmt> {
+        Ok(Pmt::Double(self.gain as f64))
+    }
+}
+
+#[async_trait]
+impl<A> SyncKernel for FeedForwardAgc<A>
+where
+    A: AgcSample,
+{
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        let o = sio.output(0).slice::<A>();
+        let finished = sio.input(0).finished();
+
+        // at the end of the stream, the window is truncated
+        let available = if finished {
+            i.len()
+        } else {
+            i.len().saturating_sub(self.n - 1)
+        };
+        let m = std::cmp::min(available, o.len());
+
+        for (j, y) in o[0..m].iter_mut().enumerate() {
+            let start = self.consumed + j as u64;
+            let end = std::cmp::min(start + self.n as u64, self.consumed + i.len() as u64);
+            while self.pushed < end {
+                let mag = i[(self.pushed - self.consumed) as usize].magnitude();
+                // like f32::max, NaNs are ignored
+                if !mag.is_nan() {
+                    while let Some(&(_, p)) = self.peaks.back() {
+                        if p > mag {
+                            break;
+                        }
+                        self.peaks.pop_back();
+                    }
+                    self.peaks.push_back((self.pushed, mag));
+                }
+                self.pushed += 1;
+            }
+            while let Some(&(k, _)) = self.peaks.front() {
+                if k >= start {
+                    break;
+                }
+                self.peaks.pop_front();
+            }
+            let peak = self.peaks.front().map_or(0.0, |p| p.1);
+            self.gain = if peak * self.max_gain > self.reference {
+                self.reference / peak
+            } else {
+                self.max_gain
+            };
+            *y = i[j] * self.gain;
+        }
+
+        sio.input(0).consume(m);
+        sio.output(0).produce(m);
+        self.consumed += m as u64;
+
+        if finished && m == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct FeedForwardAgcBuilder<A>
+where
+    A: AgcSample,
+{
+    n: usize,
+    reference: f32,
+    max_gain: f32,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> FeedForwardAgcBuilder<A>
+where
+    A: AgcSample,
+{
+    /// Window of `n` samples with a reference level of one and a maximum gain of 65536.
+    pub fn new(n: usize) -> FeedForwardAgcBuilder<A> {
+        FeedForwardAgcBuilder {
+            n,
+            reference: 1.0,
+            max_gain: 65536.0,
+            _p: std::marker::PhantomData,
+        }
+    }
+
+    /// Peak envelope of the output.
+    #[must_use]
+    pub fn reference(mut self, reference: f32) -> FeedForwardAgcBuilder<A> {
+        self.reference = reference;
+        self
+    }
+
+    #[must_use]
+    pub fn max_gain(mut self, max_gain: f32) -> FeedForwardAgcBuilder<A> {
+        self.max_gain = max_gain;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        FeedForwardAgc::<A>::new(self.n, self.reference, self.max_gain)
+    }
+}
@@ -1,10 +1,12 @@
 use rustfft::num_complex::Complex;
 use rustfft::{self, FftPlanner};
+use serde::Deserialize;
 use std::cmp;
 use std::mem::size_of;
 use std::sync::Arc;
 
 use crate::anyhow::Result;
+use crate::dsp::Window;
 use crate::runtime::AsyncKernel;
 use crate::runtime::Block;
 use crate::runtime::BlockMeta;
@@ -15,26 +17,87 @@ use crate::runtime::StreamIo;
 use crate::runtime::StreamIoBuilder;
 use crate::runtime::WorkIo;
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FftDirection {
+    Forward,
+    Inverse,
+}
+
+/// Scaling of the output of the [Fft].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FftNormalization {
+    /// No scaling, like FFTW.
+    None,
+    /// Scale by `1/sqrt(N)`, which makes the transform unitary.
+    Sqrt,
+    /// Scale by `1/N`.
+    Full,
+}
+
+/// Fast Fourier transform of frames of `len` samples.
+///
+/// By default, the block has scalar ports and transforms as many whole frames as there are
+/// samples in the buffers. With vector items, each item is one frame, so the runtime keeps the
+/// frames aligned, e.g., after a [StreamToVector](crate::blocks::StreamToVector).
 pub struct Fft {
+    len: usize,
+    direction: FftDirection,
+    vector: bool,
     plan: Arc<dyn rustfft::Fft<f32>>,
-    scratch: [Complex<f32>; 2048 * 10],
+    scratch: Vec<Complex<f32>>,
+    window: Option<Vec<f32>>,
+    shift: bool,
+    scale: Option<f32>,
 }
 
 impl Fft {
+    /// 2048-point forward transform without window, shift, or normalization.
     pub fn new() -> Block {
+        FftBuilder::new().build()
+    }
+
+    pub fn with_options(
+        len: usize,
+        direction: FftDirection,
+        window: Option<Window>,
+        shift: bool,
+        normalization: FftNormalization,
+        vector: bool,
+    ) -> Block {
+        assert!(len > 0, "FFT size has to be positive");
+
         let mut planner = FftPlanner::<f32>::new();
-        let plan = planner.plan_fft_forward(2048);
+        let plan = match direction {
+            FftDirection::Forward => planner.plan_fft_forward(len),
+            FftDirection::Inverse => planner.plan_fft_inverse(len),
+        };
+        let scratch = vec![Complex::new(0.0, 0.0); plan.get_inplace_scratch_len()];
+        let scale = match normalization {
+            FftNormalization::None => None,
+            FftNormalization::Sqrt => Some(1.0 / (len as f32).sqrt()),
+            FftNormalization::Full => Some(1.0 / len as f32),
+        };
+
+        let vlen = if vector { len } else { 1 };
 
         Block::new_async(
             BlockMetaBuilder::new("Fft").build(),
             StreamIoBuilder::new()
-                .add_input("in", size_of::<Complex<f32>>())
-                .add_output("out", size_of::<Complex<f32>>())
+                .add_vector_input("in", size_of::<Complex<f32>>(), vlen)
+                .add_vector_output("out", size_of::<Complex<f32>>(), vlen)
                 .build(),
             MessageIoBuilder::<Fft>::new().build(),
             Fft {
+                len,
+                direction,
+                vector,
                 plan,
-                scratch: [Complex::new(0.0, 0.0); 2048 * 10],
+                scratch,
+                window: window.map(|w| w.taps(len)),
+                shift,
+                scale,
             },
         )
     }
@@ -53,9 +116,9 @@ impl AsyncKernel for Fft {
         let o = sio.output(0).slice::<Complex<f32>>();
 
         let m = cmp::min(i.len(), o.len());
-        let n = (m / 2048) * 2048;
+        let n = (m / self.len) * self.len;
 
-        if sio.input(0).finished() {
+        if sio.input(0).finished() && n == (i.len() / self.len) * self.len {
             io.finished = true;
         }
 
@@ -63,25 +126,114 @@ impl AsyncKernel for Fft {
             return Ok(());
         }
 
+        match &self.window {
+            Some(w) => {
+                for (k, (o, i)) in o[0..n].iter_mut().zip(i[0..n].iter()).enumerate() {
+                    *o = i * w[k % self.len];
+                }
+            }
+            None => o[0..n].copy_from_slice(&i[0..n]),
+        }
+
+        let inverse = self.direction == FftDirection::Inverse;
+        if self.shift && inverse {
+            // move the DC bin from the center back to the start
+            for frame in o[0..n].chunks_exact_mut(self.len) {
+                frame.rotate_left(self.len / 2);
+            }
+        }
+
         self.plan
-            .process_outofplace_with_scratch(&mut i[0..n], &mut o[0..n], &mut self.scratch);
+            .process_with_scratch(&mut o[0..n], &mut self.scratch);
 
-        sio.input(0).consume(n);
-        sio.output(0).produce(n);
+        if (self.shift && !inverse) || self.scale.is_some() {
+            for frame in o[0..n].chunks_exact_mut(self.len) {
+                if self.shift && !inverse {
+                    frame.rotate_right(self.len / 2);
+                }
+                if let Some(s) = self.scale {
+                    frame.iter_mut().for_each(|x| *x *= s);
+                }
+            }
+        }
+
+        let items = if self.vector { n / self.len } else { n };
+        sio.input(0).consume(items);
+        sio.output(0).produce(items);
 
         Ok(())
     }
 }
 
-pub struct FftBuilder {}
+pub struct FftBuilder {
+    len: usize,
+    direction: FftDirection,
+    window: Option<Window>,
+    shift: bool,
+    normalization: FftNormalization,
+    vector: bool,
+}
 
 impl FftBuilder {
     pub fn new() -> FftBuilder {
-        FftBuilder {}
+        FftBuilder {
+            len: 2048,
+            direction: FftDirection::Forward,
+            window: None,
+            shift: false,
+            normalization: FftNormalization::None,
+            vector: false,
+        }
+    }
+
+    #[must_use]
+    pub fn fft_size(mut self, len: usize) -> FftBuilder {
+        self.len = len;
+        self
+    }
+
+    #[must_use]
+    pub fn direction(mut self, direction: FftDirection) -> FftBuilder {
+        self.direction = direction;
+        self
+    }
+
+    #[must_use]
+    pub fn window(mut self, window: Window) -> FftBuilder {
+        self.window = Some(window);
+        self
+    }
+
+    /// Move the DC bin to the center of the output, or, for the inverse transform, expect it in
+    /// the center of the input.
+    #[must_use]
+    pub fn shift(mut self, shift: bool) -> FftBuilder {
+        self.shift = shift;
+        self
+    }
+
+    #[must_use]
+    pub fn normalization(mut self, normalization: FftNormalization) -> FftBuilder {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Use frames as vector items instead of scalar ports.
+    #[must_use]
+    pub fn vector(mut self, vector: bool) -> FftBuilder {
+        self.vector = vector;
+        self
     }
 
     pub fn build(self) -> Block {
-        Fft::new()
+        Fft::with_options(
+            self.len,
+            self.direction,
+            self.window,
+            self.shift,
+            self.normalization,
+            self.vector,
+        )
     }
 }
 
@@ -14,7 +14,7 @@ use crate::runtime::StreamIoBuilder;
 use crate::runtime::WorkIo;
 
 pub struct FileSink {
-    // item_size: usize,
+    item_size: usize,
     file_name: String,
     file: Option<File>,
     n_written: usize,
@@ -22,12 +22,12 @@ pub struct FileSink {
 
 impl FileSink {
     pub fn new(item_size: usize, file_name: &str) -> Block {
-        debug_assert_eq!(item_size, 1);
         Block::new_async(
             BlockMetaBuilder::new("FileSink").build(),
             StreamIoBuilder::new().add_input("in", item_size).build(),
             MessageIoBuilder::new().build(),
             FileSink {
+                item_size,
                 file_name: file_name.into(),
                 file: None,
                 n_written: 0,
@@ -57,7 +57,7 @@ impl AsyncKernel for FileSink {
         }
 
         self.n_written += i.len();
-        sio.input(0).consume(i.len());
+        sio.input(0).consume(i.len() / self.item_size);
         Ok(())
     }
 
@@ -11,8 +11,9 @@ use crate::runtime::StreamIo;
 use crate::runtime::StreamIoBuilder;
 use crate::runtime::WorkIo;
 
+/// Reads items from a file, dropping an incomplete item at its end.
 pub struct FileSource {
-    // item_size: usize,
+    item_size: usize,
     file_name: String,
     file: Option<async_fs::File>,
     file_size: usize,
@@ -21,13 +22,12 @@ pub struct FileSource {
 
 impl FileSource {
     pub fn new(item_size: usize, file_name: String) -> Block {
-        // todo
-        debug_assert_eq!(item_size, 1);
         Block::new_async(
             BlockMetaBuilder::new("FileSource").build(),
             StreamIoBuilder::new().add_output("out", item_size).build(),
             MessageIoBuilder::new().build(),
             FileSource {
+                item_size,
                 file_name,
                 file_size: 0,
                 file: None,
@@ -49,6 +49,7 @@ impl AsyncKernel for FileSource {
         let out = sio.output(0).slice::<u8>();
 
         let n_read = std::cmp::min(out.len(), self.file_size - self.n_produced);
+        let n_read = n_read - n_read % self.item_size;
 
         match self
             .file
@@ -59,7 +60,7 @@ impl AsyncKernel for FileSource {
         {
             Ok(_) => {
                 self.n_produced += n_read;
-                sio.output(0).produce(n_read);
+                sio.output(0).produce(n_read / self.item_size);
             }
             Err(_) => panic!("Error while reading file"),
         }
@@ -78,6 +79,7 @@ impl AsyncKernel for FileSource {
     ) -> Result<()> {
         let metadata = std::fs::metadata(self.file_name.clone()).unwrap();
         self.file_size = metadata.len() as usize;
+        self.file_size -= self.file_size % self.item_size;
 
         self.file = Some(async_fs::File::open(self.file_name.clone()).await.unwrap());
         Ok(())
@@ -1,6 +1,6 @@
-use std::intrinsics::fadd_fast;
-use std::intrinsics::fmul_fast;
+use num_complex::Complex;
 use std::mem;
+use std::ops::{Add, Mul};
 
 use crate::anyhow::Result;
 use crate::runtime::Block;
@@ -8,41 +8,141 @@ use crate::runtime::BlockMeta;
 use crate::runtime::BlockMetaBuilder;
 use crate::runtime::MessageIo;
 use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
 use crate::runtime::StreamIo;
 use crate::runtime::StreamIoBuilder;
 use crate::runtime::SyncKernel;
 use crate::runtime::WorkIo;
 
-pub trait HasFirImpl: Copy + Send + 'static {}
-impl HasFirImpl for f32 {}
+/// Taps of a [Fir] filter.
+pub trait FirTap: Copy + Send + 'static {
+    /// Parses taps from a message, returning `None` if the message does not hold taps.
+    fn from_pmt(p: &Pmt) -> Option<Vec<Self>>;
+}
+
+impl FirTap for f32 {
+    fn from_pmt(p: &Pmt) -> Option<Vec<Self>> {
+        match p {
+            Pmt::VecF32(v) => Some(v.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Complex taps are passed as interleaved real and imaginary parts.
+impl FirTap for Complex<f32> {
+    fn from_pmt(p: &Pmt) -> Option<Vec<Self>> {
+        match p {
+            Pmt::VecF32(v) if v.len() % 2 == 0 => Some(
+                v.chunks_exact(2)
+                    .map(|c| Complex::new(c[0], c[1]))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Items that can be filtered with taps of type `T`.
+pub trait HasFirImpl<T: FirTap>: Copy + Send + 'static {
+    /// Dot product of `taps` with the first `taps.len()` items of `input`.
+    fn dot(input: &[Self], taps: &[T]) -> Self;
+}
+
+impl HasFirImpl<f32> for f32 {
+    fn dot(input: &[f32], taps: &[f32]) -> f32 {
+        dot::<_, _, 8>(input, taps, 0.0)
+    }
+}
+
+impl HasFirImpl<f32> for Complex<f32> {
+    fn dot(input: &[Complex<f32>], taps: &[f32]) -> Complex<f32> {
+        dot::<_, _, 4>(input, taps, Complex::new(0.0, 0.0))
+    }
+}
+
+impl HasFirImpl<Complex<f32>> for Complex<f32> {
+    fn dot(input: &[Complex<f32>], taps: &[Complex<f32>]) -> Complex<f32> {
+        dot::<_, _, 4>(input, taps, Complex::new(0.0, 0.0))
+    }
+}
 
-pub struct Fir<A, const N: usize>
+// Uses `L` independent accumulators, which breaks the dependency chain of the
+// sum and lets LLVM vectorize the loop without fast-math intrinsics.
+#[inline(always)]
+fn dot<A, T, const L: usize>(input: &[A], taps: &[T], zero: A) -> A
 where
-    A: HasFirImpl,
+    A: Copy + Add<Output = A> + Mul<T, Output = A>,
+    T: Copy,
 {
-    taps: [A; N],
+    let input = &input[..taps.len()];
+    let n = taps.len() / L * L;
+
+    let mut acc = [zero; L];
+    for (i, t) in input[..n].chunks_exact(L).zip(taps[..n].chunks_exact(L)) {
+        for l in 0..L {
+            acc[l] = acc[l] + i[l] * t[l];
+        }
+    }
+
+    let mut sum = acc.iter().fold(zero, |a, b| a + *b);
+    for (i, t) in input[n..].iter().zip(taps[n..].iter()) {
+        sum = sum + *i * *t;
+    }
+    sum
 }
 
-impl<A, const N: usize> Fir<A, N>
+/// FIR filter with input items of type `A` and taps of type `T`.
+///
+/// Output `k` is the dot product of the taps with the input items `kD..kD + taps.len()`, where
+/// `D` is the decimation. The taps can be replaced at runtime through the `taps` message input.
+pub struct Fir<A, T>
 where
-    A: HasFirImpl,
-    Fir<A, N>: SyncKernel,
+    A: HasFirImpl<T>,
+    T: FirTap,
 {
-    pub fn new(taps: &[A; N]) -> Block {
-        Block::new_sync(
-            BlockMetaBuilder::new("Fir").build(),
-            StreamIoBuilder::new()
-                .add_input("in", mem::size_of::<A>())
-                .add_output("out", mem::size_of::<A>())
-                .build(),
-            MessageIoBuilder::<Fir<A, N>>::new().build(),
-            Fir { taps: *taps },
-        )
+    taps: Vec<T>,
+    decimation: usize,
+    // input samples to skip before the next output
+    skip: usize,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> Fir<A, A>
+where
+    A: HasFirImpl<A> + FirTap,
+{
+    /// FIR filter with taps of the same type as the items.
+    pub fn new(taps: &[A]) -> Block {
+        FirBuilder::<A, A>::new(taps).build()
+    }
+}
+
+impl<A, T> Fir<A, T>
+where
+    A: HasFirImpl<T>,
+    T: FirTap,
+{
+    fn taps_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match T::from_pmt(&p) {
+            Some(taps) if !taps.is_empty() => self.taps = taps,
+            _ => warn!("Fir: ignoring invalid taps {:?}", p),
+        }
+        Ok(Pmt::Null)
     }
 }
 
 #[async_trait]
-impl<const N: usize> SyncKernel for Fir<f32, N> {
+impl<A, T> SyncKernel for Fir<A, T>
+where
+    A: HasFirImpl<T>,
+    T: FirTap,
+{
     fn work(
         &mut self,
         io: &mut WorkIo,
@@ -50,35 +150,82 @@ impl<const N: usize> SyncKernel for Fir<f32, N> {
         _mio: &mut MessageIo<Self>,
         _meta: &mut BlockMeta,
     ) -> Result<()> {
-        let i = sio.input(0).slice::<f32>();
-        let o = sio.output(0).slice::<f32>();
-
-        if i.len() >= N {
-            let n = std::cmp::min(i.len() + 1 - N, o.len());
-
-            unsafe {
-                for k in 0..n {
-                    let mut sum = 0.0;
-                    for t in 0..N {
-                        sum = fadd_fast(
-                            sum,
-                            fmul_fast(*i.get_unchecked(k + t), *self.taps.get_unchecked(t)),
-                        );
-                    }
-                    *o.get_unchecked_mut(k) = sum;
-                }
-            }
-
-            sio.input(0).consume(n);
-            sio.output(0).produce(n);
-
-            if sio.input(0).finished() && n == i.len() + 1 - N {
-                io.finished = true;
-            }
-        } else if sio.input(0).finished() {
+        let i = sio.input(0).slice::<A>();
+        let o = sio.output(0).slice::<A>();
+
+        let n_taps = self.taps.len();
+
+        let mut j = self.skip;
+        let mut produced = 0;
+        while j + n_taps <= i.len() && produced < o.len() {
+            o[produced] = A::dot(&i[j..], &self.taps);
+            produced += 1;
+            j += self.decimation;
+        }
+
+        let consumed = std::cmp::min(j, i.len());
+        self.skip = j - consumed;
+
+        if sio.input(0).finished() && j + n_taps > i.len() {
             io.finished = true;
         }
 
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(produced);
+
         Ok(())
     }
 }
+
+/// Builds a [Fir] filter, e.g., `FirBuilder::<Complex<f32>, f32>::new(&taps).build()`.
+pub struct FirBuilder<A, T>
+where
+    A: HasFirImpl<T>,
+    T: FirTap,
+{
+    taps: Vec<T>,
+    decimation: usize,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A, T> FirBuilder<A, T>
+where
+    A: HasFirImpl<T>,
+    T: FirTap,
+{
+    pub fn new(taps: &[T]) -> FirBuilder<A, T> {
+        assert!(!taps.is_empty(), "FIR filter needs at least one tap");
+        FirBuilder {
+            taps: taps.to_vec(),
+            decimation: 1,
+            _p: std::marker::PhantomData,
+        }
+    }
+
+    /// Only compute every `decimation`-th output.
+    #[must_use]
+    pub fn decimation(mut self, decimation: usize) -> FirBuilder<A, T> {
+        assert!(decimation > 0, "decimation has to be positive");
+        self.decimation = decimation;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("Fir").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<A>())
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::<Fir<A, T>>::new()
+                .add_sync_input("taps", Fir::taps_handler)
+                .build(),
+            Fir {
+                taps: self.taps,
+                decimation: self.decimation,
+                skip: 0,
+                _p: std::marker::PhantomData,
+            },
+        )
+    }
+}
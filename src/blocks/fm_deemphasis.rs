@@ -0,0 +1,79 @@
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// First-order lowpass that undoes the pre-emphasis of FM broadcast.
+///
+/// The time constant `tau` is 75 us in the Americas and South Korea and 50 us elsewhere. The
+/// filter is designed with the bilinear transform, pre-warped to the corner frequency
+/// `1 / (2 pi tau)`, and has unit gain at DC.
+pub struct FmDeemphasis {
+    b0: f32,
+    p1: f32,
+    last_in: f32,
+    last_out: f32,
+}
+
+impl FmDeemphasis {
+    pub fn new(sample_rate: f64, tau: f64) -> Block {
+        assert!(sample_rate > 0.0, "sample rate has to be positive");
+        assert!(tau > 0.0, "time constant has to be positive");
+
+        let w = 2.0 * sample_rate * (1.0 / (2.0 * tau * sample_rate)).tan();
+        let k = -w / (2.0 * sample_rate);
+
+        Block::new_sync(
+            BlockMetaBuilder::new("FmDeemphasis").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<f32>())
+                .add_output("out", mem::size_of::<f32>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            FmDeemphasis {
+                b0: (-k / (1.0 - k)) as f32,
+                p1: ((1.0 + k) / (1.0 - k)) as f32,
+                last_in: 0.0,
+                last_out: 0.0,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for FmDeemphasis {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<f32>();
+        let o = sio.output(0).slice::<f32>();
+
+        let n = std::cmp::min(i.len(), o.len());
+        for (x, y) in i[0..n].iter().zip(o[0..n].iter_mut()) {
+            self.last_out = self.b0 * (x + self.last_in) + self.p1 * self.last_out;
+            self.last_in = *x;
+            *y = self.last_out;
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
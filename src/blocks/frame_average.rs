@@ -0,0 +1,169 @@
+use serde::Deserialize;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Averaging of a [FrameAverage].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AverageMode {
+    /// Exponential average with weight `alpha` for the latest frame.
+    Exponential(f32),
+    /// Mean of `n` consecutive frames, restarting after each output.
+    Linear(usize),
+    /// Maximum of all frames since the start or the last reset.
+    MaxHold,
+}
+
+/// Averages frames of `len` `f32` samples element-wise, e.g., spectra for a waterfall.
+///
+/// One frame is output for every `decimation` input frames. For [AverageMode::Linear], this
+/// is in addition to the `n` frames of each mean, i.e., the block outputs one of every
+/// `decimation` means.
+///
+/// Non-finite samples are ignored, i.e., each bin only averages its finite samples and is zero
+/// if there are none. The `reset` message input restarts the average.
+///
+/// With `vector`, the ports use frames as vector items instead of `f32` samples, like the
+/// [Fft](crate::blocks::Fft).
+pub struct FrameAverage {
+    mode: AverageMode,
+    decimation: usize,
+    vector: bool,
+    avg: Vec<f32>,
+    // finite samples in the average of each bin
+    counts: Vec<usize>,
+    // frames in the average and since the last output
+    n_avg: usize,
+    n_out: usize,
+}
+
+impl FrameAverage {
+    pub fn new(len: usize, mode: AverageMode, decimation: usize, vector: bool) -> Block {
+        assert!(len > 0, "frame length has to be positive");
+        assert!(decimation > 0, "decimation has to be positive");
+        match mode {
+            AverageMode::Exponential(alpha) => {
+                assert!(alpha > 0.0 && alpha <= 1.0, "alpha has to be in (0, 1]")
+            }
+            AverageMode::Linear(n) => assert!(n > 0, "average needs at least one frame"),
+            AverageMode::MaxHold => {}
+        }
+        let vlen = if vector { len } else { 1 };
+        Block::new_sync(
+            BlockMetaBuilder::new("FrameAverage").build(),
+            StreamIoBuilder::new()
+                .add_vector_input("in", mem::size_of::<f32>(), vlen)
+                .add_vector_output("out", mem::size_of::<f32>(), vlen)
+                .build(),
+            MessageIoBuilder::new()
+                .add_sync_input("reset", Self::reset)
+                .build(),
+            FrameAverage {
+                mode,
+                decimation,
+                vector,
+                avg: vec![0.0; len],
+                counts: vec![0; len],
+                n_avg: 0,
+                n_out: 0,
+            },
+        )
+    }
+
+    fn reset(&mut self, _mio: &mut MessageIo<Self>, _meta: &mut BlockMeta, _p: Pmt) -> Result<Pmt> {
+        self.n_avg = 0;
+        Ok(Pmt::Null)
+    }
+
+    // adds a frame and returns whether an average is complete
+    fn add(&mut self, frame: &[f32]) -> bool {
+        if self.n_avg == 0 {
+            self.counts.iter_mut().for_each(|c| *c = 0);
+        }
+        for ((a, c), x) in self.avg.iter_mut().zip(&mut self.counts).zip(frame) {
+            if !x.is_finite() {
+                continue;
+            }
+            if *c == 0 {
+                *a = *x;
+            } else {
+                match self.mode {
+                    AverageMode::Exponential(alpha) => *a += alpha * (x - *a),
+                    AverageMode::Linear(_) => *a += x,
+                    AverageMode::MaxHold => *a = a.max(*x),
+                }
+            }
+            *c += 1;
+        }
+        self.n_avg += 1;
+
+        match self.mode {
+            AverageMode::Linear(n) => self.n_avg == n,
+            _ => true,
+        }
+    }
+}
+
+#[async_trait]
+impl SyncKernel for FrameAverage {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<f32>();
+        let o = sio.output(0).slice::<f32>();
+        let len = self.avg.len();
+
+        let mut consumed = 0;
+        let mut produced = 0;
+        while (consumed + 1) * len <= i.len() {
+            // the frame might complete an output
+            if (produced + 1) * len > o.len() {
+                break;
+            }
+            if self.add(&i[consumed * len..(consumed + 1) * len]) {
+                self.n_out += 1;
+                if self.n_out == self.decimation {
+                    let out = &mut o[produced * len..(produced + 1) * len];
+                    for ((y, a), c) in out.iter_mut().zip(&self.avg).zip(&self.counts) {
+                        *y = match (self.mode, *c) {
+                            (_, 0) => 0.0,
+                            (AverageMode::Linear(_), c) => a / c as f32,
+                            _ => *a,
+                        };
+                    }
+                    self.n_out = 0;
+                    produced += 1;
+                }
+                if let AverageMode::Linear(_) = self.mode {
+                    self.n_avg = 0;
+                }
+            }
+            consumed += 1;
+        }
+
+        if sio.input(0).finished() && (consumed + 1) * len > i.len() {
+            io.finished = true;
+        }
+
+        let items = if self.vector { 1 } else { len };
+        sio.input(0).consume(consumed * items);
+        sio.output(0).produce(produced * items);
+
+        Ok(())
+    }
+}
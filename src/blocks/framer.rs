@@ -0,0 +1,120 @@
+use futures::FutureExt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::anyhow::Result;
+use crate::dsp::Crc;
+use crate::runtime::AsyncKernel;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIoBuilder;
+
+/// Frames [Pmt::Blob] messages with a header and an optional checksum.
+///
+/// A frame is the payload length as big-endian `u16`, followed by the payload and the
+/// [Crc] of both. Frames are posted to the `out` message output.
+pub struct Framer {
+    crc: Option<Crc>,
+}
+
+impl Framer {
+    pub fn new(crc: Option<Crc>) -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("Framer").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_output("out")
+                .add_async_input("in", Self::handler)
+                .build(),
+            Framer { crc },
+        )
+    }
+
+    fn handler<'a>(
+        &'a mut self,
+        mio: &'a mut MessageIo<Self>,
+        _meta: &'a mut BlockMeta,
+        p: Pmt,
+    ) -> Pin<Box<dyn Future<Output = Result<Pmt>> + Send + 'a>> {
+        async move {
+            match p {
+                Pmt::Blob(payload) if payload.len() <= u16::MAX as usize => {
+                    let mut frame = Vec::with_capacity(payload.len() + 6);
+                    frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+                    frame.extend_from_slice(&payload);
+                    if let Some(crc) = self.crc {
+                        crc.append(&mut frame);
+                    }
+                    mio.post(0, Pmt::Blob(frame)).await;
+                }
+                _ => warn!("Framer: ignoring invalid payload {:?}", p),
+            }
+            Ok(Pmt::Null)
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for Framer {}
+
+/// Extracts the payload of frames from a [Framer].
+///
+/// Trailing bytes after the frame, e.g., padding added by a channel code, are ignored. Frames
+/// with an invalid length or checksum are dropped.
+pub struct Deframer {
+    crc: Option<Crc>,
+}
+
+impl Deframer {
+    pub fn new(crc: Option<Crc>) -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("Deframer").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_output("out")
+                .add_async_input("in", Self::handler)
+                .build(),
+            Deframer { crc },
+        )
+    }
+
+    fn payload(&self, frame: &[u8]) -> Option<Vec<u8>> {
+        let crc_len = self.crc.map_or(0, |c| c.size());
+        if frame.len() < 2 + crc_len {
+            return None;
+        }
+        let len = u16::from_be_bytes([frame[0], frame[1]]) as usize;
+        let frame = frame.get(..2 + len + crc_len)?;
+        match self.crc {
+            Some(crc) if !crc.check(frame) => None,
+            _ => Some(frame[2..2 + len].to_vec()),
+        }
+    }
+
+    fn handler<'a>(
+        &'a mut self,
+        mio: &'a mut MessageIo<Self>,
+        _meta: &'a mut BlockMeta,
+        p: Pmt,
+    ) -> Pin<Box<dyn Future<Output = Result<Pmt>> + Send + 'a>> {
+        async move {
+            match p {
+                Pmt::Blob(frame) => match self.payload(&frame) {
+                    Some(payload) => mio.post(0, Pmt::Blob(payload)).await,
+                    None => debug!("Deframer: dropping invalid frame"),
+                },
+                _ => warn!("Deframer: ignoring invalid frame {:?}", p),
+            }
+            Ok(Pmt::Null)
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for Deframer {}
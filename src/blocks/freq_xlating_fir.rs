@@ -0,0 +1,176 @@
+use num_complex::Complex;
+use std::f64::consts::PI;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::HasFirImpl;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Shifts `center_freq` to DC, filters, and decimates in one pass.
+///
+/// Instead of mixing every input sample, the lowpass prototype is shifted to a bandpass at the
+/// center frequency and only the decimated outputs are rotated. The `freq` message input retunes
+/// the center frequency (in Hz, as [Pmt::Double]) and returns the current one.
+pub struct FreqXlatingFir {
+    taps: Vec<f32>,
+    rotated_taps: Vec<Complex<f32>>,
+    decimation: usize,
+    center_freq: f64,
+    sample_rate: f64,
+    // phase of the output rotator
+    phase: f64,
+    // input samples to skip before the next output
+    skip: usize,
+}
+
+impl FreqXlatingFir {
+    pub fn new(taps: &[f32], decimation: usize, center_freq: f64, sample_rate: f64) -> Block {
+        assert!(!taps.is_empty(), "FIR filter needs at least one tap");
+        assert!(decimation > 0, "decimation has to be positive");
+        assert!(sample_rate > 0.0, "sample rate has to be positive");
+
+        let mut fir = FreqXlatingFir {
+            taps: taps.to_vec(),
+            rotated_taps: Vec::new(),
+            decimation,
+            center_freq,
+            sample_rate,
+            phase: 0.0,
+            skip: 0,
+        };
+        fir.rotate_taps();
+
+        Block::new_sync(
+            BlockMetaBuilder::new("FreqXlatingFir").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_output("out", mem::size_of::<Complex<f32>>())
+                .build(),
+            MessageIoBuilder::<Self>::new()
+                .add_sync_input("freq", Self::freq_handler)
+                .build(),
+            fir,
+        )
+    }
+
+    fn omega(&self) -> f64 {
+        2.0 * PI * self.center_freq / self.sample_rate
+    }
+
+    // taps[k] * exp(-j w k) mixes input sample k of the window down
+    fn rotate_taps(&mut self) {
+        let w = self.omega();
+        self.rotated_taps = self
+            .taps
+            .iter()
+            .enumerate()
+            .map(|(k, t)| Complex::from_polar(*t, (-w * k as f64) as f32))
+            .collect();
+    }
+
+    fn freq_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Double(f) => {
+                self.center_freq = f;
+                self.rotate_taps();
+            }
+            Pmt::Null => {}
+            _ => warn!("FreqXlatingFir: ignoring invalid frequency {:?}", p),
+        }
+        Ok(Pmt::Double(self.center_freq))
+    }
+}
+
+#[async_trait]
+impl SyncKernel for FreqXlatingFir {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<Complex<f32>>();
+
+        let n_taps = self.rotated_taps.len();
+        let step = -self.omega() * self.decimation as f64;
+
+        let mut j = self.skip;
+        let mut produced = 0;
+        while j + n_taps <= i.len() && produced < o.len() {
+            let y = Complex::<f32>::dot(&i[j..], &self.rotated_taps);
+            o[produced] = y * Complex::from_polar(1.0, self.phase as f32);
+            produced += 1;
+            j += self.decimation;
+            self.phase = (self.phase + step).rem_euclid(2.0 * PI);
+        }
+
+        let consumed = std::cmp::min(j, i.len());
+        self.skip = j - consumed;
+
+        if sio.input(0).finished() && j + n_taps > i.len() {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(produced);
+
+        Ok(())
+    }
+}
+
+pub struct FreqXlatingFirBuilder {
+    taps: Vec<f32>,
+    decimation: usize,
+    center_freq: f64,
+    sample_rate: f64,
+}
+
+impl FreqXlatingFirBuilder {
+    /// Lowpass `taps` for the channel, e.g., from [design::lowpass](crate::dsp::design::lowpass).
+    pub fn new(taps: &[f32], sample_rate: f64) -> FreqXlatingFirBuilder {
+        FreqXlatingFirBuilder {
+            taps: taps.to_vec(),
+            decimation: 1,
+            center_freq: 0.0,
+            sample_rate,
+        }
+    }
+
+    #[must_use]
+    pub fn decimation(mut self, decimation: usize) -> FreqXlatingFirBuilder {
+        self.decimation = decimation;
+        self
+    }
+
+    /// Frequency that is shifted to DC, in Hz.
+    #[must_use]
+    pub fn center_freq(mut self, center_freq: f64) -> FreqXlatingFirBuilder {
+        self.center_freq = center_freq;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        FreqXlatingFir::new(
+            &self.taps,
+            self.decimation,
+            self.center_freq,
+            self.sample_rate,
+        )
+    }
+}
@@ -0,0 +1,114 @@
+use num_complex::Complex;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::dsp::Nco;
+use crate::runtime::AsyncKernel;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// Coarse carrier frequency offset estimation and correction.
+///
+/// For each window of `window` samples, the offset is estimated from the phase increments of
+/// the samples raised to the `order`-th power, which removes the modulation of `order`-PSK
+/// symbols, e.g., 1 for an unmodulated carrier, 2 for BPSK, and 4 for QPSK. The window is
+/// shifted by the estimate, keeping the phase continuous, and the estimate in Hz is posted as
+/// [Pmt::Double] to the `freq` message output.
+///
+/// The estimate is unambiguous for offsets up to `sample_rate / (2 * order)`.
+pub struct FrequencyEstimator {
+    sample_rate: f64,
+    order: i32,
+    window: usize,
+    nco: Nco,
+    last: Option<Complex<f32>>,
+}
+
+impl FrequencyEstimator {
+    pub fn new(sample_rate: f64, order: usize, window: usize) -> Block {
+        assert!(order > 0, "order has to be positive");
+        assert!(window > 1, "window needs at least two samples");
+        Block::new_async(
+            BlockMetaBuilder::new("FrequencyEstimator").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_output("out", mem::size_of::<Complex<f32>>())
+                .build(),
+            MessageIoBuilder::new().add_output("freq").build(),
+            FrequencyEstimator {
+                sample_rate,
+                order: order as i32,
+                window,
+                nco: Nco::default(),
+                last: None,
+            },
+        )
+    }
+
+    // estimated offset of `input` in Hz
+    fn estimate(&mut self, input: &[Complex<f32>]) -> f64 {
+        let mut acc = Complex::new(0.0f64, 0.0);
+        for x in input {
+            let z = x.powi(self.order);
+            if let Some(last) = self.last {
+                let d = z * last.conj();
+                acc += Complex::new(d.re as f64, d.im as f64);
+            }
+            self.last = Some(z);
+        }
+        acc.arg() / self.order as f64 / (2.0 * std::f64::consts::PI) * self.sample_rate
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for FrequencyEstimator {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<Complex<f32>>();
+
+        let finished = sio.input(0).finished();
+        let mut n = 0;
+        let mut estimates = Vec::new();
+        loop {
+            let len = std::cmp::min(i.len() - n, self.window);
+            // flush the last partial window
+            if len == 0 || (len < self.window && !finished) || o.len() - n < len {
+                break;
+            }
+            let freq = self.estimate(&i[n..n + len]);
+            self.nco.set_freq(-freq, self.sample_rate);
+            for (x, y) in i[n..n + len].iter().zip(o[n..n + len].iter_mut()) {
+                *y = x * Complex::from_polar(1.0, self.nco.phase() as f32);
+                self.nco.step();
+            }
+            estimates.push(freq);
+            n += len;
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        for freq in estimates {
+            mio.post(0, Pmt::Double(freq)).await;
+        }
+
+        if finished && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use num_complex::Complex;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Binary FSK and GFSK demodulator, outputting one bit per `sps` samples as `u8` item.
+///
+/// It integrates the phase change over each symbol and decides for a one if the phase
+/// increased. The symbols are expected to be aligned with the input, as the output of the
+/// [FskMod](crate::blocks::FskMod).
+pub struct FskDemod {
+    sps: usize,
+    last: Complex<f32>,
+}
+
+impl FskDemod {
+    pub fn new(sps: usize) -> Block {
+        assert!(sps > 0, "samples per symbol have to be positive");
+        Block::new_sync(
+            BlockMetaBuilder::new("FskDemod").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_output("out", mem::size_of::<u8>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            FskDemod {
+                sps,
+                last: Complex::new(1.0, 0.0),
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for FskDemod {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<u8>();
+
+        let n = std::cmp::min(i.len() / self.sps, o.len());
+        for (symbol, y) in i.chunks_exact(self.sps).zip(o[0..n].iter_mut()) {
+            let mut phase = 0.0;
+            for x in symbol {
+                phase += (x * self.last.conj()).arg();
+                self.last = *x;
+            }
+            *y = (phase > 0.0) as u8;
+        }
+
+        sio.input(0).consume(n * self.sps);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && i.len() - n * self.sps < self.sps {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+use num_complex::Complex;
+use std::f64::consts::PI;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::dsp::design;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+// length of the Gaussian filter in symbols
+const GAUSSIAN_SPAN: usize = 4;
+
+/// Continuous-phase binary FSK modulator, with Gaussian pulse shaping for GFSK.
+///
+/// Bits, one per `u8` item, are mapped to `sps` complex samples each. A one shifts the frequency
+/// up, a zero down, changing the phase by `pi * modulation_index` per symbol. The output is
+/// aligned with the input, i.e., the frequency pulse of a symbol is centered on its samples, and
+/// each bit results in exactly `sps` samples.
+pub struct FskMod {
+    sps: usize,
+    // frequency pulse of a symbol, with a sum of `sps`
+    pulse: Vec<f32>,
+    // NRZ values of the symbols that overlap the current one, newest first
+    history: Vec<f32>,
+    // symbols that are pushed into the history before the first output
+    skip: usize,
+    flushed: usize,
+    sensitivity: f64,
+    phase: f64,
+}
+
+impl FskMod {
+    /// Modulator with rectangular pulses (2-FSK) or, given a bandwidth-time product `bt`,
+    /// Gaussian pulses (GFSK).
+    pub fn new(sps: usize, modulation_index: f64, bt: Option<f64>) -> Block {
+        assert!(sps > 0, "samples per symbol have to be positive");
+
+        let (pulse, span) = match bt {
+            None => (vec![1.0; sps], 0),
+            Some(bt) => {
+                let g = design::gaussian(GAUSSIAN_SPAN * sps + 1, sps as f64, bt);
+                // convolution with a rectangle of one symbol
+                let mut pulse = vec![0.0; (GAUSSIAN_SPAN + 1) * sps];
+                for (k, t) in g.iter().enumerate() {
+                    for p in &mut pulse[k..k + sps] {
+                        *p += t;
+                    }
+                }
+                (pulse, GAUSSIAN_SPAN)
+            }
+        };
+
+        Block::new_sync(
+            BlockMetaBuilder::new("FskMod").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<u8>())
+                .add_output("out", mem::size_of::<Complex<f32>>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            FskMod {
+                sps,
+                pulse,
+                history: vec![0.0; span + 1],
+                skip: span / 2,
+                flushed: 0,
+                sensitivity: PI * modulation_index / sps as f64,
+                phase: 0.0,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for FskMod {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        let o = sio.output(0).slice::<Complex<f32>>();
+        let finished = sio.input(0).finished();
+        // symbols of lookahead that are flushed with zeros at the end
+        let lookahead = (self.history.len() - 1) / 2;
+
+        let mut consumed = 0;
+        let mut produced = 0;
+        while produced + self.sps <= o.len() {
+            let a = if consumed < i.len() {
+                consumed += 1;
+                if i[consumed - 1] & 1 == 1 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            } else if finished && self.flushed < lookahead {
+                self.flushed += 1;
+                0.0
+            } else {
+                break;
+            };
+
+            self.history.rotate_right(1);
+            self.history[0] = a;
+            if self.skip > 0 {
+                self.skip -= 1;
+                continue;
+            }
+
+            for r in 0..self.sps {
+                let f: f32 = self
+                    .history
+                    .iter()
+                    .enumerate()
+                    .map(|(k, a)| a * self.pulse[r + k * self.sps])
+                    .sum();
+                self.phase = (self.phase + self.sensitivity * f as f64).rem_euclid(2.0 * PI);
+                o[produced] = Complex::from_polar(1.0, self.phase as f32);
+                produced += 1;
+            }
+        }
+
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(produced);
+
+        if finished && consumed == i.len() && self.flushed == lookahead {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct FskModBuilder {
+    sps: usize,
+    modulation_index: f64,
+    bt: Option<f64>,
+}
+
+impl FskModBuilder {
+    /// 2-FSK with a modulation index of 0.5, i.e., MSK.
+    pub fn new(sps: usize) -> FskModBuilder {
+        FskModBuilder {
+            sps,
+            modulation_index: 0.5,
+            bt: None,
+        }
+    }
+
+    #[must_use]
+    pub fn modulation_index(mut self, modulation_index: f64) -> FskModBuilder {
+        self.modulation_index = modulation_index;
+        self
+    }
+
+    /// Bandwidth-time product of the Gaussian filter, making it GFSK.
+    #[must_use]
+    pub fn bt(mut self, bt: f64) -> FskModBuilder {
+        self.bt = Some(bt);
+        self
+    }
+
+    pub fn build(self) -> Block {
+        FskMod::new(self.sps, self.modulation_index, self.bt)
+    }
+}
@@ -0,0 +1,125 @@
+use num_complex::Complex;
+use std::mem;
+use std::ops::{Add, Mul, Sub};
+
+use crate::anyhow::Result;
+use crate::dsp::Biquad;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Items of recursive filters, i.e., [Iir], [SinglePoleIir](crate::blocks::SinglePoleIir),
+/// and [DcBlocker](crate::blocks::DcBlocker).
+pub trait IirSample:
+    Copy + Send + 'static + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self>
+{
+    const ZERO: Self;
+
+    /// Flushes values that are close to denormal to zero.
+    ///
+    /// The state of a recursive filter decays exponentially when the input becomes zero, which
+    /// would end in denormals that are very slow on many CPUs.
+    fn flush(self) -> Self;
+}
+
+const FLUSH_THRESHOLD: f32 = 1e-30;
+
+impl IirSample for f32 {
+    const ZERO: f32 = 0.0;
+
+    fn flush(self) -> f32 {
+        if self.abs() < FLUSH_THRESHOLD {
+            0.0
+        } else {
+            self
+        }
+    }
+}
+
+impl IirSample for Complex<f32> {
+    const ZERO: Complex<f32> = Complex::new(0.0, 0.0);
+
+    fn flush(self) -> Complex<f32> {
+        Complex::new(self.re.flush(), self.im.flush())
+    }
+}
+
+/// Cascade of second-order IIR sections in direct form II transposed.
+///
+/// The sections can be designed with, e.g.,
+/// [butterworth_lowpass](crate::dsp::design::butterworth_lowpass).
+pub struct Iir<A>
+where
+    A: IirSample,
+{
+    sections: Vec<Biquad>,
+    state: Vec<[A; 2]>,
+}
+
+impl<A> Iir<A>
+where
+    A: IirSample,
+{
+    pub fn new(sections: &[Biquad]) -> Block {
+        assert!(
+            !sections.is_empty(),
+            "IIR filter needs at least one section"
+        );
+        Block::new_sync(
+            BlockMetaBuilder::new("Iir").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<A>())
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            Iir {
+                sections: sections.to_vec(),
+                state: vec![[A::ZERO; 2]; sections.len()],
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl<A> SyncKernel for Iir<A>
+where
+    A: IirSample,
+{
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        let o = sio.output(0).slice::<A>();
+
+        let n = std::cmp::min(i.len(), o.len());
+        for (x, y) in i[0..n].iter().zip(o[0..n].iter_mut()) {
+            let mut v = *x;
+            for (s, st) in self.sections.iter().zip(self.state.iter_mut()) {
+                let out = v * s.b0 + st[0];
+                st[0] = (v * s.b1 - out * s.a1 + st[1]).flush();
+                st[1] = (v * s.b2 - out * s.a2).flush();
+                v = out;
+            }
+            *y = v;
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Interleaves blocks of `block_len` items from the inputs `in0`, `in1`, ... in round-robin
+/// order.
+pub struct Interleave {
+    n_inputs: usize,
+    item_size: usize,
+    block_len: usize,
+}
+
+impl Interleave {
+    pub fn new(item_size: usize, n_inputs: usize, block_len: usize) -> Block {
+        assert!(n_inputs > 0, "Interleave needs at least one input");
+        assert!(block_len > 0, "block length has to be positive");
+        let mut sio = StreamIoBuilder::new();
+        for i in 0..n_inputs {
+            sio = sio.add_input(&format!("in{}", i), item_size);
+        }
+        Block::new_sync(
+            BlockMetaBuilder::new("Interleave").build(),
+            sio.add_output("out", item_size).build(),
+            MessageIoBuilder::<Self>::new().build(),
+            Interleave {
+                n_inputs,
+                item_size,
+                block_len,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for Interleave {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let block_size = self.block_len * self.item_size;
+        let o = sio.output(0).slice::<u8>();
+
+        // number of rounds
+        let mut n = o.len() / (block_size * self.n_inputs);
+        for k in 0..self.n_inputs {
+            n = n.min(sio.input(k).slice::<u8>().len() / block_size);
+        }
+
+        for k in 0..self.n_inputs {
+            let i = sio.input(k).slice::<u8>();
+            for r in 0..n {
+                let start = (r * self.n_inputs + k) * block_size;
+                o[start..start + block_size]
+                    .copy_from_slice(&i[r * block_size..(r + 1) * block_size]);
+            }
+            // no further round possible
+            if sio.input(k).finished() && i.len() - n * block_size < block_size {
+                io.finished = true;
+            }
+            sio.input(k).consume(n * self.block_len);
+        }
+        sio.output(0).produce(n * self.block_len * self.n_inputs);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use std::mem::size_of;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Averages vectors of `len` samples exponentially, outputting one average every `n` vectors.
+///
+/// Non-finite values, e.g., the log of zero, decay the average instead of poisoning it.
+pub struct Keep1InN {
+    alpha: f32,
+    n: usize,
+    i: usize,
+    avg: Vec<f32>,
+}
+
+impl Keep1InN {
+    pub fn new(alpha: f32, n: usize, len: usize) -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("Keep1InN").build(),
+            StreamIoBuilder::new()
+                .add_input("in", size_of::<f32>())
+                .add_output("out", size_of::<f32>())
+                .build(),
+            MessageIoBuilder::new().build(),
+            Self {
+                alpha,
+                n,
+                i: 0,
+                avg: vec![0.0; len],
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for Keep1InN {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let input = sio.input(0).slice::<f32>();
+        let output = sio.output(0).slice::<f32>();
+        let len = self.avg.len();
+
+        let mut consumed = 0;
+        let mut produced = 0;
+
+        while (consumed + 1) * len <= input.len() {
+            if self.i == self.n {
+                if (produced + 1) * len <= output.len() {
+                    output[produced * len..(produced + 1) * len].clone_from_slice(&self.avg);
+                    self.i = 0;
+                    produced += 1;
+                } else {
+                    break;
+                }
+            }
+
+            for i in 0..len {
+                let t = input[consumed * len + i];
+                if t.is_finite() {
+                    self.avg[i] = (1.0 - self.alpha) * self.avg[i] + self.alpha * t;
+                } else {
+                    self.avg[i] *= 1.0 - self.alpha;
+                }
+            }
+
+            consumed += 1;
+            self.i += 1;
+        }
+
+        if sio.input(0).finished() && consumed == input.len() / len {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(consumed * len);
+        sio.output(0).produce(produced * len);
+
+        Ok(())
+    }
+}
+
+pub struct Keep1InNBuilder {
+    alpha: f32,
+    n: usize,
+    len: usize,
+}
+
+impl Keep1InNBuilder {
+    pub fn new(n: usize) -> Keep1InNBuilder {
+        Keep1InNBuilder {
+            alpha: 0.1,
+            n,
+            len: 2048,
+        }
+    }
+
+    #[must_use]
+    pub fn alpha(mut self, alpha: f32) -> Keep1InNBuilder {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Length of the vectors, e.g., the FFT size.
+    #[must_use]
+    pub fn vector_len(mut self, len: usize) -> Keep1InNBuilder {
+        self.len = len;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        Keep1InN::new(self.alpha, self.n, self.len)
+    }
+}
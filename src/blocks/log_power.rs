@@ -0,0 +1,86 @@
+use num_complex::Complex;
+use std::cmp;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Input of the [LogPower] block.
+pub trait PowerSample: Copy + Send + Sync + 'static {
+    fn power(&self) -> f32;
+}
+
+/// Power of a complex sample, i.e., its squared magnitude.
+impl PowerSample for Complex<f32> {
+    fn power(&self) -> f32 {
+        self.norm_sqr()
+    }
+}
+
+/// Power values, e.g., the output of a [WelchPsd](crate::blocks::WelchPsd).
+impl PowerSample for f32 {
+    fn power(&self) -> f32 {
+        *self
+    }
+}
+
+/// Power in dB, i.e., `10 log10(p) + offset`.
+///
+/// Powers are limited to the smallest positive `f32`, so that the output stays finite.
+pub struct LogPower<A: PowerSample> {
+    offset: f32,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A: PowerSample> LogPower<A> {
+    pub fn new(offset: f32) -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("LogPower").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<A>())
+                .add_output("out", mem::size_of::<f32>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            LogPower::<A> {
+                offset,
+                _p: std::marker::PhantomData,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl<A: PowerSample> SyncKernel for LogPower<A> {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        let o = sio.output(0).slice::<f32>();
+
+        let n = cmp::min(i.len(), o.len());
+        for (x, y) in i[0..n].iter().zip(o[0..n].iter_mut()) {
+            *y = 10.0 * x.power().max(f32::MIN_POSITIVE).log10() + self.offset;
+        }
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        Ok(())
+    }
+}
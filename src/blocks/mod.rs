@@ -1,25 +1,55 @@
+mod agc;
+pub use agc::{Agc, AgcBuilder, AgcSample};
+mod am_demod;
+pub use am_demod::AmDemod;
 mod apply;
 pub use apply::Apply;
+mod arbitrary_resampler;
+pub use arbitrary_resampler::{ArbitraryResampler, ArbitraryResamplerBuilder};
+mod arithmetic;
+pub use arithmetic::{Add, AddConst, ArithmeticSample, Divide, Multiply, MultiplyConst, Subtract};
 
 #[cfg(feature = "audio")]
 pub mod audio;
 
+mod channel_model;
+pub use channel_model::{ChannelModel, ChannelModelBuilder};
+mod chunks_to_symbols;
+pub use chunks_to_symbols::ChunksToSymbols;
 mod combine;
 pub use combine::Combine;
-
+mod complex;
+pub use complex::{
+    ComplexToArg, ComplexToImag, ComplexToMag, ComplexToMagSquared, ComplexToReal, Conjugate,
+    FloatToComplex,
+};
+mod constellation_mapper;
+pub use constellation_mapper::ConstellationMapper;
+mod constellation_slicer;
+pub use constellation_slicer::ConstellationSlicer;
+
+mod convolutional;
+pub use convolutional::{ConvolutionalEncoder, ViterbiDecoder};
 mod copy;
 pub use copy::{Copy, CopyBuilder};
 mod copy_rand;
 pub use copy_rand::{CopyRand, CopyRandBuilder};
+mod costas_loop;
+pub use costas_loop::CostasLoop;
+mod dc_blocker;
+pub use dc_blocker::DcBlocker;
+mod deinterleave;
+pub use deinterleave::Deinterleave;
+mod feed_forward_agc;
+pub use feed_forward_agc::{FeedForwardAgc, FeedForwardAgcBuilder};
 mod filter;
 pub use filter::Filter;
 
-#[cfg_attr(RUSTC_IS_STABLE, path = "fir_stable.rs")]
 mod fir;
-pub use fir::Fir;
+pub use fir::{Fir, FirBuilder, FirTap, HasFirImpl};
 
 mod fft;
-pub use fft::{Fft, FftBuilder};
+pub use fft::{Fft, FftBuilder, FftDirection, FftNormalization};
 
 #[cfg(not(target_arch = "wasm32"))]
 mod file_sink;
@@ -33,12 +63,35 @@ pub use file_source::{FileSource, FileSourceBuilder};
 
 mod finite_source;
 pub use finite_source::FiniteSource;
+mod fm_deemphasis;
+pub use fm_deemphasis::FmDeemphasis;
+mod frame_average;
+pub use frame_average::{AverageMode, FrameAverage};
+mod framer;
+pub use framer::{Deframer, Framer};
+mod freq_xlating_fir;
+pub use freq_xlating_fir::{FreqXlatingFir, FreqXlatingFirBuilder};
+mod frequency_estimator;
+pub use frequency_estimator::FrequencyEstimator;
+mod fsk_demod;
+pub use fsk_demod::FskDemod;
+mod fsk_mod;
+pub use fsk_mod::{FskMod, FskModBuilder};
 mod head;
 pub use head::{Head, HeadBuilder};
+mod iir;
+pub use iir::{Iir, IirSample};
+mod interleave;
+pub use interleave::Interleave;
+mod keep_1_in_n;
+pub use keep_1_in_n::{Keep1InN, Keep1InNBuilder};
 
 #[cfg(feature = "lttng")]
 pub mod lttng;
 
+mod log_power;
+pub use log_power::{LogPower, PowerSample};
+
 mod message_burst;
 pub use message_burst::{MessageBurst, MessageBurstBuilder};
 mod message_copy;
@@ -51,20 +104,54 @@ mod message_source;
 #[cfg(not(target_arch = "wasm32"))]
 pub use message_source::{MessageSource, MessageSourceBuilder};
 
+mod noise_source;
+pub use noise_source::{NoiseSample, NoiseSource, NoiseSourceBuilder, NoiseType};
 mod null_sink;
 pub use null_sink::{NullSink, NullSinkBuilder};
 mod null_source;
 pub use null_source::{NullSource, NullSourceBuilder};
+mod pdu_to_stream;
+pub use pdu_to_stream::{IdleBehavior, PduToStream, PduToStreamBuilder};
+mod preamble_detector;
+pub use preamble_detector::{PreambleDetector, PreambleDetectorBuilder, PreambleSample};
+
+mod quadrature_demod;
+pub use quadrature_demod::QuadratureDemod;
+mod rational_resampler;
+pub use rational_resampler::{RationalResampler, RationalResamplerBuilder};
+
+mod reed_solomon;
+pub use reed_solomon::{ReedSolomonDecoder, ReedSolomonEncoder};
+mod registry;
+pub use registry::registry;
+mod selector;
+pub use selector::Selector;
 
 #[cfg(feature = "soapy")]
 mod soapy_src;
 #[cfg(feature = "soapy")]
 pub use soapy_src::{SoapySource, SoapySourceBuilder};
 
+mod signal_source;
+pub use signal_source::{SignalSample, SignalSource, SignalSourceBuilder, Waveform};
+mod single_pole_iir;
+pub use single_pole_iir::SinglePoleIir;
 mod source;
 pub use source::Source;
 mod split;
 pub use split::Split;
+mod ssb_demod;
+pub use ssb_demod::{Sideband, SsbDemod, SsbDemodBuilder};
+mod stream_mux;
+pub use stream_mux::StreamMux;
+mod stream_to_pdu;
+pub use stream_to_pdu::{PduItem, PduLength, StreamToPdu};
+mod stream_to_vector;
+pub use stream_to_vector::StreamToVector;
+mod symbol_sync;
+pub use symbol_sync::{SymbolSync, SymbolSyncBuilder, TimingErrorDetector};
+mod symbols_to_bits;
+pub use symbols_to_bits::SymbolsToBits;
 
 #[cfg(not(target_arch = "wasm32"))]
 mod tcp_sink;
@@ -81,10 +168,14 @@ mod throttle;
 #[cfg(not(target_arch = "wasm32"))]
 pub use throttle::{Throttle, ThrottleBuilder};
 
+mod valve;
+pub use valve::Valve;
 mod vector_sink;
 pub use vector_sink::{VectorSink, VectorSinkBuilder};
 mod vector_source;
 pub use vector_source::{VectorSource, VectorSourceBuilder};
+mod vector_to_stream;
+pub use vector_to_stream::VectorToStream;
 
 #[cfg(feature = "vulkan")]
 mod vulkan;
@@ -105,6 +196,18 @@ mod websocket_sink;
 #[cfg(not(target_arch = "wasm32"))]
 pub use websocket_sink::{WebsocketSink, WebsocketSinkBuilder, WebsocketSinkMode};
 
+#[cfg(not(target_arch = "wasm32"))]
+mod wav_sink;
+#[cfg(not(target_arch = "wasm32"))]
+pub use wav_sink::{WavSink, WavSinkBuilder};
+
+mod welch_psd;
+pub use welch_psd::{WelchPsd, WelchPsdBuilder};
+mod whitener;
+pub use whitener::Whitener;
+mod wire_format;
+pub use wire_format::{ComplexToWire, FloatToWire, WireSample, WireToComplex, WireToFloat};
+
 #[cfg(feature = "wgpu")]
 mod wgpu;
 #[cfg(feature = "wgpu")]
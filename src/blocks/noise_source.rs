@@ -0,0 +1,238 @@
+use num_complex::Complex;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+use serde::Deserialize;
+use std::f32::consts::PI;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Distributions of the [NoiseSource].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NoiseType {
+    /// Uniform in `[-amplitude, amplitude)`, independently for real and imaginary part.
+    Uniform,
+    /// Zero-mean Gaussian with power `amplitude^2`.
+    Gaussian,
+    /// Zero-mean Laplacian with power `amplitude^2`.
+    Laplacian,
+    /// Zero, except for impulses with magnitude `amplitude` and random sign or phase.
+    Impulse,
+}
+
+/// Standard normal sample.
+pub(crate) fn gaussian<R: Rng>(rng: &mut R) -> f32 {
+    // Box-Muller, the first uniform in (0, 1] to avoid ln(0)
+    let r = (-2.0 * (1.0 - rng.gen::<f32>()).ln()).sqrt();
+    r * (2.0 * PI * rng.gen::<f32>()).cos()
+}
+
+// Laplacian with unit variance as difference of two exponentials
+fn laplacian<R: Rng>(rng: &mut R) -> f32 {
+    let e1 = -(1.0 - rng.gen::<f32>()).ln();
+    let e2 = -(1.0 - rng.gen::<f32>()).ln();
+    (e1 - e2) / std::f32::consts::SQRT_2
+}
+
+/// Items of a [NoiseSource].
+pub trait NoiseSample: Copy + Send + 'static {
+    /// Draws a sample of the given distribution.
+    ///
+    /// `probability` is the probability of an impulse and only used for [NoiseType::Impulse].
+    fn noise<R: Rng>(kind: NoiseType, amplitude: f32, probability: f32, rng: &mut R) -> Self;
+}
+
+impl NoiseSample for f32 {
+    fn noise<R: Rng>(kind: NoiseType, amplitude: f32, probability: f32, rng: &mut R) -> f32 {
+        match kind {
+            NoiseType::Uniform => amplitude * (2.0 * rng.gen::<f32>() - 1.0),
+            NoiseType::Gaussian => amplitude * gaussian(rng),
+            NoiseType::Laplacian => amplitude * laplacian(rng),
+            NoiseType::Impulse => {
+                if rng.gen::<f32>() >= probability {
+                    0.0
+                } else if rng.gen::<bool>() {
+                    amplitude
+                } else {
+                    -amplitude
+                }
+            }
+        }
+    }
+}
+
+/// The power is split equally between real and imaginary part.
+impl NoiseSample for Complex<f32> {
+    fn noise<R: Rng>(
+        kind: NoiseType,
+        amplitude: f32,
+        probability: f32,
+        rng: &mut R,
+    ) -> Complex<f32> {
+        match kind {
+            NoiseType::Uniform => Complex::new(
+                f32::noise(kind, amplitude, probability, rng),
+                f32::noise(kind, amplitude, probability, rng),
+            ),
+            NoiseType::Gaussian | NoiseType::Laplacian => {
+                let a = amplitude / std::f32::consts::SQRT_2;
+                Complex::new(
+                    f32::noise(kind, a, probability, rng),
+                    f32::noise(kind, a, probability, rng),
+                )
+            }
+            NoiseType::Impulse => {
+                if rng.gen::<f32>() >= probability {
+                    Complex::new(0.0, 0.0)
+                } else {
+                    Complex::from_polar(amplitude, 2.0 * PI * rng.gen::<f32>())
+                }
+            }
+        }
+    }
+}
+
+/// Random noise generator.
+///
+/// Without a seed, the random number generator is seeded from the operating system. The
+/// message input `amplitude` takes a [Pmt::Double] to change the amplitude while running and
+/// returns the current value.
+pub struct NoiseSource<A>
+where
+    A: NoiseSample,
+{
+    kind: NoiseType,
+    amplitude: f32,
+    probability: f32,
+    rng: StdRng,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> NoiseSource<A>
+where
+    A: NoiseSample,
+{
+    pub fn new(kind: NoiseType, amplitude: f32, probability: f32, seed: Option<u64>) -> Block {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "probability has to be in [0, 1]"
+        );
+        Block::new_sync(
+            BlockMetaBuilder::new("NoiseSource").build(),
+            StreamIoBuilder::new()
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::<Self>::new()
+                .add_sync_input("amplitude", Self::amplitude_handler)
+                .build(),
+            NoiseSource {
+                kind,
+                amplitude,
+                probability,
+                rng: seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64),
+                _p: std::marker::PhantomData,
+            },
+        )
+    }
+
+    fn amplitude_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Double(a) => self.amplitude = a as f32,
+            Pmt::Null => {}
+            _ => warn!("NoiseSource: ignoring invalid amplitude {:?}", p),
+        }
+        Ok(Pmt::Double(self.amplitude as f64))
+    }
+}
+
+#[async_trait]
+impl<A> SyncKernel for NoiseSource<A>
+where
+    A: NoiseSample,
+{
+    fn work(
+        &mut self,
+        _io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = sio.output(0).slice::<A>();
+
+        for v in o.iter_mut() {
+            *v = A::noise(self.kind, self.amplitude, self.probability, &mut self.rng);
+        }
+
+        sio.output(0).produce(o.len());
+
+        Ok(())
+    }
+}
+
+pub struct NoiseSourceBuilder<A>
+where
+    A: NoiseSample,
+{
+    kind: NoiseType,
+    amplitude: f32,
+    probability: f32,
+    seed: Option<u64>,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> NoiseSourceBuilder<A>
+where
+    A: NoiseSample,
+{
+    /// Unit amplitude and an impulse probability of 0.01.
+    pub fn new(kind: NoiseType) -> NoiseSourceBuilder<A> {
+        NoiseSourceBuilder {
+            kind,
+            amplitude: 1.0,
+            probability: 0.01,
+            seed: None,
+            _p: std::marker::PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn amplitude(mut self, amplitude: f32) -> NoiseSourceBuilder<A> {
+        self.amplitude = amplitude;
+        self
+    }
+
+    /// Probability of an impulse per sample for [NoiseType::Impulse].
+    #[must_use]
+    pub fn probability(mut self, probability: f32) -> NoiseSourceBuilder<A> {
+        self.probability = probability;
+        self
+    }
+
+    /// Seed for reproducible noise.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> NoiseSourceBuilder<A> {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Block {
+        NoiseSource::<A>::new(self.kind, self.amplitude, self.probability, self.seed)
+    }
+}
@@ -0,0 +1,163 @@
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::PduItem;
+use crate::runtime::AsyncKernel;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// Output of a [PduToStream] while no message is pending.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdleBehavior {
+    /// Produce nothing until the next message arrives.
+    Block,
+    /// Produce zeros, e.g., to keep a hardware sink fed.
+    ZeroFill,
+}
+
+/// Serializes messages from the `in` message input into a stream.
+///
+/// Messages of the wrong type are dropped. Optionally, each message is preceded by its length
+/// as [PduItem::prefix], so that a [StreamToPdu](crate::blocks::StreamToPdu) with
+/// [PduLength::Prefixed](crate::blocks::PduLength::Prefixed) recovers the messages.
+pub struct PduToStream<A: PduItem> {
+    idle: IdleBehavior,
+    prefix: bool,
+    queue: VecDeque<Vec<A>>,
+    // position in the front message
+    pos: usize,
+}
+
+impl<A: PduItem> PduToStream<A> {
+    pub fn new(idle: IdleBehavior, prefix: bool) -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("PduToStream").build(),
+            StreamIoBuilder::new()
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::new()
+                .add_sync_input("in", Self::handler)
+                .build(),
+            PduToStream::<A> {
+                idle,
+                prefix,
+                queue: VecDeque::new(),
+                pos: 0,
+            },
+        )
+    }
+
+    fn handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        let items = match A::from_pmt(p) {
+            Some(items) => items,
+            None => {
+                warn!("PduToStream: ignoring message of wrong type");
+                return Ok(Pmt::Null);
+            }
+        };
+        if self.prefix {
+            match A::prefix(items.len()) {
+                Some(mut prefixed) => {
+                    prefixed.extend_from_slice(&items);
+                    self.queue.push_back(prefixed);
+                }
+                None => warn!(
+                    "PduToStream: dropping message with {} items that exceeds the length prefix",
+                    items.len()
+                ),
+            }
+        } else if !items.is_empty() {
+            self.queue.push_back(items);
+        }
+        Ok(Pmt::Null)
+    }
+}
+
+#[async_trait]
+impl<A: PduItem> AsyncKernel for PduToStream<A> {
+    async fn work(
+        &mut self,
+        _io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = sio.output(0).slice::<A>();
+
+        let mut produced = 0;
+        while let Some(front) = self.queue.front() {
+            let n = std::cmp::min(front.len() - self.pos, o.len() - produced);
+            o[produced..produced + n].copy_from_slice(&front[self.pos..self.pos + n]);
+            produced += n;
+            self.pos += n;
+            if self.pos < front.len() {
+                break;
+            }
+            self.queue.pop_front();
+            self.pos = 0;
+        }
+
+        if self.idle == IdleBehavior::ZeroFill && self.queue.is_empty() {
+            o[produced..].fill(A::default());
+            produced = o.len();
+        }
+
+        sio.output(0).produce(produced);
+
+        Ok(())
+    }
+}
+
+pub struct PduToStreamBuilder<A: PduItem> {
+    idle: IdleBehavior,
+    prefix: bool,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A: PduItem> PduToStreamBuilder<A> {
+    /// Block that waits for messages and does not prefix their length.
+    pub fn new() -> PduToStreamBuilder<A> {
+        PduToStreamBuilder {
+            idle: IdleBehavior::Block,
+            prefix: false,
+            _p: std::marker::PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn idle(mut self, idle: IdleBehavior) -> PduToStreamBuilder<A> {
+        self.idle = idle;
+        self
+    }
+
+    #[must_use]
+    pub fn prefix_length(mut self) -> PduToStreamBuilder<A> {
+        self.prefix = true;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        PduToStream::<A>::new(self.idle, self.prefix)
+    }
+}
+
+impl<A: PduItem> Default for PduToStreamBuilder<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
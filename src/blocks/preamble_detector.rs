@@ -0,0 +1,329 @@
+use num_complex::Complex;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::AsyncKernel;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// Item type of the [PreambleDetector].
+pub trait PreambleSample: Copy + Send + Sync + 'static {
+    /// Correlation of `input` with `preamble` and the energy of the first `preamble.len()`
+    /// items of `input`.
+    fn correlate(input: &[Self], preamble: &[Self]) -> (Complex<f32>, f32);
+}
+
+impl PreambleSample for Complex<f32> {
+    fn correlate(input: &[Self], preamble: &[Self]) -> (Complex<f32>, f32) {
+        input
+            .iter()
+            .zip(preamble)
+            .fold((Complex::new(0.0, 0.0), 0.0), |(c, e), (x, p)| {
+                (c + x * p.conj(), e + x.norm_sqr())
+            })
+    }
+}
+
+/// Bits, one per `u8`, mapped to `+1` for ones and `-1` for zeros.
+impl PreambleSample for u8 {
+    fn correlate(input: &[Self], preamble: &[Self]) -> (Complex<f32>, f32) {
+        let c = input
+            .iter()
+            .zip(preamble)
+            .map(|(x, p)| if (x & 1) == (p & 1) { 1.0 } else { -1.0 })
+            .sum();
+        (Complex::new(c, 0.0), preamble.len() as f32)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum State {
+    Armed,
+    // searching the peak until `until`
+    Searching {
+        until: u64,
+        offset: u64,
+        peak: f32,
+        phase: f32,
+    },
+    // waiting for the correlation to fall below the lower threshold
+    Detected,
+}
+
+/// Detects a known preamble or access code by correlation.
+///
+/// The correlation is normalized to `[0, 1]` by the energies of the preamble and the input
+/// window. Once it exceeds `threshold`, the peak is searched over the length of the preamble.
+/// Another preamble is only detected after the correlation fell below `threshold - hysteresis`.
+///
+/// Each detection is posted to the `detection` message output as [Pmt::MapStrPmt] with the
+/// `offset` of the start of the preamble in the input stream as [Pmt::U64], the `peak`
+/// correlation, and the `phase` of the input relative to the preamble, both as [Pmt::Double].
+/// For bits, a phase of `pi` indicates an inverted access code, which, like the magnitude of
+/// the correlation, also counts as detection.
+///
+/// Optionally, the block forwards `burst_len` items of each burst to its `out` stream output,
+/// starting with the preamble. Detections within a burst are only posted.
+///
+/// The `threshold` message input sets the threshold as [Pmt::Double] and returns the current
+/// value.
+pub struct PreambleDetector<A: PreambleSample> {
+    preamble: Vec<A>,
+    preamble_energy: f32,
+    threshold: f32,
+    hysteresis: f32,
+    burst_len: Option<usize>,
+    state: State,
+    // absolute offset of the first input item and of the next correlation
+    offset: u64,
+    pos: u64,
+    // next item to forward and number of items left of pending bursts
+    bursts: VecDeque<(u64, usize)>,
+}
+
+impl<A: PreambleSample> PreambleDetector<A> {
+    pub fn new(preamble: &[A], threshold: f32, hysteresis: f32, burst_len: Option<usize>) -> Block {
+        assert!(!preamble.is_empty(), "preamble must not be empty");
+        let mut sio = StreamIoBuilder::new().add_input("in", mem::size_of::<A>());
+        if burst_len.is_some() {
+            sio = sio.add_output("out", mem::size_of::<A>());
+        }
+        Block::new_async(
+            BlockMetaBuilder::new("PreambleDetector").build(),
+            sio.build(),
+            MessageIoBuilder::new()
+                .add_output("detection")
+                .add_sync_input("threshold", Self::threshold_handler)
+                .build(),
+            PreambleDetector {
+                preamble: preamble.to_vec(),
+                preamble_energy: A::correlate(preamble, preamble).1,
+                threshold,
+                hysteresis,
+                burst_len,
+                state: State::Armed,
+                offset: 0,
+                pos: 0,
+                bursts: VecDeque::new(),
+            },
+        )
+    }
+
+    fn threshold_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Double(t) if (0.0..=1.0).contains(&t) => self.threshold = t as f32,
+            Pmt::Null => {}
+            _ => warn!("PreambleDetector: ignoring invalid threshold {:?}", p),
+        }
+        Ok(Pmt::Double(self.threshold as f64))
+    }
+
+    // ends the peak search, queues the burst, and returns the detection message
+    fn detect(&mut self) -> Pmt {
+        let (offset, peak, phase) = match self.state {
+            State::Searching {
+                offset,
+                peak,
+                phase,
+                ..
+            } => (offset, peak, phase),
+            _ => unreachable!(),
+        };
+        self.state = State::Detected;
+
+        if let Some(n) = self.burst_len {
+            let free = match self.bursts.back() {
+                Some((next, left)) => offset >= next + *left as u64,
+                None => true,
+            };
+            if free {
+                self.bursts.push_back((offset, n));
+            }
+        }
+
+        let mut m = HashMap::new();
+        m.insert("offset".to_string(), Pmt::U64(offset));
+        m.insert("peak".to_string(), Pmt::Double(peak as f64));
+        m.insert("phase".to_string(), Pmt::Double(phase as f64));
+        Pmt::MapStrPmt(m)
+    }
+
+    // normalized correlation and phase at the start of `input`
+    fn correlation(&self, input: &[A]) -> (f32, f32) {
+        let (c, energy) = A::correlate(input, &self.preamble);
+        let norm = (energy * self.preamble_energy).sqrt();
+        if norm > 0.0 {
+            (c.norm() / norm, c.arg())
+        } else {
+            (0.0, 0.0)
+        }
+    }
+}
+
+#[async_trait]
+impl<A: PreambleSample> AsyncKernel for PreambleDetector<A> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        let len = self.preamble.len();
+        let end = self.offset + i.len() as u64;
+
+        let mut detections = Vec::new();
+        let mut pos = self.pos;
+        while pos + len as u64 <= end {
+            let rel = (pos - self.offset) as usize;
+            let (corr, phase) = self.correlation(&i[rel..]);
+
+            match self.state {
+                State::Armed => {
+                    if corr >= self.threshold {
+                        self.state = State::Searching {
+                            until: pos + len as u64,
+                            offset: pos,
+                            peak: corr,
+                            phase,
+                        };
+                    }
+                }
+                State::Searching { until, peak, .. } => {
+                    if corr > peak {
+                        self.state = State::Searching {
+                            until,
+                            offset: pos,
+                            peak: corr,
+                            phase,
+                        };
+                    }
+                    if pos + 1 >= until {
+                        detections.push(self.detect());
+                    }
+                }
+                State::Detected => {
+                    if corr < self.threshold - self.hysteresis {
+                        self.state = State::Armed;
+                    }
+                }
+            }
+            pos += 1;
+        }
+        self.pos = pos;
+
+        // report the peak so far at the end of the stream
+        let finished = sio.input(0).finished();
+        if finished && matches!(self.state, State::Searching { .. }) {
+            detections.push(self.detect());
+        }
+
+        // forward burst items
+        if self.burst_len.is_some() {
+            let o = sio.output(0).slice::<A>();
+            let mut produced = 0;
+            while let Some((next, left)) = self.bursts.front_mut() {
+                let rel = (*next - self.offset) as usize;
+                let n = *[*left, i.len() - rel, o.len() - produced]
+                    .iter()
+                    .min()
+                    .unwrap();
+                o[produced..produced + n].copy_from_slice(&i[rel..rel + n]);
+                produced += n;
+                *next += n as u64;
+                *left -= n;
+                if *left > 0 {
+                    break;
+                }
+                self.bursts.pop_front();
+            }
+            sio.output(0).produce(produced);
+        }
+
+        // keep the items that the peak search or a burst might need
+        let mut keep = match self.state {
+            State::Searching { offset, .. } => offset,
+            _ => pos,
+        };
+        if let Some((next, _)) = self.bursts.front() {
+            keep = keep.min(*next);
+        }
+        let consumed = (keep - self.offset) as usize;
+        self.offset = keep;
+
+        sio.input(0).consume(consumed);
+
+        for d in detections {
+            mio.post(0, d).await;
+        }
+
+        // no further preamble fits and the rest of the burst is forwarded
+        if finished && pos + len as u64 > end && self.bursts.iter().all(|(next, _)| *next == end) {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct PreambleDetectorBuilder<A: PreambleSample> {
+    preamble: Vec<A>,
+    threshold: f32,
+    hysteresis: f32,
+    burst_len: Option<usize>,
+}
+
+impl<A: PreambleSample> PreambleDetectorBuilder<A> {
+    /// Detector with a threshold of 0.8 and a hysteresis of 0.1.
+    pub fn new(preamble: &[A]) -> PreambleDetectorBuilder<A> {
+        PreambleDetectorBuilder {
+            preamble: preamble.to_vec(),
+            threshold: 0.8,
+            hysteresis: 0.1,
+            burst_len: None,
+        }
+    }
+
+    #[must_use]
+    pub fn threshold(mut self, threshold: f32) -> PreambleDetectorBuilder<A> {
+        self.threshold = threshold;
+        self
+    }
+
+    #[must_use]
+    pub fn hysteresis(mut self, hysteresis: f32) -> PreambleDetectorBuilder<A> {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Forwards `burst_len` items of each burst, which adds the `out` stream output.
+    #[must_use]
+    pub fn forward(mut self, burst_len: usize) -> PreambleDetectorBuilder<A> {
+        self.burst_len = Some(burst_len);
+        self
+    }
+
+    pub fn build(self) -> Block {
+        PreambleDetector::<A>::new(
+            &self.preamble,
+            self.threshold,
+            self.hysteresis,
+            self.burst_len,
+        )
+    }
+}
@@ -0,0 +1,74 @@
+use num_complex::Complex;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// FM demodulator, outputting `gain * arg(x[n] * conj(x[n-1]))`, i.e., the scaled phase
+/// difference of consecutive samples.
+///
+/// With a `gain` of `sample_rate / (2 pi deviation)`, the maximum frequency deviation maps to
+/// one, see [QuadratureDemod::fm].
+pub struct QuadratureDemod {
+    gain: f32,
+    last: Complex<f32>,
+}
+
+impl QuadratureDemod {
+    pub fn new(gain: f32) -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("QuadratureDemod").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_output("out", mem::size_of::<f32>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            QuadratureDemod {
+                gain,
+                last: Complex::new(1.0, 0.0),
+            },
+        )
+    }
+
+    /// Demodulator for FM with the given maximum `deviation` in Hz.
+    pub fn fm(deviation: f64, sample_rate: f64) -> Block {
+        Self::new((sample_rate / (2.0 * std::f64::consts::PI * deviation)) as f32)
+    }
+}
+
+#[async_trait]
+impl SyncKernel for QuadratureDemod {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<f32>();
+
+        let n = std::cmp::min(i.len(), o.len());
+        for (x, y) in i[0..n].iter().zip(o[0..n].iter_mut()) {
+            *y = self.gain * (x * self.last.conj()).arg();
+            self.last = *x;
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
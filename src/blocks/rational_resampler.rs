@@ -0,0 +1,171 @@
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::HasFirImpl;
+use crate::dsp::design;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Splits `taps` into `phases` sub-filters of equal length.
+///
+/// The sub-filters are reversed, so that they can be applied to the input window with the newest
+/// sample last.
+pub(crate) fn polyphase(taps: &[f32], phases: usize) -> Vec<Vec<f32>> {
+    let len = taps.len().div_ceil(phases);
+    (0..phases)
+        .map(|p| {
+            (0..len)
+                .rev()
+                .map(|k| taps.get(p + k * phases).copied().unwrap_or(0.0))
+                .collect()
+        })
+        .collect()
+}
+
+/// Polyphase resampler, interpolating by `interp` and decimating by `decim`.
+///
+/// Like [Fir](crate::blocks::Fir), the block does not zero-pad the input, i.e., the first output
+/// sample corresponds to the first full window of input samples.
+pub struct RationalResampler<A>
+where
+    A: HasFirImpl<f32>,
+{
+    interp: usize,
+    decim: usize,
+    branches: Vec<Vec<f32>>,
+    // current sub-filter
+    phase: usize,
+    // input samples to skip before the next output
+    skip: usize,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> RationalResampler<A>
+where
+    A: HasFirImpl<f32>,
+{
+    pub fn new(interp: usize, decim: usize, taps: &[f32]) -> Block {
+        assert!(
+            interp > 0 && decim > 0,
+            "resampling factors have to be positive"
+        );
+        assert!(!taps.is_empty(), "resampler needs at least one tap");
+        Block::new_sync(
+            BlockMetaBuilder::new("RationalResampler").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<A>())
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            RationalResampler {
+                interp,
+                decim,
+                branches: polyphase(taps, interp),
+                phase: 0,
+                skip: 0,
+                _p: std::marker::PhantomData,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl<A> SyncKernel for RationalResampler<A>
+where
+    A: HasFirImpl<f32>,
+{
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        let o = sio.output(0).slice::<A>();
+        let len = self.branches[0].len();
+
+        let mut j = self.skip;
+        let mut produced = 0;
+        while j + len <= i.len() && produced < o.len() {
+            o[produced] = A::dot(&i[j..], &self.branches[self.phase]);
+            produced += 1;
+
+            self.phase += self.decim;
+            j += self.phase / self.interp;
+            self.phase %= self.interp;
+        }
+
+        let consumed = std::cmp::min(j, i.len());
+        self.skip = j - consumed;
+
+        if sio.input(0).finished() && j + len > i.len() {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(produced);
+
+        Ok(())
+    }
+}
+
+pub struct RationalResamplerBuilder<A>
+where
+    A: HasFirImpl<f32>,
+{
+    interp: usize,
+    decim: usize,
+    taps: Option<Vec<f32>>,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> RationalResamplerBuilder<A>
+where
+    A: HasFirImpl<f32>,
+{
+    /// Resampler that changes the rate by `interp / decim`, which is reduced to lowest terms.
+    pub fn new(interp: usize, decim: usize) -> RationalResamplerBuilder<A> {
+        assert!(
+            interp > 0 && decim > 0,
+            "resampling factors have to be positive"
+        );
+        let g = gcd(interp, decim);
+        RationalResamplerBuilder {
+            interp: interp / g,
+            decim: decim / g,
+            taps: None,
+            _p: std::marker::PhantomData,
+        }
+    }
+
+    /// Prototype filter at `interp` times the input rate. By default, a filter is designed with
+    /// [design::resampler].
+    #[must_use]
+    pub fn taps(mut self, taps: &[f32]) -> RationalResamplerBuilder<A> {
+        self.taps = Some(taps.to_vec());
+        self
+    }
+
+    pub fn build(self) -> Block {
+        let taps = self.taps.unwrap_or_else(|| {
+            design::resampler(self.interp, self.interp as f64 / self.decim as f64)
+        });
+        RationalResampler::<A>::new(self.interp, self.decim, &taps)
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
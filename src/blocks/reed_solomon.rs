@@ -0,0 +1,121 @@
+use futures::FutureExt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::anyhow::Result;
+use crate::dsp::ReedSolomon;
+use crate::runtime::AsyncKernel;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIoBuilder;
+
+/// Encodes [Pmt::Blob] messages with a [ReedSolomon] code.
+///
+/// Messages are split into codewords with `255 - nroots` data bytes. The last codeword is
+/// shortened.
+pub struct ReedSolomonEncoder {
+    rs: ReedSolomon,
+}
+
+impl ReedSolomonEncoder {
+    pub fn new(nroots: usize) -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("ReedSolomonEncoder").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_output("out")
+                .add_async_input("in", Self::handler)
+                .build(),
+            ReedSolomonEncoder {
+                rs: ReedSolomon::new(nroots),
+            },
+        )
+    }
+
+    fn handler<'a>(
+        &'a mut self,
+        mio: &'a mut MessageIo<Self>,
+        _meta: &'a mut BlockMeta,
+        p: Pmt,
+    ) -> Pin<Box<dyn Future<Output = Result<Pmt>> + Send + 'a>> {
+        async move {
+            match p {
+                Pmt::Blob(data) => {
+                    let coded = data
+                        .chunks(self.rs.data_len())
+                        .flat_map(|c| self.rs.encode(c))
+                        .collect();
+                    mio.post(0, Pmt::Blob(coded)).await;
+                }
+                _ => warn!("ReedSolomonEncoder: ignoring invalid data {:?}", p),
+            }
+            Ok(Pmt::Null)
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for ReedSolomonEncoder {}
+
+/// Decodes [Pmt::Blob] messages from a [ReedSolomonEncoder].
+///
+/// Messages with uncorrectable codewords are dropped.
+pub struct ReedSolomonDecoder {
+    rs: ReedSolomon,
+}
+
+impl ReedSolomonDecoder {
+    pub fn new(nroots: usize) -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("ReedSolomonDecoder").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_output("out")
+                .add_async_input("in", Self::handler)
+                .build(),
+            ReedSolomonDecoder {
+                rs: ReedSolomon::new(nroots),
+            },
+        )
+    }
+
+    fn decode(&self, mut data: Vec<u8>) -> Option<Vec<u8>> {
+        let mut out = Vec::with_capacity(data.len());
+        let mut corrected = 0;
+        for c in data.chunks_mut(255) {
+            corrected += self.rs.decode(c)?;
+            out.extend_from_slice(&c[..c.len() - self.rs.nroots()]);
+        }
+        if corrected > 0 {
+            debug!("ReedSolomonDecoder: corrected {} bytes", corrected);
+        }
+        Some(out)
+    }
+
+    fn handler<'a>(
+        &'a mut self,
+        mio: &'a mut MessageIo<Self>,
+        _meta: &'a mut BlockMeta,
+        p: Pmt,
+    ) -> Pin<Box<dyn Future<Output = Result<Pmt>> + Send + 'a>> {
+        async move {
+            match p {
+                Pmt::Blob(data) => match self.decode(data) {
+                    Some(data) => mio.post(0, Pmt::Blob(data)).await,
+                    None => debug!("ReedSolomonDecoder: dropping uncorrectable message"),
+                },
+                _ => warn!("ReedSolomonDecoder: ignoring invalid data {:?}", p),
+            }
+            Ok(Pmt::Null)
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for ReedSolomonDecoder {}
@@ -0,0 +1,746 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+use crate::anyhow::{bail, Result};
+use crate::blocks::*;
+use crate::dsp::Constellation;
+use crate::dsp::ConvolutionalCode;
+use crate::dsp::Lfsr;
+use crate::dsp::Mapping;
+use crate::dsp::Window;
+use crate::num_complex::Complex32;
+use crate::runtime::Block;
+use crate::runtime::BlockParams;
+use crate::runtime::BlockRegistry;
+use crate::runtime::Pmt;
+
+/// Registry with the built-in blocks that can be configured through parameters.
+///
+/// Stream blocks take the item size either directly as `item_size` or as
+/// `item` type name (e.g., `"f32"` or `"c32"`).
+pub fn registry() -> BlockRegistry {
+    let mut r = BlockRegistry::new();
+
+    r.register("Add", |p| {
+        let n = n_inputs(p)?;
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(Add::<f32>::new(n)),
+            "f64" => Ok(Add::<f64>::new(n)),
+            "i16" => Ok(Add::<i16>::new(n)),
+            "c32" => Ok(Add::<Complex32>::new(n)),
+            t => bail!("Add does not support items of type `{}`", t),
+        }
+    });
+    r.register("AddConst", |p| {
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(AddConst::<f32>::new(p.get("value")?)),
+            "f64" => Ok(AddConst::<f64>::new(p.get("value")?)),
+            "i16" => Ok(AddConst::<i16>::new(p.get("value")?)),
+            "c32" => {
+                let (re, im): (f32, f32) = p.get("value")?;
+                Ok(AddConst::<Complex32>::new(Complex32::new(re, im)))
+            }
+            t => bail!("AddConst does not support items of type `{}`", t),
+        }
+    });
+    r.register("Agc", |p| {
+        let max_gain: f32 = p.get_or("max_gain", 65536.0)?;
+        if max_gain <= 0.0 {
+            bail!("maximum gain has to be positive");
+        }
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(agc::<f32>(p, max_gain)?),
+            "c32" => Ok(agc::<Complex32>(p, max_gain)?),
+            t => bail!("Agc does not support items of type `{}`", t),
+        }
+    });
+    r.register("AmDemod", |_| Ok(AmDemod::new()));
+    r.register("ArbitraryResampler", |p| {
+        let rate: f64 = p.get("rate")?;
+        if rate <= 0.0 {
+            bail!("rate has to be positive");
+        }
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(ArbitraryResamplerBuilder::<f32>::new(rate).build()),
+            "c32" => Ok(ArbitraryResamplerBuilder::<Complex32>::new(rate).build()),
+            t => bail!("ArbitraryResampler does not support items of type `{}`", t),
+        }
+    });
+    r.register("ChannelModel", |p| {
+        let sample_rate: f64 = p.get("sample_rate")?;
+        let sco: f64 = p.get_or("sco", 0.0)?;
+        if sample_rate <= 0.0 || sco <= -1.0 {
+            bail!("ChannelModel needs a positive sample rate and a sampling clock offset > -1");
+        }
+        let mut b = ChannelModelBuilder::new(sample_rate)
+            .signal_power(p.get_or("signal_power", 1.0)?)
+            .cfo(p.get_or("cfo", 0.0)?)
+            .sco(sco)
+            .phase_noise(p.get_or("phase_noise", 0.0)?);
+        if let Some(snr) = p.get_opt("snr")? {
+            b = b.snr(snr);
+        }
+        if let Some(taps) = p.get_opt::<Vec<(f32, f32)>>("taps")? {
+            if taps.is_empty() {
+                bail!("channel needs at least one tap");
+            }
+            let taps: Vec<Complex32> = taps.iter().map(|t| Complex32::new(t.0, t.1)).collect();
+            b = b.taps(&taps);
+        }
+        if let Some(seed) = p.get_opt("seed")? {
+            b = b.seed(seed);
+        }
+        Ok(b.build())
+    });
+    r.register("ChunksToSymbols", |p| {
+        Ok(ChunksToSymbols::new(constellation(p)?))
+    });
+    r.register("ComplexToArg", |_| Ok(ComplexToArg::new()));
+    r.register("ComplexToImag", |_| Ok(ComplexToImag::new()));
+    r.register("ComplexToMag", |_| Ok(ComplexToMag::new()));
+    r.register("ComplexToMagSquared", |_| Ok(ComplexToMagSquared::new()));
+    r.register("ComplexToReal", |_| Ok(ComplexToReal::new()));
+    r.register("ComplexToWire", |p| {
+        match p.get_or::<String>("item", "i16".to_string())?.as_str() {
+            "i16" => wire::<i16>(p, ComplexToWire::<i16>::new),
+            "i8" => wire::<i8>(p, ComplexToWire::<i8>::new),
+            "u8" => wire::<u8>(p, ComplexToWire::<u8>::new),
+            t => bail!("ComplexToWire does not support items of type `{}`", t),
+        }
+    });
+    r.register("Conjugate", |_| Ok(Conjugate::new()));
+    r.register("ConstellationMapper", |p| {
+        Ok(ConstellationMapper::new(constellation(p)?))
+    });
+    r.register("ConstellationSlicer", |p| {
+        Ok(ConstellationSlicer::new(constellation(p)?))
+    });
+    r.register("ConvolutionalEncoder", |p| {
+        Ok(ConvolutionalEncoder::new(convolutional_code(p)?))
+    });
+    r.register("Copy", |p| {
+        Ok(CopyBuilder::new(item_size(p)?)
+            .enabled(p.get_or("enabled", true)?)
+            .build())
+    });
+    r.register("CopyRand", |p| {
+        let mut b = CopyRandBuilder::new(item_size(p)?);
+        if let Some(m) = p.get_opt("max_copy")? {
+            b = b.max_copy(m);
+        }
+        Ok(b.build())
+    });
+    r.register("CostasLoop", |p| {
+        let order: usize = p.get("order")?;
+        if order != 2 && order != 4 {
+            bail!("CostasLoop supports orders 2 and 4");
+        }
+        let bandwidth: f64 = p.get_or("bandwidth", 2.0 * std::f64::consts::PI / 100.0)?;
+        if bandwidth < 0.0 {
+            bail!("loop bandwidth must not be negative");
+        }
+        Ok(CostasLoop::new(order, bandwidth))
+    });
+    r.register("DcBlocker", |p| {
+        let pole: f32 = p.get_or("pole", 0.995)?;
+        if pole <= 0.0 || pole >= 1.0 {
+            bail!("pole has to be in (0, 1)");
+        }
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(DcBlocker::<f32>::new(pole)),
+            "c32" => Ok(DcBlocker::<Complex32>::new(pole)),
+            t => bail!("DcBlocker does not support items of type `{}`", t),
+        }
+    });
+    r.register("Deframer", |p| Ok(Deframer::new(p.get_opt("crc")?)));
+    r.register("Deinterleave", |p| {
+        let (n, block_len) = interleaving(p, "n_outputs")?;
+        Ok(Deinterleave::new(item_size(p)?, n, block_len))
+    });
+    r.register("Divide", |p| {
+        let n = n_inputs(p)?;
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(Divide::<f32>::new(n)),
+            "f64" => Ok(Divide::<f64>::new(n)),
+            "i16" => Ok(Divide::<i16>::new(n)),
+            "c32" => Ok(Divide::<Complex32>::new(n)),
+            t => bail!("Divide does not support items of type `{}`", t),
+        }
+    });
+    r.register("FeedForwardAgc", |p| {
+        let n: usize = p.get("n")?;
+        let max_gain: f32 = p.get_or("max_gain", 65536.0)?;
+        if n == 0 || max_gain <= 0.0 {
+            bail!(
+                "FeedForwardAgc needs a window of at least one sample and a positive maximum gain"
+            );
+        }
+        let reference = p.get_or("reference", 1.0)?;
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(FeedForwardAgc::<f32>::new(n, reference, max_gain)),
+            "c32" => Ok(FeedForwardAgc::<Complex32>::new(n, reference, max_gain)),
+            t => bail!("FeedForwardAgc does not support items of type `{}`", t),
+        }
+    });
+    r.register("Fft", |p| {
+        let mut b = FftBuilder::new()
+            .fft_size(p.get_or("fft_size", 2048)?)
+            .direction(p.get_or("direction", FftDirection::Forward)?)
+            .shift(p.get_or("shift", false)?)
+            .normalization(p.get_or("normalization", FftNormalization::None)?)
+            .vector(p.get_or("vector", false)?);
+        if let Some(w) = p.get_opt("window")? {
+            b = b.window(w);
+        }
+        Ok(b.build())
+    });
+    r.register("Fir", |p| {
+        let taps: Vec<f32> = p.get("taps")?;
+        if taps.is_empty() {
+            bail!("FIR filter needs at least one tap");
+        }
+        let decimation = p.get_or("decimation", 1)?;
+        if decimation == 0 {
+            bail!("decimation has to be positive");
+        }
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(FirBuilder::<f32, f32>::new(&taps)
+                .decimation(decimation)
+                .build()),
+            "c32" => Ok(FirBuilder::<Complex32, f32>::new(&taps)
+                .decimation(decimation)
+                .build()),
+            t => bail!("Fir does not support items of type `{}`", t),
+        }
+    });
+    r.register("FloatToComplex", |_| Ok(FloatToComplex::new()));
+    r.register("FloatToWire", |p| {
+        match p.get_or::<String>("item", "i16".to_string())?.as_str() {
+            "i16" => wire::<i16>(p, FloatToWire::<i16>::new),
+            "i8" => wire::<i8>(p, FloatToWire::<i8>::new),
+            "u8" => wire::<u8>(p, FloatToWire::<u8>::new),
+            t => bail!("FloatToWire does not support items of type `{}`", t),
+        }
+    });
+    r.register("FmDeemphasis", |p| {
+        let sample_rate: f64 = p.get("sample_rate")?;
+        let tau: f64 = p.get_or("tau", 75e-6)?;
+        if sample_rate <= 0.0 || tau <= 0.0 {
+            bail!("FmDeemphasis needs a positive sample rate and time constant");
+        }
+        Ok(FmDeemphasis::new(sample_rate, tau))
+    });
+    r.register("FrameAverage", |p| {
+        let len: usize = p.get("len")?;
+        let mode = p.get_or("mode", AverageMode::Exponential(0.1))?;
+        let decimation: usize = p.get_or("decimation", 1)?;
+        let valid = match mode {
+            AverageMode::Exponential(alpha) => alpha > 0.0 && alpha <= 1.0,
+            AverageMode::Linear(n) => n > 0,
+            AverageMode::MaxHold => true,
+        };
+        if len == 0 || decimation == 0 || !valid {
+            bail!("FrameAverage needs a positive frame length and decimation, and a valid mode");
+        }
+        Ok(FrameAverage::new(
+            len,
+            mode,
+            decimation,
+            p.get_or("vector", false)?,
+        ))
+    });
+    r.register("Framer", |p| Ok(Framer::new(p.get_opt("crc")?)));
+    r.register("FreqXlatingFir", |p| {
+        let taps: Vec<f32> = p.get("taps")?;
+        let decimation = p.get_or("decimation", 1)?;
+        let sample_rate: f64 = p.get("sample_rate")?;
+        if taps.is_empty() || decimation == 0 || sample_rate <= 0.0 {
+            bail!("FreqXlatingFir needs taps, a positive decimation, and a positive sample rate");
+        }
+        Ok(FreqXlatingFirBuilder::new(&taps, sample_rate)
+            .decimation(decimation)
+            .center_freq(p.get_or("center_freq", 0.0)?)
+            .build())
+    });
+    r.register("FrequencyEstimator", |p| {
+        let sample_rate: f64 = p.get("sample_rate")?;
+        let order: usize = p.get_or("order", 1)?;
+        let window: usize = p.get_or("window", 1024)?;
+        if sample_rate <= 0.0 || order == 0 || window < 2 {
+            bail!("FrequencyEstimator needs a positive sample rate and order, and a window of at least two samples");
+        }
+        Ok(FrequencyEstimator::new(sample_rate, order, window))
+    });
+    r.register("FskDemod", |p| {
+        let sps: usize = p.get("sps")?;
+        if sps == 0 {
+            bail!("samples per symbol have to be positive");
+        }
+        Ok(FskDemod::new(sps))
+    });
+    r.register("FskMod", |p| {
+        let sps: usize = p.get("sps")?;
+        if sps == 0 {
+            bail!("samples per symbol have to be positive");
+        }
+        let mut b = FskModBuilder::new(sps).modulation_index(p.get_or("modulation_index", 0.5)?);
+        if let Some(bt) = p.get_opt::<f64>("bt")? {
+            if bt <= 0.0 {
+                bail!("bandwidth-time product has to be positive");
+            }
+            b = b.bt(bt);
+        }
+        Ok(b.build())
+    });
+    r.register("Head", |p| {
+        Ok(HeadBuilder::new(item_size(p)?, p.get("n_items")?).build())
+    });
+    r.register("Interleave", |p| {
+        let (n, block_len) = interleaving(p, "n_inputs")?;
+        Ok(Interleave::new(item_size(p)?, n, block_len))
+    });
+    r.register("Keep1InN", |p| {
+        Ok(Keep1InNBuilder::new(p.get("n")?)
+            .alpha(p.get_or("alpha", 0.1)?)
+            .vector_len(p.get_or("vector_len", 2048)?)
+            .build())
+    });
+    r.register("LogPower", |p| {
+        let offset: f32 = p.get_or("offset", 0.0)?;
+        match p.get_or::<String>("item", "c32".to_string())?.as_str() {
+            "c32" => Ok(LogPower::<Complex32>::new(offset)),
+            "f32" => Ok(LogPower::<f32>::new(offset)),
+            t => bail!("LogPower does not support items of type `{}`", t),
+        }
+    });
+    r.register("MessageBurst", |p| {
+        Ok(MessageBurstBuilder::new(p.get_or("message", Pmt::Null)?, p.get("n_messages")?).build())
+    });
+    r.register("MessageCopy", |_| Ok(MessageCopyBuilder::new().build()));
+    r.register("MessageSink", |_| Ok(MessageSinkBuilder::new().build()));
+    r.register("Multiply", |p| {
+        let n = n_inputs(p)?;
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(Multiply::<f32>::new(n)),
+            "f64" => Ok(Multiply::<f64>::new(n)),
+            "i16" => Ok(Multiply::<i16>::new(n)),
+            "c32" => Ok(Multiply::<Complex32>::new(n)),
+            t => bail!("Multiply does not support items of type `{}`", t),
+        }
+    });
+    r.register("MultiplyConst", |p| {
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(MultiplyConst::<f32>::new(p.get("value")?)),
+            "f64" => Ok(MultiplyConst::<f64>::new(p.get("value")?)),
+            "i16" => Ok(MultiplyConst::<i16>::new(p.get("value")?)),
+            "c32" => {
+                let (re, im): (f32, f32) = p.get("value")?;
+                Ok(MultiplyConst::<Complex32>::new(Complex32::new(re, im)))
+            }
+            t => bail!("MultiplyConst does not support items of type `{}`", t),
+        }
+    });
+    r.register("NoiseSource", |p| {
+        let probability: f32 = p.get_or("probability", 0.01)?;
+        if !(0.0..=1.0).contains(&probability) {
+            bail!("probability has to be in [0, 1]");
+        }
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(noise_source::<f32>(p, probability)?),
+            "c32" => Ok(noise_source::<Complex32>(p, probability)?),
+            t => bail!("NoiseSource does not support items of type `{}`", t),
+        }
+    });
+    r.register("NullSink", |p| {
+        Ok(NullSinkBuilder::new(item_size(p)?).build())
+    });
+    r.register("NullSource", |p| {
+        Ok(NullSourceBuilder::new(item_size(p)?).build())
+    });
+    r.register("PduToStream", |p| {
+        let idle = p.get_or("idle", IdleBehavior::Block)?;
+        let prefix = p.get_or("prefix_length", false)?;
+        match p.get_or::<String>("item", "u8".to_string())?.as_str() {
+            "u8" => Ok(PduToStream::<u8>::new(idle, prefix)),
+            "f32" => Ok(PduToStream::<f32>::new(idle, prefix)),
+            t => bail!("PduToStream does not support items of type `{}`", t),
+        }
+    });
+    r.register("PreambleDetector", |p| {
+        let threshold: f32 = p.get_or("threshold", 0.8)?;
+        let hysteresis: f32 = p.get_or("hysteresis", 0.1)?;
+        if !(0.0..=1.0).contains(&threshold) || hysteresis < 0.0 {
+            bail!("PreambleDetector needs a threshold in [0, 1] and a non-negative hysteresis");
+        }
+        let burst_len: Option<usize> = p.get_opt("burst_len")?;
+        match p.get_or::<String>("item", "c32".to_string())?.as_str() {
+            "c32" => {
+                let preamble: Vec<(f32, f32)> = p.get("preamble")?;
+                let preamble: Vec<Complex32> =
+                    preamble.iter().map(|x| Complex32::new(x.0, x.1)).collect();
+                preamble_detector(&preamble, threshold, hysteresis, burst_len)
+            }
+            "u8" => preamble_detector(
+                &p.get::<Vec<u8>>("preamble")?,
+                threshold,
+                hysteresis,
+                burst_len,
+            ),
+            t => bail!("PreambleDetector does not support items of type `{}`", t),
+        }
+    });
+    r.register("QuadratureDemod", |p| {
+        if let Some(gain) = p.get_opt("gain")? {
+            return Ok(QuadratureDemod::new(gain));
+        }
+        let deviation: f64 = p.get("deviation")?;
+        let sample_rate: f64 = p.get("sample_rate")?;
+        if deviation <= 0.0 || sample_rate <= 0.0 {
+            bail!("QuadratureDemod needs a positive deviation and sample rate");
+        }
+        Ok(QuadratureDemod::fm(deviation, sample_rate))
+    });
+    r.register("RationalResampler", |p| {
+        let (interp, decim) = (p.get("interp")?, p.get("decim")?);
+        if interp == 0 || decim == 0 {
+            bail!("resampling factors have to be positive");
+        }
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(RationalResamplerBuilder::<f32>::new(interp, decim).build()),
+            "c32" => Ok(RationalResamplerBuilder::<Complex32>::new(interp, decim).build()),
+            t => bail!("RationalResampler does not support items of type `{}`", t),
+        }
+    });
+    r.register("ReedSolomonDecoder", |p| {
+        Ok(ReedSolomonDecoder::new(nroots(p)?))
+    });
+    r.register("ReedSolomonEncoder", |p| {
+        Ok(ReedSolomonEncoder::new(nroots(p)?))
+    });
+    r.register("Selector", |p| {
+        let n_inputs: usize = p.get("n_inputs")?;
+        let n_outputs: usize = p.get("n_outputs")?;
+        if n_inputs == 0 || n_outputs == 0 {
+            bail!("Selector needs at least one input and output");
+        }
+        Ok(Selector::new(item_size(p)?, n_inputs, n_outputs))
+    });
+    r.register("SignalSource", |p| {
+        let sample_rate: f64 = p.get("sample_rate")?;
+        if sample_rate <= 0.0 {
+            bail!("sample rate has to be positive");
+        }
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(signal_source::<f32>(p, sample_rate)?),
+            "c32" => Ok(signal_source::<Complex32>(p, sample_rate)?),
+            t => bail!("SignalSource does not support items of type `{}`", t),
+        }
+    });
+    r.register("SinglePoleIir", |p| {
+        let alpha: f32 = p.get("alpha")?;
+        if alpha <= 0.0 || alpha > 1.0 {
+            bail!("alpha has to be in (0, 1]");
+        }
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(SinglePoleIir::<f32>::new(alpha)),
+            "c32" => Ok(SinglePoleIir::<Complex32>::new(alpha)),
+            t => bail!("SinglePoleIir does not support items of type `{}`", t),
+        }
+    });
+    r.register("SsbDemod", |p| {
+        let sample_rate: f64 = p.get("sample_rate")?;
+        let bandwidth: f64 = p.get_or("bandwidth", 2800.0)?;
+        let transition_width: f64 = p.get_or("transition_width", 200.0)?;
+        if bandwidth <= 0.0 || bandwidth >= sample_rate / 2.0 || transition_width <= 0.0 {
+            bail!("SsbDemod needs a bandwidth in (0, sample_rate / 2) and a positive transition width");
+        }
+        Ok(SsbDemodBuilder::new(sample_rate)
+            .sideband(p.get_or("sideband", Sideband::Upper)?)
+            .bandwidth(bandwidth)
+            .transition_width(transition_width)
+            .build())
+    });
+    r.register("StreamMux", |p| {
+        let lengths: Vec<usize> = p.get("lengths")?;
+        if lengths.is_empty() || lengths.contains(&0) {
+            bail!("StreamMux needs at least one input and positive lengths");
+        }
+        Ok(StreamMux::new(item_size(p)?, lengths))
+    });
+    r.register("StreamToPdu", |p| {
+        let length: PduLength = p.get("length")?;
+        if length == PduLength::Fixed(0) {
+            bail!("chunks have to have at least one item");
+        }
+        match p.get_or::<String>("item", "u8".to_string())?.as_str() {
+            "u8" => Ok(StreamToPdu::<u8>::new(length)),
+            "f32" => Ok(StreamToPdu::<f32>::new(length)),
+            t => bail!("StreamToPdu does not support items of type `{}`", t),
+        }
+    });
+    r.register("StreamToVector", |p| {
+        Ok(StreamToVector::new(item_size(p)?, vector_len(p)?))
+    });
+    r.register("Subtract", |p| {
+        let n = n_inputs(p)?;
+        match p.get_or::<String>("item", "f32".to_string())?.as_str() {
+            "f32" => Ok(Subtract::<f32>::new(n)),
+            "f64" => Ok(Subtract::<f64>::new(n)),
+            "i16" => Ok(Subtract::<i16>::new(n)),
+            "c32" => Ok(Subtract::<Complex32>::new(n)),
+            t => bail!("Subtract does not support items of type `{}`", t),
+        }
+    });
+    r.register("SymbolSync", |p| {
+        let sps: f64 = p.get("sps")?;
+        if sps < 2.0 {
+            bail!("SymbolSync needs at least two samples per symbol");
+        }
+        let phases: usize = p.get_or("phases", 32)?;
+        if phases == 0 {
+            bail!("SymbolSync needs at least one filter phase");
+        }
+        let mut b = SymbolSyncBuilder::new(sps)
+            .detector(p.get_or("detector", TimingErrorDetector::Gardner)?)
+            .phases(phases);
+        if let Some(bandwidth) = p.get_opt("bandwidth")? {
+            b = b.bandwidth(bandwidth);
+        }
+        if let Some(damping) = p.get_opt("damping")? {
+            b = b.damping(damping);
+        }
+        if let Some(max_deviation) = p.get_opt("max_deviation")? {
+            b = b.max_deviation(max_deviation);
+        }
+        Ok(b.build())
+    });
+    r.register("SymbolsToBits", |p| {
+        let bits: usize = p.get("bits_per_symbol")?;
+        if !(1..=8).contains(&bits) {
+            bail!("bits per symbol have to be in [1, 8]");
+        }
+        Ok(SymbolsToBits::new(bits))
+    });
+    r.register("Valve", |p| {
+        Ok(Valve::new(item_size(p)?, p.get_or("open", true)?))
+    });
+    r.register("VectorToStream", |p| {
+        Ok(VectorToStream::new(item_size(p)?, vector_len(p)?))
+    });
+    r.register("ViterbiDecoder", |p| {
+        Ok(ViterbiDecoder::new(convolutional_code(p)?))
+    });
+    r.register("WelchPsd", |p| {
+        let fft_size: usize = p.get_or("fft_size", 2048)?;
+        let overlap: usize = p.get_or("overlap", fft_size / 2)?;
+        let segments: usize = p.get_or("segments", 8)?;
+        let sample_rate: f64 = p.get_or("sample_rate", 1.0)?;
+        if fft_size == 0 || overlap >= fft_size || segments == 0 || sample_rate <= 0.0 {
+            bail!("WelchPsd needs a positive FFT size, sample rate, and number of segments, and an overlap smaller than the FFT size");
+        }
+        Ok(WelchPsdBuilder::new(fft_size)
+            .overlap(overlap)
+            .segments(segments)
+            .window(p.get_or("window", Window::Hann)?)
+            .sample_rate(sample_rate)
+            .shift(p.get_or("shift", true)?)
+            .vector(p.get_or("vector", false)?)
+            .build())
+    });
+    r.register("Whitener", |p| {
+        let degree: u32 = p.get_or("degree", 9)?;
+        if !(1..=32).contains(&degree) {
+            bail!("LFSR degree has to be in [1, 32]");
+        }
+        Ok(Whitener::new(Lfsr::new(
+            p.get_or("mask", 0x21)?,
+            p.get_or("seed", 0x1ff)?,
+            degree,
+        )))
+    });
+    r.register("WireToComplex", |p| {
+        match p.get_or::<String>("item", "i16".to_string())?.as_str() {
+            "i16" => wire::<i16>(p, WireToComplex::<i16>::new),
+            "i8" => wire::<i8>(p, WireToComplex::<i8>::new),
+            "u8" => wire::<u8>(p, WireToComplex::<u8>::new),
+            t => bail!("WireToComplex does not support items of type `{}`", t),
+        }
+    });
+    r.register("WireToFloat", |p| {
+        match p.get_or::<String>("item", "i16".to_string())?.as_str() {
+            "i16" => wire::<i16>(p, WireToFloat::<i16>::new),
+            "i8" => wire::<i8>(p, WireToFloat::<i8>::new),
+            "u8" => wire::<u8>(p, WireToFloat::<u8>::new),
+            t => bail!("WireToFloat does not support items of type `{}`", t),
+        }
+    });
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        r.register("FileSink", |p| {
+            Ok(FileSinkBuilder::new(item_size(p)?, &p.get::<String>("file")?).build())
+        });
+        r.register("FileSource", |p| {
+            Ok(FileSourceBuilder::new(item_size(p)?, p.get("file")?).build())
+        });
+        r.register("MessageSource", |p| {
+            let interval = Duration::from_secs_f64(p.get("interval")?);
+            let mut b = MessageSourceBuilder::new(p.get_or("message", Pmt::Null)?, interval);
+            if let Some(n) = p.get_opt("n_messages")? {
+                b = b.n_messages(n);
+            }
+            Ok(b.build())
+        });
+        r.register("TcpSink", |p| {
+            Ok(TcpSinkBuilder::new(p.get("port")?).build())
+        });
+        r.register("TcpSource", |p| {
+            Ok(TcpSourceBuilder::new(p.get("port")?).build())
+        });
+        r.register("Throttle", |p| {
+            Ok(ThrottleBuilder::new(item_size(p)?, p.get("rate")?).build())
+        });
+        r.register("WavSink", |p| {
+            let channels: u16 = p.get_or("channels", 1)?;
+            if channels == 0 {
+                bail!("WAV file needs at least one channel");
+            }
+            Ok(
+                WavSinkBuilder::new(&p.get::<String>("file")?, p.get("sample_rate")?)
+                    .channels(channels)
+                    .build(),
+            )
+        });
+    }
+
+    r
+}
+
+fn agc<A: AgcSample>(p: &BlockParams, max_gain: f32) -> Result<Block> {
+    Ok(AgcBuilder::<A>::new()
+        .reference(p.get_or("reference", 1.0)?)
+        .attack(p.get_or("attack", 0.1)?)
+        .decay(p.get_or("decay", 0.01)?)
+        .max_gain(max_gain)
+        .gain(p.get_or("gain", 1.0)?)
+        .build())
+}
+
+fn noise_source<A: NoiseSample>(p: &BlockParams, probability: f32) -> Result<Block> {
+    let mut b = NoiseSourceBuilder::<A>::new(p.get_or("kind", NoiseType::Gaussian)?)
+        .amplitude(p.get_or("amplitude", 1.0)?)
+        .probability(probability);
+    if let Some(seed) = p.get_opt("seed")? {
+        b = b.seed(seed);
+    }
+    Ok(b.build())
+}
+
+fn signal_source<A: SignalSample>(p: &BlockParams, sample_rate: f64) -> Result<Block> {
+    Ok(SignalSourceBuilder::<A>::new(sample_rate)
+        .waveform(p.get_or("waveform", Waveform::Sine)?)
+        .frequency(p.get_or("frequency", 1000.0)?)
+        .amplitude(p.get_or("amplitude", 1.0)?)
+        .offset(p.get_or("offset", 0.0)?)
+        .phase(p.get_or("phase", 0.0)?)
+        .build())
+}
+
+fn preamble_detector<A: PreambleSample>(
+    preamble: &[A],
+    threshold: f32,
+    hysteresis: f32,
+    burst_len: Option<usize>,
+) -> Result<Block> {
+    if preamble.is_empty() {
+        bail!("preamble must not be empty");
+    }
+    let mut b = PreambleDetectorBuilder::new(preamble)
+        .threshold(threshold)
+        .hysteresis(hysteresis);
+    if let Some(n) = burst_len {
+        b = b.forward(n);
+    }
+    Ok(b.build())
+}
+
+// code given by constraint length and polynomials, defaulting to the K = 7 code
+fn convolutional_code(p: &BlockParams) -> Result<ConvolutionalCode> {
+    let k: usize = p.get_or("constraint_length", 7)?;
+    let polynomials: Vec<u32> = p.get_or("polynomials", vec![0o171, 0o133])?;
+    if !(2..=16).contains(&k) || polynomials.is_empty() {
+        bail!("convolutional code needs a constraint length in [2, 16] and polynomials");
+    }
+    Ok(ConvolutionalCode::new(k, &polynomials))
+}
+
+fn nroots(p: &BlockParams) -> Result<usize> {
+    let nroots: usize = p.get_or("nroots", 32)?;
+    if !(1..255).contains(&nroots) {
+        bail!("number of Reed-Solomon roots has to be in [1, 254]");
+    }
+    Ok(nroots)
+}
+
+// number of ports and block length
+fn interleaving(p: &BlockParams, ports: &str) -> Result<(usize, usize)> {
+    let n: usize = p.get(ports)?;
+    let block_len: usize = p.get_or("block_len", 1)?;
+    if n == 0 || block_len == 0 {
+        bail!("number of ports and block length have to be positive");
+    }
+    Ok((n, block_len))
+}
+
+fn n_inputs(p: &BlockParams) -> Result<usize> {
+    let n: usize = p.get_or("n_inputs", 2)?;
+    if n == 0 {
+        bail!("number of inputs has to be positive");
+    }
+    Ok(n)
+}
+
+// conversion from or to a wire format, scaled to its full range by default
+fn wire<A: WireSample>(p: &BlockParams, new: fn(f32, bool) -> Block) -> Result<Block> {
+    let scale: f32 = p.get_or("scale", A::FULL_SCALE)?;
+    if scale <= 0.0 {
+        bail!("scale has to be positive");
+    }
+    Ok(new(scale, p.get_or("byte_swap", false)?))
+}
+
+fn vector_len(p: &BlockParams) -> Result<usize> {
+    let vector_len: usize = p.get("vector_len")?;
+    if vector_len == 0 {
+        bail!("vector length has to be positive");
+    }
+    Ok(vector_len)
+}
+
+// built-in constellation, given by name and mapping
+fn constellation(p: &BlockParams) -> Result<Constellation> {
+    let mapping = p.get_or("mapping", Mapping::Gray)?;
+    Ok(match p.get::<String>("constellation")?.as_str() {
+        "bpsk" => Constellation::psk(2, mapping),
+        "qpsk" => Constellation::psk(4, mapping),
+        "8psk" => Constellation::psk(8, mapping),
+        "16qam" => Constellation::qam(16, mapping),
+        "64qam" => Constellation::qam(64, mapping),
+        c => bail!("unknown constellation `{}`", c),
+    })
+}
+
+fn item_size(p: &BlockParams) -> Result<usize> {
+    if let Some(s) = p.get_opt::<usize>("item_size")? {
+        return Ok(s);
+    }
+    let s = match p.get::<String>("item")?.as_str() {
+        "u8" | "i8" => 1,
+        "u16" | "i16" => 2,
+        "u32" | "i32" | "f32" => 4,
+        "u64" | "i64" | "f64" | "c32" => 8,
+        "c64" => 16,
+        t => bail!("unknown item type `{}`", t),
+    };
+    Ok(s)
+}
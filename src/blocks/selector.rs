@@ -0,0 +1,128 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Forwards one of the inputs `in0`, `in1`, ... to one of the outputs `out0`, `out1`, ...
+///
+/// The message inputs `input` and `output` take the index of the port as [Pmt::U32] or
+/// [Pmt::U64] and return the current index, i.e., a [Pmt::Null] queries it. Items of the
+/// other inputs are dropped, so that their upstream blocks do not stall. The block terminates
+/// when the selected input is finished.
+pub struct Selector {
+    item_size: usize,
+    input: usize,
+    output: usize,
+    n_inputs: usize,
+    n_outputs: usize,
+}
+
+impl Selector {
+    pub fn new(item_size: usize, n_inputs: usize, n_outputs: usize) -> Block {
+        assert!(
+            n_inputs > 0 && n_outputs > 0,
+            "Selector needs at least one input and output"
+        );
+        let mut sio = StreamIoBuilder::new();
+        for i in 0..n_inputs {
+            sio = sio.add_input(&format!("in{}", i), item_size);
+        }
+        for i in 0..n_outputs {
+            sio = sio.add_output(&format!("out{}", i), item_size);
+        }
+        Block::new_sync(
+            BlockMetaBuilder::new("Selector").build(),
+            sio.build(),
+            MessageIoBuilder::new()
+                .add_sync_input("input", Self::select_input)
+                .add_sync_input("output", Self::select_output)
+                .build(),
+            Selector {
+                item_size,
+                input: 0,
+                output: 0,
+                n_inputs,
+                n_outputs,
+            },
+        )
+    }
+
+    fn select_input(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        select("input", &mut self.input, self.n_inputs, &p);
+        Ok(Pmt::U32(self.input as u32))
+    }
+
+    fn select_output(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        select("output", &mut self.output, self.n_outputs, &p);
+        Ok(Pmt::U32(self.output as u32))
+    }
+}
+
+// Sets the index for a valid port number, only queries it for `Null`.
+fn select(name: &str, index: &mut usize, n: usize, p: &Pmt) {
+    let i = match p {
+        Pmt::U32(i) => *i as usize,
+        Pmt::U64(i) => *i as usize,
+        Pmt::Null => return,
+        _ => {
+            warn!("Selector: ignoring invalid {} {:?}", name, p);
+            return;
+        }
+    };
+    if i < n {
+        *index = i;
+    } else {
+        warn!("Selector: {} {} out of range", name, i);
+    }
+}
+
+#[async_trait]
+impl SyncKernel for Selector {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(self.input).slice::<u8>();
+        let o = sio.output(self.output).slice::<u8>();
+
+        let n = cmp::min(i.len(), o.len()) / self.item_size;
+        let n_bytes = n * self.item_size;
+        o[0..n_bytes].copy_from_slice(&i[0..n_bytes]);
+
+        if sio.input(self.input).finished() && n_bytes == i.len() {
+            io.finished = true;
+        }
+
+        sio.input(self.input).consume(n);
+        sio.output(self.output).produce(n);
+
+        for k in (0..self.n_inputs).filter(|k| *k != self.input) {
+            let n = sio.input(k).slice::<u8>().len() / self.item_size;
+            sio.input(k).consume(n);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,308 @@
+use num_complex::Complex;
+use serde::Deserialize;
+use std::f32::consts::PI;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::dsp::Nco;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Waveforms of the [SignalSource], given for a phase `p` in turns.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Waveform {
+    /// `sin(2 pi p)`
+    Sine,
+    /// `cos(2 pi p)`
+    Cosine,
+    /// `exp(j 2 pi p)`, of which real sources output the real part.
+    ComplexExp,
+    /// One in the first half of a period, minus one in the second.
+    Square,
+    /// Starts at one, ramps down to minus one at half a period, and back.
+    Triangle,
+    /// Ramps from minus one to one.
+    Sawtooth,
+    /// One, independent of the phase.
+    Constant,
+}
+
+impl Waveform {
+    fn real(&self, phase: u32) -> f32 {
+        let p = (phase as f64 / 4294967296.0) as f32;
+        match self {
+            Waveform::Sine => (2.0 * PI * p).sin(),
+            Waveform::Cosine | Waveform::ComplexExp => (2.0 * PI * p).cos(),
+            Waveform::Square => {
+                if p < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Triangle => 4.0 * (p - 0.5).abs() - 1.0,
+            Waveform::Sawtooth => 2.0 * p - 1.0,
+            Waveform::Constant => 1.0,
+        }
+    }
+}
+
+/// Items of a [SignalSource].
+pub trait SignalSample: Copy + Send + 'static {
+    /// Unit-amplitude `waveform` at the raw [Nco] `phase`.
+    fn generate(waveform: Waveform, phase: u32) -> Self;
+    /// Scales by `amplitude` and adds `offset` to the real part.
+    fn scale(self, amplitude: f32, offset: f32) -> Self;
+}
+
+impl SignalSample for f32 {
+    fn generate(waveform: Waveform, phase: u32) -> f32 {
+        waveform.real(phase)
+    }
+
+    fn scale(self, amplitude: f32, offset: f32) -> f32 {
+        self * amplitude + offset
+    }
+}
+
+/// Complex sources output the waveform in the real and its quadrature component, i.e., the
+/// waveform a quarter period earlier, in the imaginary part. `Cosine` is, therefore, the same as
+/// `ComplexExp`, while `Constant` is real.
+impl SignalSample for Complex<f32> {
+    fn generate(waveform: Waveform, phase: u32) -> Complex<f32> {
+        if waveform == Waveform::Constant {
+            return Complex::new(1.0, 0.0);
+        }
+        Complex::new(
+            waveform.real(phase),
+            waveform.real(phase.wrapping_sub(1 << 30)),
+        )
+    }
+
+    fn scale(self, amplitude: f32, offset: f32) -> Complex<f32> {
+        Complex::new(self.re * amplitude + offset, self.im * amplitude)
+    }
+}
+
+/// Periodic signal generator, driven by an [Nco].
+///
+/// The message inputs `freq` (in Hz), `amplitude`, `phase` (in radians), and `offset` take a
+/// [Pmt::Double] to change the parameter while running, and return the current value.
+pub struct SignalSource<A>
+where
+    A: SignalSample,
+{
+    waveform: Waveform,
+    nco: Nco,
+    sample_rate: f64,
+    amplitude: f32,
+    offset: f32,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> SignalSource<A>
+where
+    A: SignalSample,
+{
+    pub fn new(
+        waveform: Waveform,
+        frequency: f64,
+        sample_rate: f64,
+        amplitude: f32,
+        offset: f32,
+        phase: f64,
+    ) -> Block {
+        assert!(sample_rate > 0.0, "sample rate has to be positive");
+        let mut nco = Nco::new(frequency, sample_rate);
+        nco.set_phase(phase);
+        Block::new_sync(
+            BlockMetaBuilder::new("SignalSource").build(),
+            StreamIoBuilder::new()
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::<Self>::new()
+                .add_sync_input("freq", Self::freq_handler)
+                .add_sync_input("amplitude", Self::amplitude_handler)
+                .add_sync_input("phase", Self::phase_handler)
+                .add_sync_input("offset", Self::offset_handler)
+                .build(),
+            SignalSource {
+                waveform,
+                nco,
+                sample_rate,
+                amplitude,
+                offset,
+                _p: std::marker::PhantomData,
+            },
+        )
+    }
+
+    fn freq_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        if let Some(f) = double(&p, "freq") {
+            self.nco.set_freq(f, self.sample_rate);
+        }
+        Ok(Pmt::Double(self.nco.freq(self.sample_rate)))
+    }
+
+    fn amplitude_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        if let Some(a) = double(&p, "amplitude") {
+            self.amplitude = a as f32;
+        }
+        Ok(Pmt::Double(self.amplitude as f64))
+    }
+
+    fn phase_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        if let Some(ph) = double(&p, "phase") {
+            self.nco.set_phase(ph);
+        }
+        Ok(Pmt::Double(self.nco.phase()))
+    }
+
+    fn offset_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        if let Some(o) = double(&p, "offset") {
+            self.offset = o as f32;
+        }
+        Ok(Pmt::Double(self.offset as f64))
+    }
+}
+
+// `Null` only queries the current value
+fn double(p: &Pmt, name: &str) -> Option<f64> {
+    match p {
+        Pmt::Double(v) => Some(*v),
+        Pmt::Null => None,
+        _ => {
+            warn!("SignalSource: ignoring invalid {} {:?}", name, p);
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl<A> SyncKernel for SignalSource<A>
+where
+    A: SignalSample,
+{
+    fn work(
+        &mut self,
+        _io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = sio.output(0).slice::<A>();
+
+        for v in o.iter_mut() {
+            *v =
+                A::generate(self.waveform, self.nco.raw_phase()).scale(self.amplitude, self.offset);
+            self.nco.step();
+        }
+
+        sio.output(0).produce(o.len());
+
+        Ok(())
+    }
+}
+
+pub struct SignalSourceBuilder<A>
+where
+    A: SignalSample,
+{
+    waveform: Waveform,
+    frequency: f64,
+    sample_rate: f64,
+    amplitude: f32,
+    offset: f32,
+    phase: f64,
+    _p: std::marker::PhantomData<A>,
+}
+
+impl<A> SignalSourceBuilder<A>
+where
+    A: SignalSample,
+{
+    /// Sine with unit amplitude at 1 kHz.
+    pub fn new(sample_rate: f64) -> SignalSourceBuilder<A> {
+        SignalSourceBuilder {
+            waveform: Waveform::Sine,
+            frequency: 1000.0,
+            sample_rate,
+            amplitude: 1.0,
+            offset: 0.0,
+            phase: 0.0,
+            _p: std::marker::PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn waveform(mut self, waveform: Waveform) -> SignalSourceBuilder<A> {
+        self.waveform = waveform;
+        self
+    }
+
+    /// Frequency in Hz, which can be negative.
+    #[must_use]
+    pub fn frequency(mut self, frequency: f64) -> SignalSourceBuilder<A> {
+        self.frequency = frequency;
+        self
+    }
+
+    #[must_use]
+    pub fn amplitude(mut self, amplitude: f32) -> SignalSourceBuilder<A> {
+        self.amplitude = amplitude;
+        self
+    }
+
+    #[must_use]
+    pub fn offset(mut self, offset: f32) -> SignalSourceBuilder<A> {
+        self.offset = offset;
+        self
+    }
+
+    /// Initial phase in radians.
+    #[must_use]
+    pub fn phase(mut self, phase: f64) -> SignalSourceBuilder<A> {
+        self.phase = phase;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        SignalSource::<A>::new(
+            self.waveform,
+            self.frequency,
+            self.sample_rate,
+            self.amplitude,
+            self.offset,
+            self.phase,
+        )
+    }
+}
@@ -0,0 +1,75 @@
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::IirSample;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Exponential averaging filter `y[n] = y[n-1] + alpha * (x[n] - y[n-1])`.
+pub struct SinglePoleIir<A>
+where
+    A: IirSample,
+{
+    alpha: f32,
+    avg: A,
+}
+
+impl<A> SinglePoleIir<A>
+where
+    A: IirSample,
+{
+    pub fn new(alpha: f32) -> Block {
+        assert!(alpha > 0.0 && alpha <= 1.0, "alpha has to be in (0, 1]");
+        Block::new_sync(
+            BlockMetaBuilder::new("SinglePoleIir").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<A>())
+                .add_output("out", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            SinglePoleIir {
+                alpha,
+                avg: A::ZERO,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl<A> SyncKernel for SinglePoleIir<A>
+where
+    A: IirSample,
+{
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        let o = sio.output(0).slice::<A>();
+
+        let n = std::cmp::min(i.len(), o.len());
+        for (x, y) in i[0..n].iter().zip(o[0..n].iter_mut()) {
+            self.avg = (self.avg + (*x - self.avg) * self.alpha).flush();
+            *y = self.avg;
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use num_complex::Complex;
+use serde::Deserialize;
+use std::f64::consts::PI;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::HasFirImpl;
+use crate::dsp::design;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Sideband of an [SsbDemod].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Sideband {
+    /// Frequencies above the carrier.
+    Upper,
+    /// Frequencies below the carrier.
+    Lower,
+}
+
+/// SSB demodulator for a complex baseband with the suppressed carrier at DC.
+///
+/// A complex filter keeps only one sideband, and the real part of its output is the audio
+/// signal. [SsbDemodBuilder] designs the filter.
+pub struct SsbDemod {
+    taps: Vec<Complex<f32>>,
+    // input samples to skip before the next output
+    skip: usize,
+}
+
+impl SsbDemod {
+    pub fn new(taps: &[Complex<f32>]) -> Block {
+        assert!(!taps.is_empty(), "FIR filter needs at least one tap");
+        Block::new_sync(
+            BlockMetaBuilder::new("SsbDemod").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_output("out", mem::size_of::<f32>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            SsbDemod {
+                taps: taps.to_vec(),
+                skip: 0,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for SsbDemod {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<f32>();
+
+        let n_taps = self.taps.len();
+
+        let mut j = self.skip;
+        let mut produced = 0;
+        while j + n_taps <= i.len() && produced < o.len() {
+            o[produced] = Complex::<f32>::dot(&i[j..], &self.taps).re;
+            produced += 1;
+            j += 1;
+        }
+
+        let consumed = std::cmp::min(j, i.len());
+        self.skip = j - consumed;
+
+        if sio.input(0).finished() && j + n_taps > i.len() {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(produced);
+
+        Ok(())
+    }
+}
+
+pub struct SsbDemodBuilder {
+    sample_rate: f64,
+    sideband: Sideband,
+    bandwidth: f64,
+    transition_width: f64,
+}
+
+impl SsbDemodBuilder {
+    /// Upper sideband with 2.8 kHz bandwidth and 200 Hz transition width.
+    pub fn new(sample_rate: f64) -> SsbDemodBuilder {
+        SsbDemodBuilder {
+            sample_rate,
+            sideband: Sideband::Upper,
+            bandwidth: 2800.0,
+            transition_width: 200.0,
+        }
+    }
+
+    #[must_use]
+    pub fn sideband(mut self, sideband: Sideband) -> SsbDemodBuilder {
+        self.sideband = sideband;
+        self
+    }
+
+    /// Audio bandwidth in Hz, starting at the carrier.
+    #[must_use]
+    pub fn bandwidth(mut self, bandwidth: f64) -> SsbDemodBuilder {
+        self.bandwidth = bandwidth;
+        self
+    }
+
+    /// Transition width of the sideband filter in Hz, defining its length.
+    #[must_use]
+    pub fn transition_width(mut self, transition_width: f64) -> SsbDemodBuilder {
+        self.transition_width = transition_width;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        assert!(
+            self.bandwidth > 0.0 && self.bandwidth < self.sample_rate / 2.0,
+            "bandwidth has to be in (0, sample_rate / 2)"
+        );
+        let (n, window) = design::kaiser_order(60.0, self.transition_width, self.sample_rate);
+        let lowpass = design::lowpass(n, self.bandwidth / 2.0, self.sample_rate, window);
+
+        // the lowpass, shifted to the center of the sideband; the window is applied in input
+        // order, so the taps rotate in the opposite direction
+        let center = match self.sideband {
+            Sideband::Upper => self.bandwidth / 2.0,
+            Sideband::Lower => -self.bandwidth / 2.0,
+        };
+        let w = 2.0 * PI * center / self.sample_rate;
+        let taps: Vec<Complex<f32>> = lowpass
+            .iter()
+            .enumerate()
+            .map(|(k, t)| Complex::from_polar(*t, (-w * k as f64) as f32))
+            .collect();
+
+        SsbDemod::new(&taps)
+    }
+}
@@ -0,0 +1,92 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Multiplexes the inputs `in0`, `in1`, ..., taking `lengths[i]` items from input `i` in turn.
+///
+/// Unlike [Interleave](crate::blocks::Interleave), the inputs can contribute different numbers
+/// of items, e.g., to insert a header before each payload. The block terminates when the input
+/// it waits for is finished.
+pub struct StreamMux {
+    item_size: usize,
+    lengths: Vec<usize>,
+    // input and items left in its turn
+    current: usize,
+    remaining: usize,
+}
+
+impl StreamMux {
+    pub fn new(item_size: usize, lengths: Vec<usize>) -> Block {
+        assert!(!lengths.is_empty(), "StreamMux needs at least one input");
+        assert!(
+            lengths.iter().all(|l| *l > 0),
+            "StreamMux needs positive lengths"
+        );
+        let mut sio = StreamIoBuilder::new();
+        for i in 0..lengths.len() {
+            sio = sio.add_input(&format!("in{}", i), item_size);
+        }
+        Block::new_sync(
+            BlockMetaBuilder::new("StreamMux").build(),
+            sio.add_output("out", item_size).build(),
+            MessageIoBuilder::<Self>::new().build(),
+            StreamMux {
+                item_size,
+                remaining: lengths[0],
+                lengths,
+                current: 0,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for StreamMux {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let o = sio.output(0).slice::<u8>();
+        let n_out = o.len() / self.item_size;
+
+        let mut produced = 0;
+        while produced < n_out {
+            let i = sio.input(self.current).slice::<u8>();
+            let n_in = i.len() / self.item_size;
+            let n = cmp::min(cmp::min(n_in, n_out - produced), self.remaining);
+            if n == 0 {
+                if sio.input(self.current).finished() {
+                    io.finished = true;
+                }
+                break;
+            }
+
+            o[produced * self.item_size..(produced + n) * self.item_size]
+                .copy_from_slice(&i[0..n * self.item_size]);
+            sio.input(self.current).consume(n);
+            produced += n;
+            self.remaining -= n;
+
+            if self.remaining == 0 {
+                self.current = (self.current + 1) % self.lengths.len();
+                self.remaining = self.lengths[self.current];
+            }
+        }
+
+        sio.output(0).produce(produced);
+
+        Ok(())
+    }
+}
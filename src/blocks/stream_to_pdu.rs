@@ -0,0 +1,186 @@
+use serde::Deserialize;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::AsyncKernel;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// Item type of the [StreamToPdu] and [PduToStream](crate::blocks::PduToStream) blocks.
+pub trait PduItem: Copy + Default + Send + Sync + 'static {
+    /// Number of items of the length prefix.
+    const PREFIX_LEN: usize;
+
+    /// Length of the chunk that follows the prefix or `None` if the prefix is invalid.
+    fn parse_prefix(prefix: &[Self]) -> Option<usize>;
+
+    /// Length prefix for a chunk or `None` if the length cannot be represented.
+    fn prefix(len: usize) -> Option<Vec<Self>>;
+
+    fn to_pmt(items: Vec<Self>) -> Pmt;
+
+    fn from_pmt(p: Pmt) -> Option<Vec<Self>>;
+}
+
+/// Bytes as [Pmt::Blob] with the length as big-endian `u16` prefix.
+impl PduItem for u8 {
+    const PREFIX_LEN: usize = 2;
+
+    fn parse_prefix(prefix: &[Self]) -> Option<usize> {
+        Some(u16::from_be_bytes([prefix[0], prefix[1]]) as usize)
+    }
+
+    fn prefix(len: usize) -> Option<Vec<Self>> {
+        u16::try_from(len).ok().map(|l| l.to_be_bytes().to_vec())
+    }
+
+    fn to_pmt(items: Vec<Self>) -> Pmt {
+        Pmt::Blob(items)
+    }
+
+    fn from_pmt(p: Pmt) -> Option<Vec<Self>> {
+        match p {
+            Pmt::Blob(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Samples as [Pmt::VecF32] with the length as one item prefix.
+impl PduItem for f32 {
+    const PREFIX_LEN: usize = 1;
+
+    fn parse_prefix(prefix: &[Self]) -> Option<usize> {
+        let l = prefix[0];
+        if l >= 0.0 && l.fract() == 0.0 && l <= (1 << 24) as f32 {
+            Some(l as usize)
+        } else {
+            None
+        }
+    }
+
+    fn prefix(len: usize) -> Option<Vec<Self>> {
+        if len <= 1 << 24 {
+            Some(vec![len as f32])
+        } else {
+            None
+        }
+    }
+
+    fn to_pmt(items: Vec<Self>) -> Pmt {
+        Pmt::VecF32(items)
+    }
+
+    fn from_pmt(p: Pmt) -> Option<Vec<Self>> {
+        match p {
+            Pmt::VecF32(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Length of the chunks of a [StreamToPdu].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PduLength {
+    Fixed(usize),
+    /// Each chunk starts with its length, given in-band as [PduItem::prefix], which is not part
+    /// of the message.
+    Prefixed,
+}
+
+/// Cuts a stream into chunks that are posted as messages to the `out` message output.
+///
+/// An incomplete chunk at the end of the stream is dropped.
+pub struct StreamToPdu<A: PduItem> {
+    length: PduLength,
+    buffer: Vec<A>,
+}
+
+impl<A: PduItem> StreamToPdu<A> {
+    pub fn new(length: PduLength) -> Block {
+        assert!(
+            length != PduLength::Fixed(0),
+            "chunks have to have at least one item"
+        );
+        Block::new_async(
+            BlockMetaBuilder::new("StreamToPdu").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<A>())
+                .build(),
+            MessageIoBuilder::new().add_output("out").build(),
+            StreamToPdu::<A> {
+                length,
+                buffer: Vec::new(),
+            },
+        )
+    }
+
+    // start and length of the next complete chunk in the buffer, skipping invalid prefixes
+    fn next_chunk(&mut self) -> Option<(usize, usize)> {
+        match self.length {
+            PduLength::Fixed(n) => {
+                if self.buffer.len() >= n {
+                    Some((0, n))
+                } else {
+                    None
+                }
+            }
+            PduLength::Prefixed => loop {
+                if self.buffer.len() < A::PREFIX_LEN {
+                    return None;
+                }
+                match A::parse_prefix(&self.buffer[..A::PREFIX_LEN]) {
+                    Some(n) if self.buffer.len() >= A::PREFIX_LEN + n => {
+                        return Some((A::PREFIX_LEN, n));
+                    }
+                    Some(_) => return None,
+                    None => {
+                        warn!("StreamToPdu: skipping invalid length prefix");
+                        self.buffer.drain(..1);
+                    }
+                }
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl<A: PduItem> AsyncKernel for StreamToPdu<A> {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<A>();
+        self.buffer.extend_from_slice(i);
+        sio.input(0).consume(i.len());
+
+        while let Some((start, n)) = self.next_chunk() {
+            let chunk = self.buffer[start..start + n].to_vec();
+            self.buffer.drain(..start + n);
+            mio.post(0, A::to_pmt(chunk)).await;
+        }
+
+        if sio.input(0).finished() {
+            if !self.buffer.is_empty() {
+                debug!(
+                    "StreamToPdu: dropping {} items of incomplete chunk",
+                    self.buffer.len()
+                );
+            }
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Groups `vector_len` items of a stream into one vector item.
+///
+/// Remaining items at the end of the stream that do not fill a vector are dropped.
+pub struct StreamToVector {
+    // bytes per vector
+    vector_size: usize,
+    vector_len: usize,
+}
+
+impl StreamToVector {
+    pub fn new(item_size: usize, vector_len: usize) -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("StreamToVector").build(),
+            StreamIoBuilder::new()
+                .add_input("in", item_size)
+                .add_vector_output("out", item_size, vector_len)
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            StreamToVector {
+                vector_size: item_size * vector_len,
+                vector_len,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for StreamToVector {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        let o = sio.output(0).slice::<u8>();
+
+        let n = cmp::min(i.len(), o.len()) / self.vector_size;
+        let bytes = n * self.vector_size;
+        o[0..bytes].copy_from_slice(&i[0..bytes]);
+
+        if sio.input(0).finished() && i.len() - bytes < self.vector_size {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(n * self.vector_len);
+        sio.output(0).produce(n);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use num_complex::Complex;
+use serde::Deserialize;
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::blocks::rational_resampler::polyphase;
+use crate::blocks::HasFirImpl;
+use crate::dsp::design;
+use crate::dsp::LoopFilter;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Timing error detector of the [SymbolSync].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimingErrorDetector {
+    /// Gardner detector, using an additional sample between the symbols. It is independent of
+    /// the carrier phase.
+    Gardner,
+    /// Mueller and Müller detector, using decisions of BPSK or QPSK symbols. It only needs one
+    /// sample per symbol but requires carrier phase lock.
+    MuellerMuller,
+}
+
+/// Symbol timing recovery, outputting one sample per symbol.
+///
+/// The input, ideally matched filtered, has about `sps` samples per symbol. The samples at the
+/// estimated symbol times are interpolated with a polyphase filter bank. A second-order loop
+/// tracks the timing offset and the deviation of the symbol rate, which is limited to
+/// `max_deviation` times `sps`.
+///
+/// The `bandwidth` message input sets the loop bandwidth in radians per symbol as [Pmt::Double]
+/// and returns the current value.
+pub struct SymbolSync {
+    sps: f64,
+    detector: TimingErrorDetector,
+    filter: LoopFilter,
+    branches: Vec<Vec<f32>>,
+    diff_branches: Vec<Vec<f32>>,
+    // position of the next symbol, relative to the first input sample
+    pos: f64,
+    prev: Option<Complex<f32>>,
+}
+
+impl SymbolSync {
+    pub fn new(
+        sps: f64,
+        detector: TimingErrorDetector,
+        bandwidth: f64,
+        damping: f64,
+        max_deviation: f64,
+        phases: usize,
+        taps: &[f32],
+    ) -> Block {
+        assert!(
+            sps >= 2.0,
+            "timing recovery needs at least two samples per symbol"
+        );
+        assert!(phases > 0, "filter bank needs at least one phase");
+        assert!(!taps.is_empty(), "filter bank needs at least one tap");
+
+        let diff: Vec<f32> = taps
+            .iter()
+            .zip(taps.iter().skip(1).chain(std::iter::once(&0.0)))
+            .map(|(a, b)| b - a)
+            .collect();
+
+        Block::new_sync(
+            BlockMetaBuilder::new("SymbolSync").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_output("out", mem::size_of::<Complex<f32>>())
+                .build(),
+            MessageIoBuilder::<Self>::new()
+                .add_sync_input("bandwidth", Self::bandwidth_handler)
+                .build(),
+            SymbolSync {
+                sps,
+                detector,
+                filter: LoopFilter::new(bandwidth, damping, max_deviation * sps),
+                branches: polyphase(taps, phases),
+                diff_branches: polyphase(&diff, phases),
+                pos: 0.0,
+                prev: None,
+            },
+        )
+    }
+
+    fn bandwidth_handler(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::Double(b) if b >= 0.0 => self.filter.set_bandwidth(b),
+            Pmt::Null => {}
+            _ => warn!("SymbolSync: ignoring invalid bandwidth {:?}", p),
+        }
+        Ok(Pmt::Double(self.filter.bandwidth()))
+    }
+
+    // interpolated sample at `pos`, relative to the first sample of `input`
+    fn interpolate(&self, input: &[Complex<f32>], pos: f64) -> Complex<f32> {
+        let phases = self.branches.len() as f64;
+        let j = pos as usize;
+        let phase = (pos - j as f64) * phases;
+        let p = phase as usize;
+        let frac = (phase - p as f64) as f32;
+        let x = &input[j..];
+        <Complex<f32> as HasFirImpl<f32>>::dot(x, &self.branches[p])
+            + <Complex<f32> as HasFirImpl<f32>>::dot(x, &self.diff_branches[p]) * frac
+    }
+}
+
+fn decision(x: Complex<f32>) -> Complex<f32> {
+    Complex::new(x.re.signum(), x.im.signum())
+}
+
+#[async_trait]
+impl SyncKernel for SymbolSync {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<Complex<f32>>();
+        let len = self.branches[0].len();
+
+        let mut produced = 0;
+        let mut last = 0.0;
+        while self.pos as usize + len <= i.len() && produced < o.len() {
+            let omega = self.sps + self.filter.freq();
+            let y = self.interpolate(i, self.pos);
+
+            let error = match (self.detector, self.prev) {
+                (_, None) => 0.0,
+                (TimingErrorDetector::Gardner, Some(prev)) => {
+                    let mid = self.interpolate(i, f64::max(self.pos - omega / 2.0, 0.0));
+                    ((prev - y) * mid.conj()).re
+                }
+                (TimingErrorDetector::MuellerMuller, Some(prev)) => {
+                    (y * decision(prev).conj() - prev * decision(y).conj()).re
+                }
+            };
+
+            o[produced] = y;
+            produced += 1;
+            self.prev = Some(y);
+
+            last = self.pos;
+            // error in samples
+            let error = (error as f64).clamp(-1.0, 1.0) * self.sps / 2.0;
+            self.pos += self.sps + self.filter.advance(error);
+        }
+
+        let consumed = last as usize;
+        self.pos -= consumed as f64;
+
+        if sio.input(0).finished() && self.pos as usize + len > i.len() {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(consumed);
+        sio.output(0).produce(produced);
+
+        Ok(())
+    }
+}
+
+pub struct SymbolSyncBuilder {
+    sps: f64,
+    detector: TimingErrorDetector,
+    bandwidth: f64,
+    damping: f64,
+    max_deviation: f64,
+    phases: usize,
+    taps: Option<Vec<f32>>,
+}
+
+impl SymbolSyncBuilder {
+    /// Gardner timing recovery with a loop bandwidth of `2 * pi / 100` radians per symbol.
+    pub fn new(sps: f64) -> SymbolSyncBuilder {
+        SymbolSyncBuilder {
+            sps,
+            detector: TimingErrorDetector::Gardner,
+            bandwidth: 2.0 * std::f64::consts::PI / 100.0,
+            damping: std::f64::consts::FRAC_1_SQRT_2,
+            max_deviation: 0.01,
+            phases: 32,
+            taps: None,
+        }
+    }
+
+    #[must_use]
+    pub fn detector(mut self, detector: TimingErrorDetector) -> SymbolSyncBuilder {
+        self.detector = detector;
+        self
+    }
+
+    #[must_use]
+    pub fn bandwidth(mut self, bandwidth: f64) -> SymbolSyncBuilder {
+        self.bandwidth = bandwidth;
+        self
+    }
+
+    #[must_use]
+    pub fn damping(mut self, damping: f64) -> SymbolSyncBuilder {
+        self.damping = damping;
+        self
+    }
+
+    /// Maximum relative deviation of the symbol rate.
+    #[must_use]
+    pub fn max_deviation(mut self, max_deviation: f64) -> SymbolSyncBuilder {
+        self.max_deviation = max_deviation;
+        self
+    }
+
+    /// Number of sub-filters of the filter bank.
+    #[must_use]
+    pub fn phases(mut self, phases: usize) -> SymbolSyncBuilder {
+        self.phases = phases;
+        self
+    }
+
+    /// Interpolation filter at `phases` times the input rate, e.g., a matched filter. By default,
+    /// a lowpass is designed with [design::resampler].
+    #[must_use]
+    pub fn taps(mut self, taps: &[f32]) -> SymbolSyncBuilder {
+        self.taps = Some(taps.to_vec());
+        self
+    }
+
+    pub fn build(self) -> Block {
+        let taps = self
+            .taps
+            .unwrap_or_else(|| design::resampler(self.phases, 1.0));
+        SymbolSync::new(
+            self.sps,
+            self.detector,
+            self.bandwidth,
+            self.damping,
+            self.max_deviation,
+            self.phases,
+            &taps,
+        )
+    }
+}
@@ -0,0 +1,67 @@
+use std::mem;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Unpacks symbols, i.e., chunks of `bits_per_symbol` bits given as `u8` items, into bits, one
+/// per `u8` item, most significant bit first.
+pub struct SymbolsToBits {
+    bits_per_symbol: usize,
+}
+
+impl SymbolsToBits {
+    pub fn new(bits_per_symbol: usize) -> Block {
+        assert!(
+            (1..=8).contains(&bits_per_symbol),
+            "bits per symbol have to be in [1, 8]"
+        );
+        Block::new_sync(
+            BlockMetaBuilder::new("SymbolsToBits").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<u8>())
+                .add_output("out", mem::size_of::<u8>())
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            SymbolsToBits { bits_per_symbol },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for SymbolsToBits {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        let o = sio.output(0).slice::<u8>();
+
+        let k = self.bits_per_symbol;
+        let n = std::cmp::min(i.len(), o.len() / k);
+        for (s, bits) in i[0..n].iter().zip(o.chunks_exact_mut(k)) {
+            for (j, b) in bits.iter_mut().enumerate() {
+                *b = (s >> (k - 1 - j)) & 1;
+            }
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n * k);
+
+        if sio.input(0).finished() && n == i.len() {
+            io.finished = true;
+        }
+
+        Ok(())
+    }
+}
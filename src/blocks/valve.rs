@@ -0,0 +1,83 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Copies its input to its output while open and drops the input while closed.
+///
+/// The message input `open` takes a [Pmt::U32], opening the valve for a nonzero value and
+/// closing it for zero. It returns the current state, i.e., a [Pmt::Null] queries it.
+pub struct Valve {
+    item_size: usize,
+    open: bool,
+}
+
+impl Valve {
+    pub fn new(item_size: usize, open: bool) -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("Valve").build(),
+            StreamIoBuilder::new()
+                .add_input("in", item_size)
+                .add_output("out", item_size)
+                .build(),
+            MessageIoBuilder::new()
+                .add_sync_input("open", Self::set_open)
+                .build(),
+            Valve { item_size, open },
+        )
+    }
+
+    fn set_open(
+        &mut self,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+        p: Pmt,
+    ) -> Result<Pmt> {
+        match p {
+            Pmt::U32(v) => self.open = v != 0,
+            Pmt::Null => {}
+            _ => warn!("Valve: ignoring invalid state {:?}", p),
+        }
+        Ok(Pmt::U32(self.open as u32))
+    }
+}
+
+#[async_trait]
+impl SyncKernel for Valve {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+
+        let n = if self.open {
+            let o = sio.output(0).slice::<u8>();
+            let n = cmp::min(i.len(), o.len()) / self.item_size;
+            o[0..n * self.item_size].copy_from_slice(&i[0..n * self.item_size]);
+            sio.output(0).produce(n);
+            n
+        } else {
+            i.len() / self.item_size
+        };
+
+        if sio.input(0).finished() && n * self.item_size == i.len() {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(n);
+
+        Ok(())
+    }
+}
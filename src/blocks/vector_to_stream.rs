@@ -0,0 +1,63 @@
+use std::cmp;
+
+use crate::anyhow::Result;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Splits vector items into a stream of their `vector_len` elements.
+pub struct VectorToStream {
+    // bytes per vector
+    vector_size: usize,
+    vector_len: usize,
+}
+
+impl VectorToStream {
+    pub fn new(item_size: usize, vector_len: usize) -> Block {
+        Block::new_sync(
+            BlockMetaBuilder::new("VectorToStream").build(),
+            StreamIoBuilder::new()
+                .add_vector_input("in", item_size, vector_len)
+                .add_output("out", item_size)
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            VectorToStream {
+                vector_size: item_size * vector_len,
+                vector_len,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl SyncKernel for VectorToStream {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<u8>();
+        let o = sio.output(0).slice::<u8>();
+
+        let n = cmp::min(i.len(), o.len()) / self.vector_size;
+        let bytes = n * self.vector_size;
+        o[0..bytes].copy_from_slice(&i[0..bytes]);
+
+        if sio.input(0).finished() && bytes == i.len() {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(n);
+        sio.output(0).produce(n * self.vector_len);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use async_fs::File;
+use futures::io::AsyncSeekExt;
+use futures::io::AsyncWriteExt;
+use std::io::SeekFrom;
+
+use crate::anyhow::Result;
+use crate::runtime::AsyncKernel;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::WorkIo;
+
+/// Writes `f32` samples in `[-1, 1]` to a 16-bit PCM WAV file.
+///
+/// Multiple channels are expected to be interleaved. Samples outside of the range are clipped.
+/// The header is finalized when the flowgraph terminates.
+pub struct WavSink {
+    file_name: String,
+    file: Option<File>,
+    sample_rate: u32,
+    channels: u16,
+    n_written: usize,
+}
+
+impl WavSink {
+    pub fn new(file_name: &str, sample_rate: u32, channels: u16) -> Block {
+        assert!(channels > 0, "WAV file needs at least one channel");
+        Block::new_async(
+            BlockMetaBuilder::new("WavSink").build(),
+            StreamIoBuilder::new()
+                .add_input("in", std::mem::size_of::<f32>())
+                .build(),
+            MessageIoBuilder::new().build(),
+            WavSink {
+                file_name: file_name.into(),
+                file: None,
+                sample_rate,
+                channels,
+                n_written: 0,
+            },
+        )
+    }
+
+    fn header(&self) -> Vec<u8> {
+        let data_len = (self.n_written * 2) as u32;
+        let block_align = self.channels * 2;
+
+        let mut h = Vec::with_capacity(44);
+        h.extend_from_slice(b"RIFF");
+        h.extend_from_slice(&(36 + data_len).to_le_bytes());
+        h.extend_from_slice(b"WAVE");
+        h.extend_from_slice(b"fmt ");
+        h.extend_from_slice(&16u32.to_le_bytes());
+        // PCM
+        h.extend_from_slice(&1u16.to_le_bytes());
+        h.extend_from_slice(&self.channels.to_le_bytes());
+        h.extend_from_slice(&self.sample_rate.to_le_bytes());
+        h.extend_from_slice(&(self.sample_rate * block_align as u32).to_le_bytes());
+        h.extend_from_slice(&block_align.to_le_bytes());
+        h.extend_from_slice(&16u16.to_le_bytes());
+        h.extend_from_slice(b"data");
+        h.extend_from_slice(&data_len.to_le_bytes());
+        h
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for WavSink {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<f32>();
+
+        let bytes: Vec<u8> = i
+            .iter()
+            .flat_map(|x| ((x.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16).to_le_bytes())
+            .collect();
+        self.file.as_mut().unwrap().write_all(&bytes).await?;
+
+        if sio.input(0).finished() {
+            io.finished = true;
+        }
+
+        self.n_written += i.len();
+        sio.input(0).consume(i.len());
+        Ok(())
+    }
+
+    async fn init(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let mut file = File::create(&self.file_name).await?;
+        file.write_all(&self.header()).await?;
+        self.file = Some(file);
+        Ok(())
+    }
+
+    async fn deinit(
+        &mut self,
+        _sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        debug!("n_written: {}", self.n_written);
+        let header = self.header();
+        let file = self.file.as_mut().unwrap();
+        file.seek(SeekFrom::Start(0)).await?;
+        file.write_all(&header).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+pub struct WavSinkBuilder {
+    file: String,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl WavSinkBuilder {
+    /// Mono file.
+    pub fn new(file: &str, sample_rate: u32) -> WavSinkBuilder {
+        WavSinkBuilder {
+            file: file.into(),
+            sample_rate,
+            channels: 1,
+        }
+    }
+
+    #[must_use]
+    pub fn channels(mut self, channels: u16) -> WavSinkBuilder {
+        self.channels = channels;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        WavSink::new(&self.file, self.sample_rate, self.channels)
+    }
+}
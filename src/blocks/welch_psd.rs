@@ -0,0 +1,234 @@
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use std::mem;
+use std::sync::Arc;
+
+use crate::anyhow::Result;
+use crate::dsp::Window;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::StreamIo;
+use crate::runtime::StreamIoBuilder;
+use crate::runtime::SyncKernel;
+use crate::runtime::WorkIo;
+
+/// Power spectral density estimate with Welch's method.
+///
+/// Segments of `fft_size` samples that overlap by `overlap` samples are windowed and
+/// transformed. The periodograms of `n_segments` segments are averaged and output as frames of
+/// `fft_size` `f32` values in units of power per Hz, with the DC bin at the center, if `shift`
+/// is set. The output is linear; a [LogPower](crate::blocks::LogPower) converts it to dB.
+///
+/// With `vector`, the output uses frames as vector items instead of `f32` samples, like the
+/// [Fft](crate::blocks::Fft). The input is a stream of samples, since segments overlap.
+pub struct WelchPsd {
+    plan: Arc<dyn rustfft::Fft<f32>>,
+    window: Vec<f32>,
+    // 1 / (sample_rate * sum of the squared window)
+    scale: f32,
+    step: usize,
+    n_segments: usize,
+    shift: bool,
+    vector: bool,
+    buffer: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    psd: Vec<f32>,
+    n_avg: usize,
+}
+
+impl WelchPsd {
+    pub fn new(
+        fft_size: usize,
+        overlap: usize,
+        n_segments: usize,
+        window: Window,
+        sample_rate: f64,
+        shift: bool,
+        vector: bool,
+    ) -> Block {
+        assert!(fft_size > 0, "FFT size has to be positive");
+        assert!(
+            overlap < fft_size,
+            "overlap has to be smaller than the FFT size"
+        );
+        assert!(n_segments > 0, "average needs at least one segment");
+
+        let plan = FftPlanner::<f32>::new().plan_fft_forward(fft_size);
+        let scratch = vec![Complex::new(0.0, 0.0); plan.get_inplace_scratch_len()];
+        // periodic window for spectral analysis
+        let mut window = window.taps(fft_size + 1);
+        window.truncate(fft_size);
+        let energy: f32 = window.iter().map(|w| w * w).sum();
+
+        let vlen = if vector { fft_size } else { 1 };
+
+        Block::new_sync(
+            BlockMetaBuilder::new("WelchPsd").build(),
+            StreamIoBuilder::new()
+                .add_input("in", mem::size_of::<Complex<f32>>())
+                .add_vector_output("out", mem::size_of::<f32>(), vlen)
+                .build(),
+            MessageIoBuilder::<Self>::new().build(),
+            WelchPsd {
+                plan,
+                window,
+                scale: 1.0 / (sample_rate as f32 * energy),
+                step: fft_size - overlap,
+                n_segments,
+                shift,
+                vector,
+                buffer: vec![Complex::new(0.0, 0.0); fft_size],
+                scratch,
+                psd: vec![0.0; fft_size],
+                n_avg: 0,
+            },
+        )
+    }
+
+    fn add_segment(&mut self, segment: &[Complex<f32>]) {
+        for ((b, x), w) in self.buffer.iter_mut().zip(segment).zip(&self.window) {
+            *b = x * w;
+        }
+        self.plan
+            .process_with_scratch(&mut self.buffer, &mut self.scratch);
+        for (p, b) in self.psd.iter_mut().zip(&self.buffer) {
+            *p += b.norm_sqr();
+        }
+        self.n_avg += 1;
+    }
+}
+
+#[async_trait]
+impl SyncKernel for WelchPsd {
+    fn work(
+        &mut self,
+        io: &mut WorkIo,
+        sio: &mut StreamIo,
+        _mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        let i = sio.input(0).slice::<Complex<f32>>();
+        let o = sio.output(0).slice::<f32>();
+        let len = self.psd.len();
+
+        let mut consumed = 0;
+        let mut produced = 0;
+        while consumed + len <= i.len() {
+            // the segment completes an output
+            if self.n_avg + 1 == self.n_segments && (produced + 1) * len > o.len() {
+                break;
+            }
+            self.add_segment(&i[consumed..consumed + len]);
+            consumed += self.step;
+
+            if self.n_avg == self.n_segments {
+                let out = &mut o[produced * len..(produced + 1) * len];
+                let scale = self.scale / self.n_segments as f32;
+                for (y, p) in out.iter_mut().zip(&self.psd) {
+                    *y = p * scale;
+                }
+                if self.shift {
+                    out.rotate_right(len / 2);
+                }
+                self.psd.iter_mut().for_each(|p| *p = 0.0);
+                self.n_avg = 0;
+                produced += 1;
+            }
+        }
+
+        if sio.input(0).finished() && consumed + len > i.len() {
+            io.finished = true;
+        }
+
+        sio.input(0).consume(consumed);
+        let items = if self.vector {
+            produced
+        } else {
+            produced * len
+        };
+        sio.output(0).produce(items);
+
+        Ok(())
+    }
+}
+
+pub struct WelchPsdBuilder {
+    fft_size: usize,
+    overlap: usize,
+    n_segments: usize,
+    window: Window,
+    sample_rate: f64,
+    shift: bool,
+    vector: bool,
+}
+
+impl WelchPsdBuilder {
+    /// Estimator with a Hann window, 50% overlap, and eight segments per estimate, relative
+    /// to a sample rate of one and with the DC bin at the center.
+    pub fn new(fft_size: usize) -> WelchPsdBuilder {
+        WelchPsdBuilder {
+            fft_size,
+            overlap: fft_size / 2,
+            n_segments: 8,
+            window: Window::Hann,
+            sample_rate: 1.0,
+            shift: true,
+            vector: false,
+        }
+    }
+
+    /// Number of samples shared by consecutive segments.
+    #[must_use]
+    pub fn overlap(mut self, overlap: usize) -> WelchPsdBuilder {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Number of segments averaged for each estimate.
+    #[must_use]
+    pub fn segments(mut self, n_segments: usize) -> WelchPsdBuilder {
+        self.n_segments = n_segments;
+        self
+    }
+
+    #[must_use]
+    pub fn window(mut self, window: Window) -> WelchPsdBuilder {
+        self.window = window;
+        self
+    }
+
+    #[must_use]
+    pub fn sample_rate(mut self, sample_rate: f64) -> WelchPsdBuilder {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Move the DC bin to the center of the output.
+    #[must_use]
+    pub fn shift(mut self, shift: bool) -> WelchPsdBuilder {
+        self.shift = shift;
+        self
+    }
+
+    /// Use frames as vector output items instead of `f32` samples.
+    #[must_use]
+    pub fn vector(mut self, vector: bool) -> WelchPsdBuilder {
+        self.vector = vector;
+        self
+    }
+
+    pub fn build(self) -> Block {
+        WelchPsd::new(
+            self.fft_size,
+            self.overlap,
+            self.n_segments,
+            self.window,
+            self.sample_rate,
+            self.shift,
+            self.vector,
+        )
+    }
+}
@@ -0,0 +1,58 @@
+use futures::FutureExt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::anyhow::Result;
+use crate::dsp::Lfsr;
+use crate::runtime::AsyncKernel;
+use crate::runtime::Block;
+use crate::runtime::BlockMeta;
+use crate::runtime::BlockMetaBuilder;
+use crate::runtime::MessageIo;
+use crate::runtime::MessageIoBuilder;
+use crate::runtime::Pmt;
+use crate::runtime::StreamIoBuilder;
+
+/// Whitens [Pmt::Blob] messages by XORing them with the sequence of an [Lfsr].
+///
+/// The sequence restarts with each message, so the same block also removes the whitening.
+pub struct Whitener {
+    lfsr: Lfsr,
+}
+
+impl Whitener {
+    pub fn new(lfsr: Lfsr) -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("Whitener").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_output("out")
+                .add_async_input("in", Self::handler)
+                .build(),
+            Whitener { lfsr },
+        )
+    }
+
+    fn handler<'a>(
+        &'a mut self,
+        mio: &'a mut MessageIo<Self>,
+        _meta: &'a mut BlockMeta,
+        p: Pmt,
+    ) -> Pin<Box<dyn Future<Output = Result<Pmt>> + Send + 'a>> {
+        async move {
+            match p {
+                Pmt::Blob(mut data) => {
+                    self.lfsr.reset();
+                    self.lfsr.apply(&mut data);
+                    mio.post(0, Pmt::Blob(data)).await;
+                }
+                _ => warn!("Whitener: ignoring invalid data {:?}", p),
+            }
+            Ok(Pmt::Null)
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for Whitener {}
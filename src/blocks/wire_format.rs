@@ -0,0 +1,144 @@
+use num_complex::Complex;
+use std::marker::PhantomData;
+
+use crate::blocks::arithmetic::map_block;
+use crate::runtime::Block;
+
+/// Integer sample of a wire format, e.g., of recorded IQ files or SDR network streams.
+pub trait WireSample: Copy + Send + Sync + 'static {
+    /// Value that corresponds to an amplitude of one.
+    const FULL_SCALE: f32;
+    /// Centered value, i.e., with the offset of offset-binary formats removed.
+    fn to_f32(self) -> f32;
+    /// Rounded, saturating inverse of [WireSample::to_f32].
+    fn from_f32(x: f32) -> Self;
+    fn swap_bytes(self) -> Self;
+}
+
+/// Signed 16-bit samples, e.g., SC16.
+impl WireSample for i16 {
+    const FULL_SCALE: f32 = 32768.0;
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+    fn from_f32(x: f32) -> Self {
+        x.round() as i16
+    }
+    fn swap_bytes(self) -> Self {
+        i16::swap_bytes(self)
+    }
+}
+
+/// Signed 8-bit samples, e.g., of the HackRF.
+impl WireSample for i8 {
+    const FULL_SCALE: f32 = 128.0;
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+    fn from_f32(x: f32) -> Self {
+        x.round() as i8
+    }
+    fn swap_bytes(self) -> Self {
+        self
+    }
+}
+
+/// Offset-binary 8-bit samples, centered at 127.5, e.g., of the RTL-SDR.
+impl WireSample for u8 {
+    const FULL_SCALE: f32 = 128.0;
+    fn to_f32(self) -> f32 {
+        self as f32 - 127.5
+    }
+    fn from_f32(x: f32) -> Self {
+        (x + 127.5).round() as u8
+    }
+    fn swap_bytes(self) -> Self {
+        self
+    }
+}
+
+fn swap<A: WireSample>(x: Complex<A>) -> Complex<A> {
+    Complex::new(x.re.swap_bytes(), x.im.swap_bytes())
+}
+
+/// Converts interleaved integer IQ samples to `Complex<f32>`, dividing by `scale`.
+///
+/// With `byte_swap`, the samples are big-endian. [WireSample::FULL_SCALE] maps the full range of
+/// the format to `[-1, 1]`.
+pub struct WireToComplex<A: WireSample> {
+    _p: PhantomData<A>,
+}
+
+impl<A: WireSample> WireToComplex<A> {
+    pub fn new(scale: f32, byte_swap: bool) -> Block {
+        let s = 1.0 / scale;
+        if byte_swap {
+            map_block("WireToComplex", move |x: Complex<A>| {
+                let x = swap(x);
+                Complex::new(x.re.to_f32() * s, x.im.to_f32() * s)
+            })
+        } else {
+            map_block("WireToComplex", move |x: Complex<A>| {
+                Complex::new(x.re.to_f32() * s, x.im.to_f32() * s)
+            })
+        }
+    }
+}
+
+/// Converts `Complex<f32>` to interleaved integer IQ samples, multiplying by `scale`.
+///
+/// Values that exceed the range of the format saturate. With `byte_swap`, the samples are
+/// big-endian.
+pub struct ComplexToWire<A: WireSample> {
+    _p: PhantomData<A>,
+}
+
+impl<A: WireSample> ComplexToWire<A> {
+    pub fn new(scale: f32, byte_swap: bool) -> Block {
+        if byte_swap {
+            map_block("ComplexToWire", move |x: Complex<f32>| {
+                swap(Complex::new(
+                    A::from_f32(x.re * scale),
+                    A::from_f32(x.im * scale),
+                ))
+            })
+        } else {
+            map_block("ComplexToWire", move |x: Complex<f32>| {
+                Complex::new(A::from_f32(x.re * scale), A::from_f32(x.im * scale))
+            })
+        }
+    }
+}
+
+/// Converts real integer samples to `f32`, dividing by `scale`.
+pub struct WireToFloat<A: WireSample> {
+    _p: PhantomData<A>,
+}
+
+impl<A: WireSample> WireToFloat<A> {
+    pub fn new(scale: f32, byte_swap: bool) -> Block {
+        let s = 1.0 / scale;
+        if byte_swap {
+            map_block("WireToFloat", move |x: A| x.swap_bytes().to_f32() * s)
+        } else {
+            map_block("WireToFloat", move |x: A| x.to_f32() * s)
+        }
+    }
+}
+
+/// Converts `f32` to real integer samples, multiplying by `scale`.
+pub struct FloatToWire<A: WireSample> {
+    _p: PhantomData<A>,
+}
+
+impl<A: WireSample> FloatToWire<A> {
+    pub fn new(scale: f32, byte_swap: bool) -> Block {
+        if byte_swap {
+            map_block("FloatToWire", move |x: f32| {
+                A::from_f32(x * scale).swap_bytes()
+            })
+        } else {
+            map_block("FloatToWire", move |x: f32| A::from_f32(x * scale))
+        }
+    }
+}
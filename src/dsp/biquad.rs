@@ -0,0 +1,31 @@
+use std::f64::consts::PI;
+
+use crate::num_complex::Complex;
+
+/// Second-order IIR section `H(z) = (b0 + b1 z^-1 + b2 z^-2) / (1 + a1 z^-1 + a2 z^-2)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Biquad {
+    pub b0: f32,
+    pub b1: f32,
+    pub b2: f32,
+    pub a1: f32,
+    pub a2: f32,
+}
+
+impl Biquad {
+    /// Frequency response of the section at `freq`.
+    pub fn response(&self, freq: f64, sample_rate: f64) -> Complex<f64> {
+        let z1 = Complex::from_polar(1.0, -2.0 * PI * freq / sample_rate);
+        let z2 = z1 * z1;
+        (z1 * self.b1 as f64 + z2 * self.b2 as f64 + self.b0 as f64)
+            / (z1 * self.a1 as f64 + z2 * self.a2 as f64 + 1.0)
+    }
+
+    /// Frequency response of a cascade of sections at `freq`.
+    pub fn cascade_response(sections: &[Biquad], freq: f64, sample_rate: f64) -> Complex<f64> {
+        sections
+            .iter()
+            .map(|s| s.response(freq, sample_rate))
+            .product()
+    }
+}
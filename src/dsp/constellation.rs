@@ -0,0 +1,135 @@
+use serde::Deserialize;
+use std::f32::consts::PI;
+
+use crate::num_complex::Complex;
+
+/// Assignment of bits to the points of the built-in constellations.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Mapping {
+    /// Neighboring points differ in one bit, minimizing bit errors for symbol errors.
+    Gray,
+    /// Points are numbered in order, counterclockwise for PSK and row by row for QAM.
+    Natural,
+}
+
+/// Set of complex points, where the index of a point is the symbol that it represents.
+///
+/// The built-in constellations have unit average energy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constellation {
+    points: Vec<Complex<f32>>,
+    bits_per_symbol: usize,
+}
+
+impl Constellation {
+    /// Constellation with custom points, where `points[s]` represents the symbol `s`.
+    ///
+    /// The number of points has to be a power of two.
+    pub fn new(points: Vec<Complex<f32>>) -> Constellation {
+        assert!(
+            points.len() >= 2 && points.len().is_power_of_two(),
+            "number of points has to be a power of two"
+        );
+        let bits_per_symbol = points.len().trailing_zeros() as usize;
+        Constellation {
+            points,
+            bits_per_symbol,
+        }
+    }
+
+    /// `order`-PSK, with a point at phase zero for BPSK and 8PSK and at `pi / 4` for QPSK.
+    pub fn psk(order: usize, mapping: Mapping) -> Constellation {
+        assert!(
+            order >= 2 && order.is_power_of_two(),
+            "order has to be a power of two"
+        );
+        let offset = if order == 4 { PI / 4.0 } else { 0.0 };
+        let mut points = vec![Complex::new(0.0, 0.0); order];
+        for k in 0..order {
+            let p = Complex::from_polar(1.0, offset + 2.0 * PI * k as f32 / order as f32);
+            points[code(k, mapping)] = p;
+        }
+        Constellation::new(points)
+    }
+
+    /// Square `order`-QAM, e.g., 16 or 64.
+    ///
+    /// The upper half of the bits selects the column (in-phase), the lower half the row
+    /// (quadrature), each mapped to the amplitude levels independently.
+    pub fn qam(order: usize, mapping: Mapping) -> Constellation {
+        let bits = order.trailing_zeros() as usize;
+        assert!(
+            order >= 4 && order.is_power_of_two() && bits.is_multiple_of(2),
+            "order has to be an even power of two"
+        );
+        let side = 1 << (bits / 2);
+        // average energy of the levels -(side - 1), ..., side - 1 in both dimensions
+        let scale = (2.0 * (order as f32 - 1.0) / 3.0).sqrt();
+        let level = |k: usize| (2.0 * k as f32 - (side as f32 - 1.0)) / scale;
+
+        let mut points = vec![Complex::new(0.0, 0.0); order];
+        for i in 0..side {
+            for q in 0..side {
+                let s = (code(i, mapping) << (bits / 2)) | code(q, mapping);
+                points[s] = Complex::new(level(i), level(q));
+            }
+        }
+        Constellation::new(points)
+    }
+
+    pub fn bpsk() -> Constellation {
+        Self::psk(2, Mapping::Gray)
+    }
+
+    pub fn qpsk() -> Constellation {
+        Self::psk(4, Mapping::Gray)
+    }
+
+    pub fn psk8() -> Constellation {
+        Self::psk(8, Mapping::Gray)
+    }
+
+    pub fn qam16() -> Constellation {
+        Self::qam(16, Mapping::Gray)
+    }
+
+    pub fn qam64() -> Constellation {
+        Self::qam(64, Mapping::Gray)
+    }
+
+    pub fn points(&self) -> &[Complex<f32>] {
+        &self.points
+    }
+
+    pub fn bits_per_symbol(&self) -> usize {
+        self.bits_per_symbol
+    }
+
+    /// Point of `symbol`.
+    pub fn map(&self, symbol: usize) -> Complex<f32> {
+        self.points[symbol]
+    }
+
+    /// Symbol of the point closest to `x`.
+    pub fn slice(&self, x: Complex<f32>) -> usize {
+        let mut best = 0;
+        let mut best_dist = f32::INFINITY;
+        for (s, p) in self.points.iter().enumerate() {
+            let d = (x - p).norm_sqr();
+            if d < best_dist {
+                best = s;
+                best_dist = d;
+            }
+        }
+        best
+    }
+}
+
+// code word of the `k`-th point or amplitude level
+fn code(k: usize, mapping: Mapping) -> usize {
+    match mapping {
+        Mapping::Gray => k ^ (k >> 1),
+        Mapping::Natural => k,
+    }
+}
@@ -0,0 +1,124 @@
+/// Convolutional code with rate `1 / polynomials.len()`, terminated with zeros.
+///
+/// Bits are handled as one bit per `u8`. The shift register holds the newest bit in its
+/// lowest bit, and each polynomial selects the bits of the register that are added up for one
+/// output bit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConvolutionalCode {
+    constraint_length: usize,
+    polynomials: Vec<u32>,
+}
+
+impl ConvolutionalCode {
+    pub fn new(constraint_length: usize, polynomials: &[u32]) -> ConvolutionalCode {
+        assert!(
+            (2..=16).contains(&constraint_length),
+            "constraint length has to be in [2, 16]"
+        );
+        assert!(
+            !polynomials.is_empty(),
+            "code needs at least one polynomial"
+        );
+        ConvolutionalCode {
+            constraint_length,
+            polynomials: polynomials.to_vec(),
+        }
+    }
+
+    /// Rate 1/2 code with constraint length 7 and polynomials `0o171` and `0o133`, as used,
+    /// e.g., by IEEE 802.11 and CCSDS.
+    pub fn k7() -> ConvolutionalCode {
+        Self::new(7, &[0o171, 0o133])
+    }
+
+    pub fn constraint_length(&self) -> usize {
+        self.constraint_length
+    }
+
+    pub fn polynomials(&self) -> &[u32] {
+        &self.polynomials
+    }
+
+    /// Number of coded bits for `n` bits, including the termination.
+    pub fn coded_len(&self, n: usize) -> usize {
+        (n + self.constraint_length - 1) * self.polynomials.len()
+    }
+
+    fn output(&self, register: u32, out: &mut Vec<u8>) {
+        for p in &self.polynomials {
+            out.push(((register & p).count_ones() & 1) as u8);
+        }
+    }
+
+    pub fn encode(&self, bits: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.coded_len(bits.len()));
+        let mut padded = bits.to_vec();
+        padded.resize(bits.len() + self.constraint_length - 1, 0);
+        let mut register = 0u32;
+        for b in &padded {
+            register = (register << 1) | (*b as u32 & 1);
+            self.output(register, &mut out);
+        }
+        out
+    }
+
+    /// Maximum-likelihood decoding of hard bits with the Viterbi algorithm.
+    ///
+    /// Incomplete trailing symbols are ignored.
+    pub fn decode(&self, coded: &[u8]) -> Vec<u8> {
+        let n = self.polynomials.len();
+        let steps = coded.len() / n;
+        if steps < self.constraint_length - 1 {
+            return Vec::new();
+        }
+        let states = 1usize << (self.constraint_length - 1);
+
+        // expected output bits for each register value, i.e., state and input bit
+        let expected: Vec<Vec<u8>> = (0..2 * states as u32)
+            .map(|r| {
+                let mut out = Vec::with_capacity(n);
+                self.output(r, &mut out);
+                out
+            })
+            .collect();
+
+        let mut metric = vec![u32::MAX; states];
+        metric[0] = 0;
+        let mut next_metric = vec![u32::MAX; states];
+        // surviving predecessor of each state for each step
+        let mut history = vec![0u32; steps * states];
+
+        for (step, symbol) in coded.chunks_exact(n).enumerate() {
+            next_metric.fill(u32::MAX);
+            for (state, m) in metric.iter().enumerate() {
+                if *m == u32::MAX {
+                    continue;
+                }
+                for bit in 0..2 {
+                    let register = (state << 1) | bit;
+                    let next = register & (states - 1);
+                    let distance = expected[register]
+                        .iter()
+                        .zip(symbol)
+                        .filter(|(e, s)| **e != (**s & 1))
+                        .count() as u32;
+                    if m + distance < next_metric[next] {
+                        next_metric[next] = m + distance;
+                        history[step * states + next] = state as u32;
+                    }
+                }
+            }
+            std::mem::swap(&mut metric, &mut next_metric);
+        }
+
+        // trace back from the all-zero state of the termination
+        let mut bits = vec![0; steps];
+        let mut state = 0usize;
+        for step in (0..steps).rev() {
+            bits[step] = (state & 1) as u8;
+            state = history[step * states + state] as usize;
+        }
+        bits.truncate(steps - (self.constraint_length - 1));
+        bits
+    }
+}
@@ -0,0 +1,69 @@
+use serde::Deserialize;
+
+/// Cyclic redundancy check.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Crc {
+    /// CRC-16/CCITT-FALSE, i.e., polynomial `0x1021` with initial value `0xffff`.
+    Crc16,
+    /// CRC-32 as used by Ethernet and zlib.
+    Crc32,
+}
+
+impl Crc {
+    /// Number of bytes of the checksum.
+    pub fn size(&self) -> usize {
+        match self {
+            Crc::Crc16 => 2,
+            Crc::Crc32 => 4,
+        }
+    }
+
+    pub fn checksum(&self, data: &[u8]) -> u32 {
+        match self {
+            Crc::Crc16 => {
+                let mut crc = 0xffffu16;
+                for b in data {
+                    crc ^= (*b as u16) << 8;
+                    for _ in 0..8 {
+                        crc = if crc & 0x8000 != 0 {
+                            (crc << 1) ^ 0x1021
+                        } else {
+                            crc << 1
+                        };
+                    }
+                }
+                crc as u32
+            }
+            Crc::Crc32 => {
+                let mut crc = 0xffff_ffffu32;
+                for b in data {
+                    crc ^= *b as u32;
+                    for _ in 0..8 {
+                        crc = if crc & 1 != 0 {
+                            (crc >> 1) ^ 0xedb8_8320
+                        } else {
+                            crc >> 1
+                        };
+                    }
+                }
+                !crc
+            }
+        }
+    }
+
+    /// Appends the checksum of `data` in big-endian byte order.
+    pub fn append(&self, data: &mut Vec<u8>) {
+        let crc = self.checksum(data).to_be_bytes();
+        data.extend_from_slice(&crc[4 - self.size()..]);
+    }
+
+    /// Checks data with an appended checksum.
+    pub fn check(&self, data: &[u8]) -> bool {
+        if data.len() < self.size() {
+            return false;
+        }
+        let (data, crc) = data.split_at(data.len() - self.size());
+        self.checksum(data).to_be_bytes()[4 - self.size()..] == *crc
+    }
+}
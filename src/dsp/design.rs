@@ -0,0 +1,647 @@
+//! Filter design.
+//!
+//! Frequencies are given in Hz, together with the sample rate. FIR taps can be passed
+//! directly to, e.g., [Fir](crate::blocks::Fir), IIR sections to [Iir](crate::blocks::Iir).
+use std::f64::consts::PI;
+
+use crate::anyhow::{bail, Result};
+use crate::dsp::Biquad;
+use crate::dsp::Window;
+use crate::num_complex::Complex;
+
+/// Frequency response of `taps` at `freq`.
+pub fn frequency_response(taps: &[f32], freq: f64, sample_rate: f64) -> Complex<f64> {
+    let w = -2.0 * PI * freq / sample_rate;
+    taps.iter()
+        .enumerate()
+        .map(|(n, t)| Complex::from_polar(*t as f64, w * n as f64))
+        .sum()
+}
+
+/// Number of taps and window for a Kaiser window design with the given stopband
+/// attenuation (in dB) and transition width.
+///
+/// The number of taps is always odd, so that it can be used for all filter types.
+pub fn kaiser_order(
+    attenuation_db: f64,
+    transition_width: f64,
+    sample_rate: f64,
+) -> (usize, Window) {
+    assert!(
+        transition_width > 0.0,
+        "transition width has to be positive"
+    );
+    let a = attenuation_db;
+    let beta = if a > 50.0 {
+        0.1102 * (a - 8.7)
+    } else if a >= 21.0 {
+        0.5842 * (a - 21.0).powf(0.4) + 0.07886 * (a - 21.0)
+    } else {
+        0.0
+    };
+    let n = ((a - 7.95) / (14.36 * transition_width / sample_rate))
+        .ceil()
+        .max(2.0) as usize
+        + 1;
+    (n | 1, Window::Kaiser(beta as f32))
+}
+
+/// Windowed-sinc lowpass filter with unity gain at DC.
+pub fn lowpass(num_taps: usize, cutoff: f64, sample_rate: f64, window: Window) -> Vec<f32> {
+    check_freqs(num_taps, &[cutoff], sample_rate);
+    let h = windowed_sinc(num_taps, cutoff / sample_rate, window);
+    to_f32(normalize(h, 0.0))
+}
+
+/// Windowed-sinc highpass filter with unity gain at Nyquist. `num_taps` has to be odd.
+pub fn highpass(num_taps: usize, cutoff: f64, sample_rate: f64, window: Window) -> Vec<f32> {
+    check_freqs(num_taps, &[cutoff], sample_rate);
+    assert!(
+        num_taps % 2 == 1,
+        "highpass filters need an odd number of taps"
+    );
+    let h = normalize(windowed_sinc(num_taps, cutoff / sample_rate, window), 0.0);
+    to_f32(normalize(spectral_inversion(h), 0.5))
+}
+
+/// Windowed-sinc bandpass filter with unity gain at the center of the passband.
+pub fn bandpass(
+    num_taps: usize,
+    low: f64,
+    high: f64,
+    sample_rate: f64,
+    window: Window,
+) -> Vec<f32> {
+    check_freqs(num_taps, &[low, high], sample_rate);
+    let h = windowed_band(num_taps, low / sample_rate, high / sample_rate, window);
+    to_f32(normalize(h, (low + high) / 2.0 / sample_rate))
+}
+
+/// Windowed-sinc bandstop filter with unity gain at DC. `num_taps` has to be odd.
+pub fn bandstop(
+    num_taps: usize,
+    low: f64,
+    high: f64,
+    sample_rate: f64,
+    window: Window,
+) -> Vec<f32> {
+    check_freqs(num_taps, &[low, high], sample_rate);
+    assert!(
+        num_taps % 2 == 1,
+        "bandstop filters need an odd number of taps"
+    );
+    let h = windowed_band(num_taps, low / sample_rate, high / sample_rate, window);
+    let h = normalize(h, (low + high) / 2.0 / sample_rate);
+    to_f32(normalize(spectral_inversion(h), 0.0))
+}
+
+/// Root-raised-cosine pulse with `sps` samples per symbol and the given roll-off, normalized
+/// to unit energy.
+///
+/// Two filters in cascade form a raised-cosine pulse with a peak of one and zero crossings at
+/// the other symbols.
+pub fn root_raised_cosine(num_taps: usize, sps: f64, rolloff: f64) -> Vec<f32> {
+    assert!(num_taps > 0, "filter needs at least one tap");
+    assert!(
+        (0.0..=1.0).contains(&rolloff),
+        "roll-off has to be in [0, 1]"
+    );
+    let a = rolloff;
+    let m = (num_taps - 1) as f64 / 2.0;
+
+    let h: Vec<f64> = (0..num_taps)
+        .map(|n| {
+            let t = (n as f64 - m) / sps;
+            if t.abs() < 1e-9 {
+                1.0 - a + 4.0 * a / PI
+            } else if a > 0.0 && ((4.0 * a * t).abs() - 1.0).abs() < 1e-9 {
+                a / 2f64.sqrt()
+                    * ((1.0 + 2.0 / PI) * (PI / (4.0 * a)).sin()
+                        + (1.0 - 2.0 / PI) * (PI / (4.0 * a)).cos())
+            } else {
+                ((PI * t * (1.0 - a)).sin() + 4.0 * a * t * (PI * t * (1.0 + a)).cos())
+                    / (PI * t * (1.0 - (4.0 * a * t).powi(2)))
+            }
+        })
+        .collect();
+
+    let energy = h.iter().map(|x| x * x).sum::<f64>().sqrt();
+    to_f32(h.iter().map(|x| x / energy).collect())
+}
+
+/// Gaussian pulse with `sps` samples per symbol and bandwidth-time product `bt`, e.g., for
+/// GFSK, with unity gain at DC.
+pub fn gaussian(num_taps: usize, sps: f64, bt: f64) -> Vec<f32> {
+    assert!(num_taps > 0, "filter needs at least one tap");
+    assert!(bt > 0.0, "bandwidth-time product has to be positive");
+    let m = (num_taps - 1) as f64 / 2.0;
+    let k = 2.0 * PI * PI * bt * bt / 2f64.ln();
+    let h = (0..num_taps)
+        .map(|n| {
+            let t = (n as f64 - m) / sps;
+            (-k * t * t).exp()
+        })
+        .collect();
+    to_f32(normalize(h, 0.0))
+}
+
+/// Anti-aliasing and anti-imaging filter for resamplers.
+///
+/// The filter runs at `phases` times the input rate, changing the rate by `rate` (output over
+/// input). It passes 90% of the lower Nyquist frequency, attenuates by 60 dB, and has a gain of
+/// `phases` to compensate for the zero-stuffing.
+pub fn resampler(phases: usize, rate: f64) -> Vec<f32> {
+    assert!(phases > 0 && rate > 0.0, "invalid resampling parameters");
+    let bw = rate.min(1.0);
+    let fs = phases as f64;
+    let (num_taps, window) = kaiser_order(60.0, 0.1 * bw, fs);
+    lowpass(num_taps, 0.45 * bw, fs, window)
+        .iter()
+        .map(|x| x * phases as f32)
+        .collect()
+}
+
+/// Parks-McClellan equiripple filter.
+///
+/// `bands` are `(start, stop)` frequencies in increasing order between zero and Nyquist, with the
+/// desired gain and weight of the error for each band. Fails if the algorithm does not converge.
+pub fn parks_mcclellan(
+    num_taps: usize,
+    bands: &[(f64, f64)],
+    desired: &[f64],
+    weights: &[f64],
+    sample_rate: f64,
+) -> Result<Vec<f32>> {
+    if num_taps < 3 {
+        bail!("equiripple filters need at least three taps");
+    }
+    if bands.is_empty() || bands.len() != desired.len() || bands.len() != weights.len() {
+        bail!("bands, desired gains, and weights need the same, non-zero length");
+    }
+    let mut last = 0.0;
+    for (i, (start, stop)) in bands.iter().enumerate() {
+        if (i > 0 && start <= &last) || start > stop || *start < 0.0 || *stop > sample_rate / 2.0 {
+            bail!("band edges have to increase between zero and Nyquist");
+        }
+        last = *stop;
+    }
+    if weights.iter().any(|w| *w <= 0.0) {
+        bail!("weights have to be positive");
+    }
+
+    let edges: Vec<f64> = bands
+        .iter()
+        .flat_map(|(a, b)| [a / sample_rate, b / sample_rate])
+        .collect();
+    Ok(to_f32(remez::design(num_taps, &edges, desired, weights)?))
+}
+
+/// Butterworth lowpass as cascade of second-order sections, with unity gain at DC.
+pub fn butterworth_lowpass(order: usize, cutoff: f64, sample_rate: f64) -> Vec<Biquad> {
+    iir(&butterworth_poles(order), cutoff, sample_rate, false, 1.0)
+}
+
+/// Butterworth highpass as cascade of second-order sections, with unity gain at Nyquist.
+pub fn butterworth_highpass(order: usize, cutoff: f64, sample_rate: f64) -> Vec<Biquad> {
+    iir(&butterworth_poles(order), cutoff, sample_rate, true, 1.0)
+}
+
+/// Chebyshev type I lowpass with `ripple_db` passband ripple up to `cutoff`.
+pub fn chebyshev_lowpass(
+    order: usize,
+    ripple_db: f64,
+    cutoff: f64,
+    sample_rate: f64,
+) -> Vec<Biquad> {
+    let (poles, gain) = chebyshev_poles(order, ripple_db);
+    iir(&poles, cutoff, sample_rate, false, gain)
+}
+
+/// Chebyshev type I highpass with `ripple_db` passband ripple above `cutoff`.
+pub fn chebyshev_highpass(
+    order: usize,
+    ripple_db: f64,
+    cutoff: f64,
+    sample_rate: f64,
+) -> Vec<Biquad> {
+    let (poles, gain) = chebyshev_poles(order, ripple_db);
+    iir(&poles, cutoff, sample_rate, true, gain)
+}
+
+// poles of the analog lowpass prototype with cutoff 1 in the upper left half-plane,
+// including the real pole for odd orders
+fn butterworth_poles(order: usize) -> Vec<Complex<f64>> {
+    assert!(order > 0, "filter order has to be positive");
+    (0..order.div_ceil(2))
+        .map(|k| {
+            let theta = PI * (2 * k + 1) as f64 / (2 * order) as f64;
+            Complex::new(-theta.sin(), theta.cos())
+        })
+        .collect()
+}
+
+// Chebyshev poles and the passband gain at DC
+fn chebyshev_poles(order: usize, ripple_db: f64) -> (Vec<Complex<f64>>, f64) {
+    assert!(order > 0, "filter order has to be positive");
+    assert!(ripple_db > 0.0, "ripple has to be positive");
+    let eps = (10f64.powf(ripple_db / 10.0) - 1.0).sqrt();
+    let mu = (1.0 / eps).asinh() / order as f64;
+    let poles = (0..order.div_ceil(2))
+        .map(|k| {
+            let theta = PI * (2 * k + 1) as f64 / (2 * order) as f64;
+            Complex::new(-mu.sinh() * theta.sin(), mu.cosh() * theta.cos())
+        })
+        .collect();
+    let gain = if order.is_multiple_of(2) {
+        1.0 / (1.0 + eps * eps).sqrt()
+    } else {
+        1.0
+    };
+    (poles, gain)
+}
+
+// Maps the analog prototype with bilinear transform, prewarped to `cutoff`. Each section has
+// unity gain in the passband, `gain` is applied to the first one.
+fn iir(
+    poles: &[Complex<f64>],
+    cutoff: f64,
+    sample_rate: f64,
+    highpass: bool,
+    gain: f64,
+) -> Vec<Biquad> {
+    assert!(
+        cutoff > 0.0 && cutoff < sample_rate / 2.0,
+        "cutoff frequency has to be between zero and Nyquist"
+    );
+    let k = (PI * cutoff / sample_rate).tan();
+
+    let mut sections: Vec<Biquad> = poles
+        .iter()
+        .map(|p| {
+            if p.im.abs() < 1e-12 {
+                // first-order section s / (s + 1/σ) or σ / (s + σ)
+                let sigma = -p.re;
+                if highpass {
+                    bilinear1([1.0, 0.0], [1.0, 1.0 / sigma], k)
+                } else {
+                    bilinear1([0.0, sigma], [1.0, sigma], k)
+                }
+            } else {
+                // conjugate pair with s^2 + a s + c
+                let a = -2.0 * p.re;
+                let c = p.norm_sqr();
+                if highpass {
+                    bilinear2([1.0, 0.0, 0.0], [1.0, a / c, 1.0 / c], k)
+                } else {
+                    bilinear2([0.0, 0.0, c], [1.0, a, c], k)
+                }
+            }
+        })
+        .collect();
+
+    let g = gain as f32;
+    sections[0].b0 *= g;
+    sections[0].b1 *= g;
+    sections[0].b2 *= g;
+    sections
+}
+
+// substitutes s = (1 - z^-1) / (k (1 + z^-1)) in a second-order section, given as coefficients
+// of s^2, s, and 1
+fn bilinear2(num: [f64; 3], den: [f64; 3], k: f64) -> Biquad {
+    let z = |c: [f64; 3]| {
+        [
+            c[0] + c[1] * k + c[2] * k * k,
+            -2.0 * c[0] + 2.0 * c[2] * k * k,
+            c[0] - c[1] * k + c[2] * k * k,
+        ]
+    };
+    let (b, a) = (z(num), z(den));
+    Biquad {
+        b0: (b[0] / a[0]) as f32,
+        b1: (b[1] / a[0]) as f32,
+        b2: (b[2] / a[0]) as f32,
+        a1: (a[1] / a[0]) as f32,
+        a2: (a[2] / a[0]) as f32,
+    }
+}
+
+// first-order version of `bilinear2`, with coefficients of s and 1
+fn bilinear1(num: [f64; 2], den: [f64; 2], k: f64) -> Biquad {
+    let z = |c: [f64; 2]| [c[0] + c[1] * k, -c[0] + c[1] * k];
+    let (b, a) = (z(num), z(den));
+    Biquad {
+        b0: (b[0] / a[0]) as f32,
+        b1: (b[1] / a[0]) as f32,
+        b2: 0.0,
+        a1: (a[1] / a[0]) as f32,
+        a2: 0.0,
+    }
+}
+
+fn check_freqs(num_taps: usize, freqs: &[f64], sample_rate: f64) {
+    assert!(num_taps > 0, "filter needs at least one tap");
+    let mut last = 0.0;
+    for f in freqs {
+        assert!(
+            *f > last && *f < sample_rate / 2.0,
+            "cutoff frequencies have to increase between zero and Nyquist"
+        );
+        last = *f;
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-12 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+// ideal lowpass with cutoff `fc` (relative to the sample rate), windowed
+fn windowed_sinc(num_taps: usize, fc: f64, window: Window) -> Vec<f64> {
+    let w = window.taps(num_taps);
+    let m = (num_taps - 1) as f64 / 2.0;
+    (0..num_taps)
+        .map(|n| 2.0 * fc * sinc(2.0 * fc * (n as f64 - m)) * w[n] as f64)
+        .collect()
+}
+
+fn windowed_band(num_taps: usize, low: f64, high: f64, window: Window) -> Vec<f64> {
+    windowed_sinc(num_taps, high, window)
+        .iter()
+        .zip(windowed_sinc(num_taps, low, window))
+        .map(|(h, l)| h - l)
+        .collect()
+}
+
+// subtracts the filter from a delta, which swaps pass- and stopband of filters with unity gain
+fn spectral_inversion(mut h: Vec<f64>) -> Vec<f64> {
+    let m = h.len() / 2;
+    h.iter_mut().for_each(|x| *x = -*x);
+    h[m] += 1.0;
+    h
+}
+
+// scales to unity gain at `freq` (relative to the sample rate)
+fn normalize(h: Vec<f64>, freq: f64) -> Vec<f64> {
+    let g: Complex<f64> = h
+        .iter()
+        .enumerate()
+        .map(|(n, t)| Complex::from_polar(*t, -2.0 * PI * freq * n as f64))
+        .sum();
+    let g = g.norm();
+    h.iter().map(|x| x / g).collect()
+}
+
+fn to_f32(h: Vec<f64>) -> Vec<f32> {
+    h.iter().map(|x| *x as f32).collect()
+}
+
+// Remez exchange algorithm for symmetric (type I and II) filters, following the
+// implementation of Janovetz that is also used by SciPy.
+mod remez {
+    use std::f64::consts::PI;
+
+    use crate::anyhow::{bail, Result};
+
+    const GRID_DENSITY: usize = 16;
+    const MAX_ITERATIONS: usize = 40;
+
+    pub fn design(num_taps: usize, edges: &[f64], des: &[f64], weight: &[f64]) -> Result<Vec<f64>> {
+        let r = num_taps.div_ceil(2);
+        let even = num_taps.is_multiple_of(2);
+
+        let (grid, mut d, mut w) = dense_grid(r, even, edges, des, weight);
+        if grid.len() <= 2 * r {
+            bail!("bands are too narrow for {} taps", num_taps);
+        }
+
+        if even {
+            for i in 0..grid.len() {
+                let c = (PI * grid[i]).cos();
+                d[i] /= c;
+                w[i] *= c;
+            }
+        }
+
+        let mut ext: Vec<usize> = (0..=r).map(|i| i * (grid.len() - 1) / r).collect();
+        let mut e = vec![0.0; grid.len()];
+        let mut p = Parameters::default();
+
+        let mut converged = false;
+        for _ in 0..MAX_ITERATIONS {
+            p = Parameters::new(r, &ext, &grid, &d, &w);
+            for i in 0..grid.len() {
+                e[i] = w[i] * (d[i] - p.response(grid[i]));
+            }
+            search(r, &mut ext, &e)?;
+            if is_done(&ext, &e) {
+                converged = true;
+                break;
+            }
+        }
+        if !converged {
+            bail!("Parks-McClellan design did not converge");
+        }
+
+        // sample the amplitude response and transform back to taps
+        let a: Vec<f64> = (0..=num_taps / 2)
+            .map(|i| {
+                let f = i as f64 / num_taps as f64;
+                let c = if even { (PI * f).cos() } else { 1.0 };
+                p.response(f) * c
+            })
+            .collect();
+
+        let m = (num_taps - 1) as f64 / 2.0;
+        let k_max = if even {
+            num_taps / 2 - 1
+        } else {
+            (num_taps - 1) / 2
+        };
+        Ok((0..num_taps)
+            .map(|n| {
+                let x = 2.0 * PI * (n as f64 - m) / num_taps as f64;
+                let v = a[0]
+                    + (1..=k_max)
+                        .map(|k| 2.0 * a[k] * (x * k as f64).cos())
+                        .sum::<f64>();
+                v / num_taps as f64
+            })
+            .collect())
+    }
+
+    fn dense_grid(
+        r: usize,
+        even: bool,
+        edges: &[f64],
+        des: &[f64],
+        weight: &[f64],
+    ) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let delf = 0.5 / (GRID_DENSITY * r) as f64;
+        let mut grid = Vec::new();
+        let mut d = Vec::new();
+        let mut w = Vec::new();
+
+        for band in 0..des.len() {
+            let mut f = edges[2 * band];
+            let high = edges[2 * band + 1];
+            let k = (((high - f) / delf).round() as usize).max(1);
+            for _ in 0..k {
+                grid.push(f);
+                d.push(des[band]);
+                w.push(weight[band]);
+                f += delf;
+            }
+            *grid.last_mut().unwrap() = high;
+        }
+
+        // an even number of taps has a zero at Nyquist
+        if even {
+            let last = grid.last_mut().unwrap();
+            if *last > 0.5 - delf {
+                *last = 0.5 - delf;
+            }
+        }
+
+        (grid, d, w)
+    }
+
+    #[derive(Default)]
+    struct Parameters {
+        ad: Vec<f64>,
+        x: Vec<f64>,
+        y: Vec<f64>,
+    }
+
+    impl Parameters {
+        // barycentric Lagrange interpolation through the current extremal frequencies
+        fn new(r: usize, ext: &[usize], grid: &[f64], d: &[f64], w: &[f64]) -> Parameters {
+            let x: Vec<f64> = ext.iter().map(|e| (2.0 * PI * grid[*e]).cos()).collect();
+
+            let ld = (r - 1) / 15 + 1;
+            let ad: Vec<f64> = (0..=r)
+                .map(|i| {
+                    let mut denom = 1.0;
+                    for j in 0..ld {
+                        for k in (j..=r).step_by(ld) {
+                            if k != i {
+                                denom *= 2.0 * (x[i] - x[k]);
+                            }
+                        }
+                    }
+                    if denom.abs() < 0.00001 {
+                        denom = 0.00001;
+                    }
+                    1.0 / denom
+                })
+                .collect();
+
+            let mut numer = 0.0;
+            let mut denom = 0.0;
+            let mut sign = 1.0;
+            for i in 0..=r {
+                numer += ad[i] * d[ext[i]];
+                denom += sign * ad[i] / w[ext[i]];
+                sign = -sign;
+            }
+            let delta = numer / denom;
+
+            let mut sign = 1.0;
+            let y = (0..=r)
+                .map(|i| {
+                    let y = d[ext[i]] - sign * delta / w[ext[i]];
+                    sign = -sign;
+                    y
+                })
+                .collect();
+
+            Parameters { ad, x, y }
+        }
+
+        fn response(&self, freq: f64) -> f64 {
+            let xc = (2.0 * PI * freq).cos();
+            let mut numer = 0.0;
+            let mut denom = 0.0;
+            for i in 0..self.x.len() {
+                let c = xc - self.x[i];
+                if c.abs() < 1.0e-7 {
+                    return self.y[i];
+                }
+                let c = self.ad[i] / c;
+                denom += c;
+                numer += c * self.y[i];
+            }
+            numer / denom
+        }
+    }
+
+    // finds the r + 1 largest, alternating extrema of the error
+    fn search(r: usize, ext: &mut [usize], e: &[f64]) -> Result<()> {
+        let n = e.len();
+        let mut found = Vec::with_capacity(2 * r);
+
+        if (e[0] > 0.0 && e[0] > e[1]) || (e[0] < 0.0 && e[0] < e[1]) {
+            found.push(0);
+        }
+        for i in 1..n - 1 {
+            if (e[i] >= e[i - 1] && e[i] > e[i + 1] && e[i] > 0.0)
+                || (e[i] <= e[i - 1] && e[i] < e[i + 1] && e[i] < 0.0)
+            {
+                found.push(i);
+            }
+        }
+        let j = n - 1;
+        if (e[j] > 0.0 && e[j] > e[j - 1]) || (e[j] < 0.0 && e[j] < e[j - 1]) {
+            found.push(j);
+        }
+
+        if found.len() < r + 1 {
+            bail!("Parks-McClellan design did not converge");
+        }
+
+        while found.len() > r + 1 {
+            let k = found.len();
+            let mut up = e[found[0]] > 0.0;
+            let mut l = 0;
+            let mut alternating = true;
+            for j in 1..k {
+                if e[found[j]].abs() < e[found[l]].abs() {
+                    l = j;
+                }
+                if up && e[found[j]] < 0.0 {
+                    up = false;
+                } else if !up && e[found[j]] > 0.0 {
+                    up = true;
+                } else {
+                    alternating = false;
+                    break;
+                }
+            }
+            if alternating && k == r + 2 {
+                l = if e[found[k - 1]].abs() < e[found[0]].abs() {
+                    k - 1
+                } else {
+                    0
+                };
+            }
+            found.remove(l);
+        }
+
+        ext.copy_from_slice(&found);
+        Ok(())
+    }
+
+    fn is_done(ext: &[usize], e: &[f64]) -> bool {
+        let mut min = f64::MAX;
+        let mut max = 0.0f64;
+        for i in ext {
+            let v = e[*i].abs();
+            min = min.min(v);
+            max = max.max(v);
+        }
+        (max - min) / max < 0.0001
+    }
+}
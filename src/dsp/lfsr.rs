@@ -0,0 +1,55 @@
+/// Linear-feedback shift register, e.g., for whitening or additive scrambling.
+///
+/// The register has `degree` bits and outputs its lowest bit. The feedback polynomial is given
+/// by `mask`, which has bit `k` set for each term `x^k` below `x^degree`, e.g., `0x21` for
+/// `x^9 + x^5 + 1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lfsr {
+    mask: u32,
+    seed: u32,
+    degree: u32,
+    state: u32,
+}
+
+impl Lfsr {
+    pub fn new(mask: u32, seed: u32, degree: u32) -> Lfsr {
+        assert!((1..=32).contains(&degree), "degree has to be in [1, 32]");
+        let seed = seed & (u32::MAX >> (32 - degree));
+        Lfsr {
+            mask,
+            seed,
+            degree,
+            state: seed,
+        }
+    }
+
+    /// PN9 sequence with polynomial `x^9 + x^5 + 1`, starting with all ones, as used, e.g., by
+    /// the CC1101 and IEEE 802.15.4g.
+    pub fn pn9() -> Lfsr {
+        Self::new(0x21, 0x1ff, 9)
+    }
+
+    /// Restarts the sequence.
+    pub fn reset(&mut self) {
+        self.state = self.seed;
+    }
+
+    pub fn next_bit(&mut self) -> u8 {
+        let out = (self.state & 1) as u8;
+        let feedback = (self.state & self.mask).count_ones() & 1;
+        self.state = (self.state >> 1) | (feedback << (self.degree - 1));
+        out
+    }
+
+    /// Next eight bits, the first in the least significant bit.
+    pub fn next_byte(&mut self) -> u8 {
+        (0..8).fold(0, |b, i| b | (self.next_bit() << i))
+    }
+
+    /// XORs `data` with the sequence.
+    pub fn apply(&mut self, data: &mut [u8]) {
+        for b in data {
+            *b ^= self.next_byte();
+        }
+    }
+}
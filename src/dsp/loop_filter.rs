@@ -0,0 +1,82 @@
+/// Proportional-integral filter of a second-order tracking loop, e.g., a PLL.
+///
+/// The gains follow from the loop bandwidth (normalized to radians per update) and the damping
+/// factor. The integrator, i.e., the frequency, is clamped to `[-max_freq, max_freq]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoopFilter {
+    bandwidth: f64,
+    damping: f64,
+    alpha: f64,
+    beta: f64,
+    freq: f64,
+    max_freq: f64,
+}
+
+impl LoopFilter {
+    pub fn new(bandwidth: f64, damping: f64, max_freq: f64) -> LoopFilter {
+        let mut l = LoopFilter {
+            bandwidth,
+            damping,
+            alpha: 0.0,
+            beta: 0.0,
+            freq: 0.0,
+            max_freq,
+        };
+        l.update_gains();
+        l
+    }
+
+    /// Critically damped loop.
+    pub fn critically_damped(bandwidth: f64, max_freq: f64) -> LoopFilter {
+        Self::new(bandwidth, std::f64::consts::FRAC_1_SQRT_2, max_freq)
+    }
+
+    fn update_gains(&mut self) {
+        let denom = 1.0 + 2.0 * self.damping * self.bandwidth + self.bandwidth * self.bandwidth;
+        self.alpha = 4.0 * self.damping * self.bandwidth / denom;
+        self.beta = 4.0 * self.bandwidth * self.bandwidth / denom;
+    }
+
+    pub fn set_bandwidth(&mut self, bandwidth: f64) {
+        self.bandwidth = bandwidth;
+        self.update_gains();
+    }
+
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    pub fn set_damping(&mut self, damping: f64) {
+        self.damping = damping;
+        self.update_gains();
+    }
+
+    pub fn damping(&self) -> f64 {
+        self.damping
+    }
+
+    /// Proportional gain.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Integral gain.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    pub fn set_freq(&mut self, freq: f64) {
+        self.freq = freq.clamp(-self.max_freq, self.max_freq);
+    }
+
+    pub fn freq(&self) -> f64 {
+        self.freq
+    }
+
+    /// Feeds the phase `error` into the loop and returns the phase increment until the next
+    /// update.
+    pub fn advance(&mut self, error: f64) -> f64 {
+        self.set_freq(self.freq + self.beta * error);
+        self.freq + self.alpha * error
+    }
+}
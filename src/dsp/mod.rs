@@ -0,0 +1,29 @@
+//! Signal processing functions that are independent of the runtime, e.g., to compute filter taps.
+mod biquad;
+pub use biquad::Biquad;
+
+mod constellation;
+pub use constellation::{Constellation, Mapping};
+
+mod convolutional;
+pub use convolutional::ConvolutionalCode;
+
+mod crc;
+pub use crc::Crc;
+
+pub mod design;
+
+mod lfsr;
+pub use lfsr::Lfsr;
+
+mod loop_filter;
+pub use loop_filter::LoopFilter;
+
+mod nco;
+pub use nco::Nco;
+
+mod reed_solomon;
+pub use reed_solomon::ReedSolomon;
+
+mod window;
+pub use window::Window;
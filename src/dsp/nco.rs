@@ -0,0 +1,76 @@
+use std::f64::consts::PI;
+
+const TURN: f64 = 4294967296.0;
+
+/// Numerically controlled oscillator with a 32-bit fixed-point phase accumulator.
+///
+/// The phase wraps around exactly, i.e., unlike a floating-point accumulator, it does not
+/// lose precision or drift the longer it runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Nco {
+    phase: u32,
+    step: u32,
+}
+
+impl Nco {
+    pub fn new(freq: f64, sample_rate: f64) -> Nco {
+        let mut nco = Nco::default();
+        nco.set_freq(freq, sample_rate);
+        nco
+    }
+
+    /// Sets the frequency in Hz, which can be negative.
+    pub fn set_freq(&mut self, freq: f64, sample_rate: f64) {
+        self.step = to_fixed(freq / sample_rate);
+    }
+
+    /// Frequency in Hz in `[-sample_rate / 2, sample_rate / 2)`.
+    pub fn freq(&self, sample_rate: f64) -> f64 {
+        self.step as i32 as f64 / TURN * sample_rate
+    }
+
+    /// Sets the frequency in radians per sample.
+    pub fn set_angular_freq(&mut self, omega: f64) {
+        self.step = to_fixed(omega / (2.0 * PI));
+    }
+
+    /// Frequency in radians per sample in `[-pi, pi)`.
+    pub fn angular_freq(&self) -> f64 {
+        self.step as i32 as f64 / TURN * 2.0 * PI
+    }
+
+    /// Sets the phase in radians.
+    pub fn set_phase(&mut self, phase: f64) {
+        self.phase = to_fixed(phase / (2.0 * PI));
+    }
+
+    /// Adds `delta` radians to the phase.
+    pub fn adjust_phase(&mut self, delta: f64) {
+        self.phase = self.phase.wrapping_add(to_fixed(delta / (2.0 * PI)));
+    }
+
+    /// Phase in radians in `[-pi, pi)`.
+    pub fn phase(&self) -> f64 {
+        self.phase as i32 as f64 / TURN * 2.0 * PI
+    }
+
+    /// Raw phase, where `2^32` corresponds to a full turn.
+    pub fn raw_phase(&self) -> u32 {
+        self.phase
+    }
+
+    /// Advances the phase by one sample.
+    pub fn step(&mut self) {
+        self.phase = self.phase.wrapping_add(self.step);
+    }
+
+    /// Advances the phase by `n` samples.
+    pub fn step_n(&mut self, n: u32) {
+        self.phase = self.phase.wrapping_add(self.step.wrapping_mul(n));
+    }
+}
+
+// fraction of a turn to fixed point, wrapping around
+fn to_fixed(turns: f64) -> u32 {
+    (turns.rem_euclid(1.0) * TURN).round() as u64 as u32
+}
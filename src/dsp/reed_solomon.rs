@@ -0,0 +1,222 @@
+/// Systematic Reed-Solomon code over GF(256).
+///
+/// Codewords are the data followed by `nroots` parity bytes and have at most 255 bytes. Shorter
+/// codewords are shortened codes, i.e., the missing leading data bytes are zero. The code
+/// corrects up to `nroots / 2` byte errors.
+#[derive(Clone, Debug)]
+pub struct ReedSolomon {
+    nroots: usize,
+    fcr: usize,
+    exp: Vec<u8>,
+    log: Vec<u8>,
+    // monic generator polynomial, highest degree first
+    generator: Vec<u8>,
+}
+
+impl ReedSolomon {
+    /// Code with field polynomial `0x11d` and first consecutive root `1`.
+    pub fn new(nroots: usize) -> ReedSolomon {
+        Self::with_params(0x11d, 0, nroots)
+    }
+
+    /// Code with the given primitive field polynomial of degree 8 and first consecutive root
+    /// `alpha^fcr`.
+    pub fn with_params(gf_poly: u32, fcr: usize, nroots: usize) -> ReedSolomon {
+        assert!(
+            (1..255).contains(&nroots),
+            "number of roots has to be in [1, 254]"
+        );
+        let mut exp = vec![0u8; 512];
+        let mut log = vec![0u8; 256];
+        let mut x = 1u32;
+        for (i, e) in exp.iter_mut().take(255).enumerate() {
+            *e = x as u8;
+            log[x as usize] = i as u8;
+            x <<= 1;
+            if x & 0x100 != 0 {
+                x ^= gf_poly;
+            }
+        }
+        assert_eq!(x, 1, "field polynomial is not primitive");
+        for i in 255..512 {
+            exp[i] = exp[i - 255];
+        }
+
+        let mut rs = ReedSolomon {
+            nroots,
+            fcr,
+            exp,
+            log,
+            generator: vec![1],
+        };
+        for j in 0..nroots {
+            let root = rs.pow((fcr + j) % 255);
+            let mut g = rs.generator.clone();
+            g.push(0);
+            for (c, prev) in g[1..].iter_mut().zip(&rs.generator) {
+                *c ^= rs.mul(root, *prev);
+            }
+            rs.generator = g;
+        }
+        rs
+    }
+
+    pub fn nroots(&self) -> usize {
+        self.nroots
+    }
+
+    /// Maximum number of data bytes per codeword.
+    pub fn data_len(&self) -> usize {
+        255 - self.nroots
+    }
+
+    fn mul(&self, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+        }
+    }
+
+    fn div(&self, a: u8, b: u8) -> u8 {
+        if a == 0 {
+            0
+        } else {
+            self.exp[self.log[a as usize] as usize + 255 - self.log[b as usize] as usize]
+        }
+    }
+
+    fn pow(&self, e: usize) -> u8 {
+        self.exp[e % 255]
+    }
+
+    // evaluates a polynomial given with the lowest degree first
+    fn eval(&self, poly: &[u8], x: u8) -> u8 {
+        poly.iter().rev().fold(0, |acc, c| self.mul(acc, x) ^ c)
+    }
+
+    /// Codeword with `data` followed by the parity bytes.
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        assert!(
+            data.len() <= self.data_len(),
+            "data exceeds {} bytes",
+            self.data_len()
+        );
+        let mut parity = vec![0u8; self.nroots];
+        for d in data {
+            let feedback = d ^ parity[0];
+            parity.rotate_left(1);
+            parity[self.nroots - 1] = 0;
+            if feedback != 0 {
+                for (p, g) in parity.iter_mut().zip(&self.generator[1..]) {
+                    *p ^= self.mul(feedback, *g);
+                }
+            }
+        }
+        let mut out = data.to_vec();
+        out.extend_from_slice(&parity);
+        out
+    }
+
+    fn syndromes(&self, codeword: &[u8]) -> Vec<u8> {
+        (0..self.nroots)
+            .map(|j| {
+                let x = self.pow(self.fcr + j);
+                codeword.iter().fold(0, |acc, c| self.mul(acc, x) ^ c)
+            })
+            .collect()
+    }
+
+    /// Corrects the codeword in place and returns the number of corrected bytes or `None` if
+    /// the errors could not be corrected.
+    pub fn decode(&self, codeword: &mut [u8]) -> Option<usize> {
+        let n = codeword.len();
+        if n <= self.nroots || n > 255 {
+            return None;
+        }
+        let s = self.syndromes(codeword);
+        if s.iter().all(|x| *x == 0) {
+            return Some(0);
+        }
+
+        // Berlekamp-Massey for the error locator, lowest degree first
+        let mut lambda = vec![1u8];
+        let mut b = vec![1u8];
+        let mut l = 0;
+        let mut m = 1;
+        let mut last = 1u8;
+        for k in 0..self.nroots {
+            let mut d = s[k];
+            for i in 1..=l.min(lambda.len() - 1) {
+                d ^= self.mul(lambda[i], s[k - i]);
+            }
+            if d == 0 {
+                m += 1;
+                continue;
+            }
+            let scale = self.div(d, last);
+            let mut next = lambda.clone();
+            next.resize(next.len().max(b.len() + m), 0);
+            for (i, c) in b.iter().enumerate() {
+                next[i + m] ^= self.mul(scale, *c);
+            }
+            if 2 * l <= k {
+                l = k + 1 - l;
+                b = lambda;
+                last = d;
+                m = 1;
+            } else {
+                m += 1;
+            }
+            lambda = next;
+        }
+        lambda.truncate(l + 1);
+        if l == 0 || 2 * l > self.nroots {
+            return None;
+        }
+
+        // Chien search for the error positions; byte i has power n - 1 - i
+        let positions: Vec<usize> = (0..n)
+            .filter(|i| {
+                let power = n - 1 - i;
+                self.eval(&lambda, self.pow(255 - power)) == 0
+            })
+            .collect();
+        if positions.len() != l {
+            return None;
+        }
+
+        // Forney for the error values
+        let mut omega = vec![0u8; self.nroots];
+        for (i, si) in s.iter().enumerate() {
+            for (j, lj) in lambda.iter().enumerate().take(self.nroots - i) {
+                omega[i + j] ^= self.mul(*si, *lj);
+            }
+        }
+        // formal derivative, only odd terms remain
+        let derivative: Vec<u8> = lambda
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| if i % 2 == 1 { *c } else { 0 })
+            .collect();
+        for i in &positions {
+            let power = n - 1 - i;
+            let x_inv = self.pow(255 - power);
+            let num = self.mul(
+                self.eval(&omega, x_inv),
+                self.pow((power * (256 - self.fcr % 255)) % 255),
+            );
+            let den = self.eval(&derivative, x_inv);
+            if den == 0 {
+                return None;
+            }
+            codeword[*i] ^= self.div(num, den);
+        }
+
+        if self.syndromes(codeword).iter().any(|x| *x != 0) {
+            return None;
+        }
+        Some(l)
+    }
+}
@@ -0,0 +1,56 @@
+use serde::Deserialize;
+use std::f32::consts::PI;
+
+/// Window functions for spectral analysis and filter design.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Window {
+    Rectangular,
+    Hann,
+    Hamming,
+    BlackmanHarris,
+    /// Kaiser window with the given beta.
+    Kaiser(f32),
+}
+
+impl Window {
+    /// Symmetric window of length `len`.
+    pub fn taps(&self, len: usize) -> Vec<f32> {
+        if len == 1 {
+            return vec![1.0];
+        }
+        let m = (len - 1) as f32;
+        (0..len)
+            .map(|n| {
+                let x = n as f32 / m;
+                match self {
+                    Window::Rectangular => 1.0,
+                    Window::Hann => 0.5 - 0.5 * (2.0 * PI * x).cos(),
+                    Window::Hamming => 0.54 - 0.46 * (2.0 * PI * x).cos(),
+                    Window::BlackmanHarris => {
+                        0.35875 - 0.48829 * (2.0 * PI * x).cos() + 0.14128 * (4.0 * PI * x).cos()
+                            - 0.01168 * (6.0 * PI * x).cos()
+                    }
+                    Window::Kaiser(beta) => {
+                        let r = 2.0 * x - 1.0;
+                        bessel_i0(beta * (1.0 - r * r).sqrt()) / bessel_i0(*beta)
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+// zeroth-order modified Bessel function of the first kind (power series)
+fn bessel_i0(x: f32) -> f32 {
+    let x = x as f64 / 2.0;
+    let mut sum = 1.0f64;
+    let mut term = 1.0f64;
+    let mut k = 1.0f64;
+    while term > 1e-12 * sum {
+        term *= (x / k) * (x / k);
+        sum += term;
+        k += 1.0;
+    }
+    sum as f32
+}
@@ -1,6 +1,5 @@
 #![recursion_limit = "512"]
 #![allow(clippy::new_ret_no_self)]
-#![cfg_attr(not(RUSTC_IS_STABLE), feature(core_intrinsics))]
 
 //! An experimental asynchronous SDR runtime for heterogeneous architectures that is:
 //! * **Extensible**: custom buffers (supporting accelerators like GPUs and FPGAs) and custom schedulers (optimized for your application).
@@ -35,6 +34,7 @@
 //! ```
 
 pub mod blocks;
+pub mod dsp;
 pub mod runtime;
 
 // re-exports
@@ -124,6 +124,7 @@ pub trait AsyncBlockT: Send + Any {
     // ##### MESSAGE IO
     fn message_input_is_async(&self, id: usize) -> bool;
     fn message_input_name_to_id(&self, name: &str) -> Option<usize>;
+    fn message_input_names(&self) -> Vec<String>;
     fn message_outputs(&self) -> &Vec<MessageOutput>;
     fn message_outputs_mut(&mut self) -> &mut Vec<MessageOutput>;
     fn message_output(&self, id: usize) -> &MessageOutput;
@@ -167,6 +168,7 @@ pub trait SyncBlockT: Send + Any {
     // ##### MESSAGE IO
     fn message_input_is_async(&self, id: usize) -> bool;
     fn message_input_name_to_id(&self, name: &str) -> Option<usize>;
+    fn message_input_names(&self) -> Vec<String>;
     fn message_outputs(&self) -> &Vec<MessageOutput>;
     fn message_outputs_mut(&mut self) -> &mut Vec<MessageOutput>;
     fn message_output(&self, id: usize) -> &MessageOutput;
@@ -265,6 +267,9 @@ impl<T: AsyncKernel + Send + 'static> AsyncBlockT for AsyncBlock<T> {
     fn message_input_name_to_id(&self, name: &str) -> Option<usize> {
         self.mio.input_name_to_id(name)
     }
+    fn message_input_names(&self) -> Vec<String> {
+        self.mio.input_names()
+    }
     fn message_outputs(&self) -> &Vec<MessageOutput> {
         self.mio.outputs()
     }
@@ -386,6 +391,9 @@ impl<T: SyncKernel + Send + 'static> SyncBlockT for SyncBlock<T> {
     fn message_input_name_to_id(&self, name: &str) -> Option<usize> {
         self.mio.input_name_to_id(name)
     }
+    fn message_input_names(&self) -> Vec<String> {
+        self.mio.input_names()
+    }
     fn message_outputs(&self) -> &Vec<MessageOutput> {
         self.mio.outputs()
     }
@@ -613,6 +621,12 @@ impl Block {
             Block::Async(b) => b.message_input_name_to_id(name),
         }
     }
+    pub fn message_input_names(&self) -> Vec<String> {
+        match self {
+            Block::Sync(b) => b.message_input_names(),
+            Block::Async(b) => b.message_input_names(),
+        }
+    }
     pub fn message_outputs(&self) -> &Vec<MessageOutput> {
         match self {
             Block::Sync(b) => b.message_outputs(),
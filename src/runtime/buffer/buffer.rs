@@ -3,6 +3,7 @@ use std::any::Any;
 use std::fmt::Debug;
 use std::usize;
 
+use crate::runtime::config::Config;
 use crate::runtime::AsyncMessage;
 
 pub trait BufferBuilder: Send + Sync + Any {
@@ -12,6 +13,27 @@ pub trait BufferBuilder: Send + Sync + Any {
         writer_inbox: Sender<AsyncMessage>,
         writer_output_id: usize,
     ) -> BufferWriter;
+
+    /// Builds the buffer for a flowgraph that runs with the given [Config].
+    ///
+    /// Builders that do not depend on the configuration can rely on the default implementation.
+    fn build_with_config(
+        &self,
+        _config: &Config,
+        item_size: usize,
+        writer_inbox: Sender<AsyncMessage>,
+        writer_output_id: usize,
+    ) -> BufferWriter {
+        self.build(item_size, writer_inbox, writer_output_id)
+    }
+
+    /// Describes the buffer that is built for the given [Config], e.g., to render the flowgraph.
+    ///
+    /// Only builders that choose the buffer at runtime have to implement it, others are shown
+    /// with their [Debug] representation.
+    fn describe(&self, _config: &Config) -> Option<String> {
+        None
+    }
 }
 
 #[async_trait]
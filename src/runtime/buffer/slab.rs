@@ -193,14 +193,15 @@ impl BufferWriterHost for Writer {
             return;
         }
 
-        self.reader_inbox
+        // the reader might have terminated already, e.g., a Head
+        let _ = self
+            .reader_inbox
             .as_mut()
             .unwrap()
             .send(AsyncMessage::StreamInputDone {
                 input_id: self.reader_input_id.unwrap(),
             })
-            .await
-            .unwrap();
+            .await;
     }
 
     fn finish(&mut self) {
@@ -287,12 +288,13 @@ impl BufferReaderHost for Reader {
             return;
         }
 
-        self.writer_inbox
+        // the writer terminates without waiting for its readers, like for the Circular buffer
+        let _ = self
+            .writer_inbox
             .send(AsyncMessage::StreamOutputDone {
                 output_id: self.writer_output_id,
             })
-            .await
-            .unwrap();
+            .await;
     }
 
     fn finish(&mut self) {
@@ -1,116 +1,279 @@
-use config::Value;
+pub use config::FileFormat;
+pub use config::Value;
 #[cfg(not(target_arch = "wasm32"))]
-use config::{File, Source};
+use config::{Environment, File, Source};
 use log::LevelFilter;
 use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
 use std::collections::HashMap;
+use std::fmt;
 use std::net::SocketAddr;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
 use std::path::PathBuf;
 use std::str::FromStr;
 
+/// Global configuration, loaded on first use.
 pub fn config() -> &'static Config {
     &*CONFIG
 }
 
 pub fn get_value(name: &str) -> Option<Value> {
-    CONFIG.misc.get(name).cloned()
+    CONFIG.get_value(name)
 }
 
 pub fn get<T: FromStr>(name: &str) -> Option<T> {
-    CONFIG
-        .misc
-        .get(name)
-        .and_then(|v| v.clone().into_str().ok())
-        .and_then(|v| v.parse::<T>().ok())
+    CONFIG.get(name)
 }
 
 pub fn get_or_default<T: FromStr>(name: &str, default: T) -> T {
     get(name).unwrap_or(default)
 }
 
+/// Deserializes a custom value of the global configuration, e.g., a table.
+pub fn get_typed<T: DeserializeOwned>(name: &str) -> Option<T> {
+    CONFIG.get_typed(name)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 static CONFIG: Lazy<Config> = Lazy::new(|| {
-    let mut settings = ::config::Config::new();
-
-    // user config
-    if let Some(mut path) = dirs::config_dir() {
-        path.push("futuresdr");
-        path.push("config.toml");
+    let mut c = Config::default();
 
-        if let Err(e) = settings.merge(File::from(path.clone()).required(false)) {
-            println!("user config error ({:?}): {:?}", path, e);
+    // invalid values are reported and skipped, keeping the defaults
+    // the logger is configured from the config, so errors go to stderr directly
+    for (origin, source) in Config::sources() {
+        for e in c.merge_source(&origin, source.as_ref()) {
+            eprintln!("{}", e);
         }
     }
 
-    // project config
-    if let Err(e) =
-        settings.merge(File::new("config.toml", config::FileFormat::Toml).required(false))
-    {
-        println!("project config error (config.toml): {:?}", e);
-    }
-
-    // env config
-    if let Err(e) = settings.merge(config::Environment::with_prefix("futuresdr")) {
-        println!("env config error: {:?}", e);
-    }
-
-    // start from default config
-    let mut c = Config::default();
-
-    if let Ok(config) = settings.collect() {
-        for (k, v) in config.iter() {
-            match k.as_str() {
-                "queue_size" => {
-                    c.queue_size = config_parse::<usize>(v);
-                }
-                "buffer_size" => {
-                    c.buffer_size = config_parse::<usize>(v);
-                }
-                "log_level" => {
-                    c.log_level = config_parse::<LevelFilter>(v);
-                }
-                "ctrlport_enable" => {
-                    c.ctrlport_enable = config_parse::<bool>(v);
-                }
-                "ctrlport_bind" => {
-                    c.ctrlport_bind = Some(config_parse::<SocketAddr>(v));
-                }
-                "frontend_path" => {
-                    c.frontend_path = Some(config_parse::<PathBuf>(v));
-                }
-                _ => {
-                    c.misc.insert(k.clone(), v.clone());
-                }
+    // values that are only invalid in combination are reset as well
+    let defaults = Config::default();
+    while let Err(e) = c.validate() {
+        match e.key() {
+            Some("queue_size") => {
+                eprintln!("{}, using default {}", e, defaults.queue_size);
+                c.queue_size = defaults.queue_size;
+            }
+            Some("scheduler_threads") => {
+                eprintln!("{}, using default", e);
+                c.scheduler_threads = defaults.scheduler_threads;
+            }
+            _ => {
+                eprintln!("{}, disabling ctrlport", e);
+                c.ctrlport_enable = false;
             }
         }
     }
-    assert!(c.validate(), "invalid config");
 
     c
 });
 
 #[cfg(target_arch = "wasm32")]
-static CONFIG: Lazy<Config> = Lazy::new(|| Config::default());
+static CONFIG: Lazy<Config> = Lazy::new(Config::default);
 
-#[derive(Debug)]
+/// Scheduler selected through the configuration.
+///
+/// Only relevant where the scheduler is chosen at runtime. A [Runtime](crate::runtime::Runtime)
+/// is typed over its scheduler.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SchedulerKind {
+    Smol,
+    Flow,
+    Tpb,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub queue_size: usize,
     pub buffer_size: usize,
+    #[serde(deserialize_with = "deserialize_level_filter")]
     pub log_level: LevelFilter,
+    /// Starts the control port, which is shared by all flowgraphs of the process.
+    ///
+    /// The control port settings are process-wide. They are taken from the global
+    /// configuration when the first flowgraph starts, not from the [Config] of a runtime or
+    /// flowgraph.
     pub ctrlport_enable: bool,
+    /// Address of the control port, only read from the global configuration.
     pub ctrlport_bind: Option<SocketAddr>,
+    /// Serves the metrics of all flowgraphs on `/metrics`, only read from the global
+    /// configuration.
+    pub ctrlport_metrics: bool,
+    /// Frontend served by the control port, only read from the global configuration.
     pub frontend_path: Option<PathBuf>,
+    /// Scheduler of `futuresdr-run`.
+    pub scheduler: SchedulerKind,
+    /// Worker threads of the Smol and Flow schedulers, defaults to the number of cores.
+    ///
+    /// The TPB scheduler runs every block on its own thread.
+    pub scheduler_threads: Option<usize>,
+    /// Pins the worker threads of the Smol and Flow schedulers to cores.
+    pub scheduler_pin_threads: bool,
+    #[serde(flatten)]
     misc: HashMap<String, Value>,
 }
 
 impl Config {
+    /// Loads the configuration from the user config directory, the `config.toml` of
+    /// the project, and `FUTURESDR_` environment variables, in this order.
+    ///
+    /// In contrast to the global configuration, the first invalid value is returned
+    /// as error.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Result<Config, ConfigError> {
+        let mut c = Config::default();
+        for (origin, source) in Config::sources() {
+            if let Some(e) = c.merge_source(&origin, source.as_ref()).into_iter().next() {
+                return Err(e);
+            }
+        }
+        c.validate()?;
+        Ok(c)
+    }
+
+    /// Loads the configuration from a single file, on top of the defaults.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
+        let mut c = Config::default();
+        c.merge_file(path)?;
+        c.validate()?;
+        Ok(c)
+    }
+
+    /// Merges the values of a file into the configuration.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn merge_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ConfigError> {
+        let path = path.as_ref();
+        let origin = path.display().to_string();
+        let source = File::from(path.to_path_buf()).required(true);
+        match self.merge_source(&origin, &source).into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
-    fn validate(&self) -> bool {
+    fn sources() -> Vec<(String, Box<dyn Source + Send + Sync>)> {
+        let mut sources: Vec<(String, Box<dyn Source + Send + Sync>)> = Vec::new();
+
+        // user config
+        if let Some(mut path) = dirs::config_dir() {
+            path.push("futuresdr");
+            path.push("config.toml");
+            sources.push((
+                path.display().to_string(),
+                Box::new(File::from(path).required(false)),
+            ));
+        }
+
+        // project config
+        sources.push((
+            "config.toml".to_string(),
+            Box::new(File::new("config.toml", FileFormat::Toml).required(false)),
+        ));
+
+        // env config
+        sources.push((
+            "environment".to_string(),
+            Box::new(Environment::with_prefix("futuresdr")),
+        ));
+
+        sources
+    }
+
+    /// Applies all values of a source, returning the errors of the values that were skipped.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn merge_source(&mut self, origin: &str, source: &dyn Source) -> Vec<ConfigError> {
+        let values = match source.collect() {
+            Ok(v) => v,
+            Err(e) => return vec![ConfigError::new(None, origin, e.to_string())],
+        };
+
+        let mut keys: Vec<&String> = values.keys().collect();
+        keys.sort();
+
+        let mut errors = Vec::new();
+        for k in keys {
+            if let Err(e) = self.set(k, values[k].clone()) {
+                errors.push(e.with_origin(origin));
+            }
+        }
+        errors
+    }
+
+    /// Sets a value, parsing it into the type of the corresponding field.
+    ///
+    /// Unknown keys are kept and can be retrieved with [get](Config::get).
+    pub fn set(&mut self, key: &str, value: Value) -> Result<(), ConfigError> {
+        match key {
+            "queue_size" => self.queue_size = parse(key, value)?,
+            "buffer_size" => self.buffer_size = parse(key, value)?,
+            "log_level" => {
+                let s: String = parse(key, value)?;
+                self.log_level = s.parse::<LevelFilter>().map_err(|_| {
+                    ConfigError::new(Some(key), "value", format!("invalid log level {:?}", s))
+                })?;
+            }
+            "ctrlport_enable" => self.ctrlport_enable = parse(key, value)?,
+            "ctrlport_bind" => self.ctrlport_bind = Some(parse(key, value)?),
+            "ctrlport_metrics" => self.ctrlport_metrics = parse(key, value)?,
+            "frontend_path" => self.frontend_path = Some(parse(key, value)?),
+            "scheduler" => self.scheduler = parse(key, value)?,
+            "scheduler_threads" => self.scheduler_threads = Some(parse(key, value)?),
+            "scheduler_pin_threads" => self.scheduler_pin_threads = parse(key, value)?,
+            _ => {
+                self.misc.insert(key.to_string(), value);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_value(&self, name: &str) -> Option<Value> {
+        self.misc.get(name).cloned()
+    }
+
+    /// Parses a custom value, i.e., one that is not a field of [Config], from its string.
+    pub fn get<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.misc
+            .get(name)
+            .and_then(|v| v.clone().into_str().ok())
+            .and_then(|v| v.parse::<T>().ok())
+    }
+
+    /// Deserializes a custom value, e.g., a table.
+    pub fn get_typed<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        self.misc
+            .get(name)
+            .and_then(|v| v.clone().try_into::<T>().ok())
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
         if self.ctrlport_enable && self.ctrlport_bind.is_none() {
-            println!("ctrlport enabled but socket not set");
-            return false;
+            return Err(ConfigError::new(
+                Some("ctrlport_bind"),
+                "config",
+                "ctrlport enabled but socket not set",
+            ));
+        }
+        if self.queue_size == 0 {
+            return Err(ConfigError::new(
+                Some("queue_size"),
+                "config",
+                "has to be non-zero",
+            ));
         }
-        true
+        if self.scheduler_threads == Some(0) {
+            return Err(ConfigError::new(
+                Some("scheduler_threads"),
+                "config",
+                "has to be non-zero",
+            ));
+        }
+        Ok(())
     }
 }
 
@@ -123,7 +286,11 @@ impl Default for Config {
             log_level: LevelFilter::Debug,
             ctrlport_enable: true,
             ctrlport_bind: "127.0.0.1:26125".parse::<SocketAddr>().ok(),
+            ctrlport_metrics: false,
             frontend_path: None,
+            scheduler: SchedulerKind::Smol,
+            scheduler_threads: None,
+            scheduler_pin_threads: false,
             misc: HashMap::new(),
         }
     }
@@ -136,20 +303,117 @@ impl Default for Config {
             log_level: LevelFilter::Info,
             ctrlport_enable: false,
             ctrlport_bind: None,
+            ctrlport_metrics: false,
             frontend_path: None,
+            scheduler: SchedulerKind::Smol,
+            scheduler_threads: None,
+            scheduler_pin_threads: false,
             misc: HashMap::new(),
         }
     }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-fn config_parse<T: FromStr>(v: &Value) -> T {
-    if let Ok(v) = v.clone().into_str() {
-        if let Ok(v) = v.parse::<T>() {
-            return v;
+/// Invalid configuration value.
+#[derive(Clone, Debug)]
+pub struct ConfigError {
+    key: Option<String>,
+    origin: String,
+    message: String,
+}
+
+impl ConfigError {
+    fn new(key: Option<&str>, origin: &str, message: impl Into<String>) -> ConfigError {
+        ConfigError {
+            key: key.map(|k| k.to_string()),
+            origin: origin.to_string(),
+            message: message.into(),
         }
     }
 
-    println!("invalid config value {:?}", v);
-    panic!();
+    #[cfg(not(target_arch = "wasm32"))]
+    fn with_origin(mut self, origin: &str) -> ConfigError {
+        self.origin = origin.to_string();
+        self
+    }
+
+    /// The offending key, if the error is not about the source as a whole.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Where the value came from, e.g., the path of the config file.
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.key {
+            Some(k) => write!(
+                f,
+                "config error in {}: invalid value for `{}`: {}",
+                self.origin, k, self.message
+            ),
+            None => write!(f, "config error in {}: {}", self.origin, self.message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn parse<T: DeserializeOwned>(key: &str, value: Value) -> Result<T, ConfigError> {
+    value
+        .try_into::<T>()
+        .map_err(|e| ConfigError::new(Some(key), "value", e.to_string()))
+}
+
+fn deserialize_level_filter<'de, D: Deserializer<'de>>(d: D) -> Result<LevelFilter, D::Error> {
+    let s = String::deserialize(d)?;
+    s.parse::<LevelFilter>().map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_typed() {
+        let mut c = Config::default();
+        c.set("buffer_size", Value::from("65536")).unwrap();
+        c.set("log_level", Value::from("warn")).unwrap();
+        c.set("scheduler", Value::from("flow")).unwrap();
+        c.set("ctrlport_bind", Value::from("127.0.0.1:1234"))
+            .unwrap();
+        c.set("foo", Value::from(42)).unwrap();
+        c.set("bar", Value::from(vec![1, 2])).unwrap();
+
+        assert_eq!(c.buffer_size, 65536);
+        assert_eq!(c.log_level, LevelFilter::Warn);
+        assert_eq!(c.scheduler, SchedulerKind::Flow);
+        assert_eq!(c.ctrlport_bind, "127.0.0.1:1234".parse().ok());
+        assert_eq!(c.get::<u32>("foo"), Some(42));
+        assert_eq!(c.get_typed::<u32>("foo"), Some(42));
+        assert_eq!(c.get_typed::<Vec<u32>>("bar"), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn set_invalid() {
+        let mut c = Config::default();
+        let e = c.set("queue_size", Value::from("lots")).unwrap_err();
+        assert_eq!(e.key(), Some("queue_size"));
+        assert_eq!(c.queue_size, Config::default().queue_size);
+    }
+
+    #[test]
+    fn file_error_names_key_and_file() {
+        let path = std::env::temp_dir().join("futuresdr-config-test.toml");
+        std::fs::write(&path, "buffer_size = 1024\nctrlport_metrics = \"maybe\"\n").unwrap();
+
+        let e = Config::from_file(&path).unwrap_err();
+        assert_eq!(e.key(), Some("ctrlport_metrics"));
+        assert_eq!(e.origin(), path.display().to_string());
+
+        std::fs::remove_file(&path).unwrap();
+    }
 }
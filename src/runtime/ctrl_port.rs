@@ -1,19 +1,27 @@
 use axum::extract::{Extension, Path};
+use axum::http::header;
 use axum::http::StatusCode;
+use axum::response::Headers;
 use axum::routing::{get, get_service, post};
 use axum::Json;
 use axum::Router;
 use futures::channel::mpsc;
 use futures::channel::oneshot;
 use futures::prelude::*;
+use once_cell::sync::Lazy;
 use slab::Slab;
+use std::collections::BTreeMap;
+use std::fmt::Write;
 use std::path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use tower_http::add_extension::AddExtensionLayer;
 use tower_http::cors::CorsLayer;
 use tower_http::services::ServeDir;
 
 use crate::runtime::config;
 use crate::runtime::AsyncMessage;
+use crate::runtime::BlockMetrics;
 use crate::runtime::Pmt;
 
 macro_rules! relative {
@@ -26,69 +34,379 @@ macro_rules! relative {
     };
 }
 
-async fn index(Extension(boxes): Extension<Slab<Option<mpsc::Sender<AsyncMessage>>>>) -> String {
-    format!("number of Blocks {:?}", boxes.len())
+type Inboxes = Slab<Option<mpsc::Sender<AsyncMessage>>>;
+type Registry = Arc<Mutex<BTreeMap<usize, RegisteredFlowgraph>>>;
+
+struct RegisteredFlowgraph {
+    inboxes: Inboxes,
+    /// Topology of the flowgraph, rendered when it was started.
+    dot: String,
+    /// Set once the first block terminated.
+    terminating: bool,
 }
 
-async fn handler_id(
-    Path((blk, handler)): Path<(usize, usize)>,
-    Extension(boxes): Extension<Slab<Option<mpsc::Sender<AsyncMessage>>>>,
-) -> String {
-    let mut b = match boxes.get(blk) {
-        Some(Some(s)) => s.clone(),
-        _ => return "block not found".to_string(),
-    };
+static CTRL_PORT: Lazy<ControlPort> = Lazy::new(ControlPort::new);
+// ids are never reused, so that a stale id cannot address another flowgraph
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Process-wide control port, shared by all running flowgraphs.
+///
+/// The web server is started when the first flowgraph registers. Flowgraphs
+/// are addressed through the id they got assigned during registration. The
+/// server is set up from the global config, since it outlives the runtimes.
+struct ControlPort {
+    flowgraphs: Registry,
+}
+
+impl ControlPort {
+    fn new() -> ControlPort {
+        let flowgraphs = Arc::new(Mutex::new(BTreeMap::new()));
+
+        if config::config().ctrlport_enable {
+            start_control_port(flowgraphs.clone());
+        }
+
+        ControlPort { flowgraphs }
+    }
+}
+
+/// Registration of a running flowgraph, which is removed from the control port when dropped.
+pub struct Registration {
+    id: usize,
+}
+
+impl Registration {
+    /// Marks the flowgraph as terminating in the metrics.
+    pub fn set_terminating(&self) {
+        if let Some(fg) = CTRL_PORT.flowgraphs.lock().unwrap().get_mut(&self.id) {
+            fg.terminating = true;
+        }
+    }
+}
 
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if CTRL_PORT
+            .flowgraphs
+            .lock()
+            .unwrap()
+            .remove(&self.id)
+            .is_some()
+        {
+            debug!("control port: unregistered flowgraph {}", self.id);
+        }
+    }
+}
+
+/// Registers the inboxes and the DOT rendering of a running flowgraph.
+pub fn register(inboxes: Inboxes, dot: String) -> Registration {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    CTRL_PORT.flowgraphs.lock().unwrap().insert(
+        id,
+        RegisteredFlowgraph {
+            inboxes,
+            dot,
+            terminating: false,
+        },
+    );
+    debug!("control port: registered flowgraph {}", id);
+    Registration { id }
+}
+
+fn block_inbox(
+    flowgraphs: &Registry,
+    fg: usize,
+    blk: usize,
+) -> Result<mpsc::Sender<AsyncMessage>, String> {
+    let flowgraphs = flowgraphs.lock().unwrap();
+    let registered = flowgraphs
+        .get(&fg)
+        .ok_or_else(|| "flowgraph not found".to_string())?;
+    match registered.inboxes.get(blk) {
+        Some(Some(s)) => Ok(s.clone()),
+        _ => Err("block not found".to_string()),
+    }
+}
+
+async fn call(mut b: mpsc::Sender<AsyncMessage>, handler: usize, data: Pmt) -> String {
     let (tx, rx) = oneshot::channel::<Pmt>();
 
-    b.send(AsyncMessage::Callback {
+    if b.send(AsyncMessage::Callback {
         port_id: handler,
-        data: Pmt::Null,
+        data,
         tx,
     })
     .await
-    .unwrap();
+    .is_err()
+    {
+        return "block terminated".to_string();
+    }
 
-    let ret = rx.await.unwrap();
+    match rx.await {
+        Ok(ret) => format!("{:?}", ret),
+        Err(_) => "block terminated".to_string(),
+    }
+}
 
-    format!("{:?}", ret)
+async fn index(Extension(flowgraphs): Extension<Registry>) -> String {
+    format!(
+        "number of Flowgraphs {:?}",
+        flowgraphs.lock().unwrap().len()
+    )
+}
+
+async fn flowgraphs_list(Extension(flowgraphs): Extension<Registry>) -> Json<Vec<usize>> {
+    Json(flowgraphs.lock().unwrap().keys().copied().collect())
+}
+
+async fn flowgraph(Path(fg): Path<usize>, Extension(flowgraphs): Extension<Registry>) -> String {
+    match flowgraphs.lock().unwrap().get(&fg) {
+        Some(registered) => format!("number of Blocks {:?}", registered.inboxes.len()),
+        None => "flowgraph not found".to_string(),
+    }
+}
+
+async fn flowgraph_dot(
+    Path(fg): Path<usize>,
+    Extension(flowgraphs): Extension<Registry>,
+) -> Result<(Headers<[(header::HeaderName, &'static str); 1]>, String), StatusCode> {
+    match flowgraphs.lock().unwrap().get(&fg) {
+        Some(registered) => Ok((
+            Headers([(header::CONTENT_TYPE, "text/vnd.graphviz; charset=utf-8")]),
+            registered.dot.clone(),
+        )),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+async fn handler_id(
+    Path((fg, blk, handler)): Path<(usize, usize, usize)>,
+    Extension(flowgraphs): Extension<Registry>,
+) -> String {
+    match block_inbox(&flowgraphs, fg, blk) {
+        Ok(b) => call(b, handler, Pmt::Null).await,
+        Err(e) => e,
+    }
 }
 
 async fn handler_id_post(
-    Path((blk, handler)): Path<(usize, usize)>,
+    Path((fg, blk, handler)): Path<(usize, usize, usize)>,
     Json(pmt): Json<Pmt>,
-    Extension(boxes): Extension<Slab<Option<mpsc::Sender<AsyncMessage>>>>,
+    Extension(flowgraphs): Extension<Registry>,
 ) -> String {
-    let mut b = match boxes.get(blk) {
-        Some(Some(s)) => s.clone(),
-        _ => return "block not found".to_string(),
-    };
+    match block_inbox(&flowgraphs, fg, blk) {
+        Ok(b) => call(b, handler, pmt).await,
+        Err(e) => e,
+    }
+}
 
-    let (tx, rx) = oneshot::channel::<Pmt>();
+struct FlowgraphMetrics {
+    id: usize,
+    terminating: bool,
+    n_blocks: usize,
+    blocks: Vec<(usize, BlockMetrics)>,
+}
 
-    b.send(AsyncMessage::Callback {
-        port_id: handler,
-        data: pmt,
-        tx,
-    })
-    .await
-    .unwrap();
+async fn metrics(
+    Extension(flowgraphs): Extension<Registry>,
+) -> (Headers<[(header::HeaderName, &'static str); 1]>, String) {
+    #[allow(clippy::type_complexity)]
+    let inboxes: Vec<(usize, bool, Vec<(usize, mpsc::Sender<AsyncMessage>)>)> = flowgraphs
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(fg, registered)| {
+            (
+                *fg,
+                registered.terminating,
+                registered
+                    .inboxes
+                    .iter()
+                    .filter_map(|(blk, b)| b.clone().map(|b| (blk, b)))
+                    .collect(),
+            )
+        })
+        .collect();
 
-    let ret = rx.await.unwrap();
+    let mut fgs = Vec::new();
+    for (id, terminating, inboxes) in inboxes {
+        let n_blocks = inboxes.len();
+        let mut blocks = Vec::new();
+        for (blk, mut b) in inboxes {
+            let (tx, rx) = oneshot::channel::<BlockMetrics>();
+            // blocks that already terminated do not report
+            if b.send(AsyncMessage::Metrics { tx }).await.is_ok() {
+                if let Ok(m) = rx.await {
+                    blocks.push((blk, m));
+                }
+            }
+        }
+        fgs.push(FlowgraphMetrics {
+            id,
+            terminating,
+            n_blocks,
+            blocks,
+        });
+    }
+
+    (
+        Headers([(
+            header::CONTENT_TYPE,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        )]),
+        render_metrics(&fgs),
+    )
+}
 
-    format!("{:?}", ret)
+fn escape_label(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
 }
 
-pub async fn start_control_port(inboxes: Slab<Option<mpsc::Sender<AsyncMessage>>>) {
-    if !config::config().ctrlport_enable {
-        return;
+/// Renders metrics in the OpenMetrics text format.
+fn render_metrics(fgs: &[FlowgraphMetrics]) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# TYPE futuresdr_flowgraph_state stateset");
+    let _ = writeln!(
+        out,
+        "# HELP futuresdr_flowgraph_state State of the flowgraph."
+    );
+    for fg in fgs {
+        for (state, set) in [
+            ("running", !fg.terminating),
+            ("terminating", fg.terminating),
+        ] {
+            let _ = writeln!(
+                out,
+                "futuresdr_flowgraph_state{{fg=\"{}\",futuresdr_flowgraph_state=\"{}\"}} {}",
+                fg.id, state, set as u8
+            );
+        }
+    }
+
+    let _ = writeln!(out, "# TYPE futuresdr_flowgraph_blocks gauge");
+    let _ = writeln!(
+        out,
+        "# HELP futuresdr_flowgraph_blocks Blocks in the flowgraph."
+    );
+    for fg in fgs {
+        let _ = writeln!(
+            out,
+            "futuresdr_flowgraph_blocks{{fg=\"{}\"}} {}",
+            fg.id, fg.n_blocks
+        );
+    }
+
+    let _ = writeln!(out, "# TYPE futuresdr_flowgraph_active_blocks gauge");
+    let _ = writeln!(
+        out,
+        "# HELP futuresdr_flowgraph_active_blocks Blocks that did not terminate yet."
+    );
+    for fg in fgs {
+        let _ = writeln!(
+            out,
+            "futuresdr_flowgraph_active_blocks{{fg=\"{}\"}} {}",
+            fg.id,
+            fg.blocks.len()
+        );
+    }
+
+    #[allow(clippy::type_complexity)]
+    let block_counters: [(&str, &str, fn(&BlockMetrics) -> u64); 3] = [
+        ("work_calls", "Calls to the work function.", |m| {
+            m.work_calls
+        }),
+        ("messages_received", "Messages received.", |m| {
+            m.messages_received
+        }),
+        ("messages_sent", "Messages posted to outputs.", |m| {
+            m.messages_sent
+        }),
+    ];
+    for (name, help, f) in block_counters {
+        let _ = writeln!(out, "# TYPE futuresdr_block_{} counter", name);
+        let _ = writeln!(out, "# HELP futuresdr_block_{} {}", name, help);
+        for fg in fgs {
+            for (blk, m) in fg.blocks.iter() {
+                let _ = writeln!(
+                    out,
+                    "futuresdr_block_{}_total{{fg=\"{}\",block=\"{}\",name=\"{}\",type=\"{}\"}} {}",
+                    name,
+                    fg.id,
+                    blk,
+                    escape_label(&m.instance_name),
+                    escape_label(&m.type_name),
+                    f(m)
+                );
+            }
+        }
     }
 
+    let port_metrics: [(&str, &str, &str, bool); 3] = [
+        ("stream_input_items", "counter", "Items consumed.", true),
+        ("stream_output_items", "counter", "Items produced.", false),
+        (
+            "stream_input_buffered_items",
+            "gauge",
+            "Items available in the input buffer.",
+            true,
+        ),
+    ];
+    for (name, kind, help, input) in port_metrics {
+        let _ = writeln!(out, "# TYPE futuresdr_{} {}", name, kind);
+        let _ = writeln!(out, "# HELP futuresdr_{} {}", name, help);
+        let suffix = if kind == "counter" { "_total" } else { "" };
+        for fg in fgs {
+            for (blk, m) in fg.blocks.iter() {
+                let ports = if input { &m.inputs } else { &m.outputs };
+                for p in ports {
+                    let v = if kind == "counter" {
+                        p.items
+                    } else if let Some(b) = p.buffered {
+                        b as u64
+                    } else {
+                        continue;
+                    };
+                    let _ = writeln!(
+                        out,
+                        "futuresdr_{}{}{{fg=\"{}\",block=\"{}\",name=\"{}\",port=\"{}\"}} {}",
+                        name,
+                        suffix,
+                        fg.id,
+                        blk,
+                        escape_label(&m.instance_name),
+                        escape_label(&p.name),
+                        v
+                    );
+                }
+            }
+        }
+    }
+
+    out.push_str("# EOF\n");
+    out
+}
+
+fn start_control_port(flowgraphs: Registry) {
     let mut app = Router::new()
         .route("/api/", get(index))
-        .route("/api/block/:blk/call/:handler/", get(handler_id))
-        .route("/api/block/:blk/call/:handler/", post(handler_id_post))
-        .layer(AddExtensionLayer::new(inboxes))
+        .route("/api/fg/", get(flowgraphs_list))
+        .route("/api/fg/:fg/", get(flowgraph))
+        .route("/api/fg/:fg/dot/", get(flowgraph_dot))
+        .route("/api/fg/:fg/block/:blk/call/:handler/", get(handler_id))
+        .route(
+            "/api/fg/:fg/block/:blk/call/:handler/",
+            post(handler_id_post),
+        );
+
+    if config::config().ctrlport_metrics {
+        app = app.route("/metrics", get(metrics));
+    }
+
+    let mut app = app
+        .layer(AddExtensionLayer::new(flowgraphs))
         .layer(CorsLayer::permissive());
 
     let frontend = if let Some(ref p) = config::config().frontend_path {
@@ -118,10 +436,14 @@ pub async fn start_control_port(inboxes: Slab<Option<mpsc::Sender<AsyncMessage>>
 
         runtime.block_on(async move {
             let addr = config::config().ctrlport_bind.unwrap();
-            axum::Server::bind(&addr)
-                .serve(app.into_make_service())
-                .await
-                .unwrap();
+            match axum::Server::try_bind(&addr) {
+                Ok(server) => {
+                    if let Err(e) = server.serve(app.into_make_service()).await {
+                        warn!("control port: server error {:?}", e);
+                    }
+                }
+                Err(e) => warn!("control port: could not bind {}: {:?}", addr, e),
+            }
         });
     });
 }
@@ -0,0 +1,199 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+use crate::anyhow::{bail, Context, Result};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::runtime::buffer::circular::Circular;
+use crate::runtime::buffer::slab::Slab;
+use crate::runtime::config;
+use crate::runtime::config::{Config, FileFormat, Value};
+use crate::runtime::BlockParams;
+use crate::runtime::BlockRegistry;
+use crate::runtime::Flowgraph;
+
+/// Declarative description of a [Flowgraph], loaded from a TOML, YAML, or JSON file.
+///
+/// ```toml
+/// [config]
+/// buffer_size = 65536
+///
+/// [[blocks]]
+/// id = "src"
+/// type = "NullSource"
+/// params = { item_size = 4 }
+///
+/// [[blocks]]
+/// id = "head"
+/// type = "Head"
+/// params = { item_size = 4, n_items = 1000000 }
+///
+/// [[blocks]]
+/// id = "snk"
+/// type = "NullSink"
+/// params = { item_size = 4 }
+///
+/// [[stream]]
+/// src = "src.out"
+/// dst = "head.in"
+///
+/// [[stream]]
+/// src = "head.out"
+/// dst = "snk.in"
+/// buffer = "slab"
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct FlowgraphDescription {
+    /// Overrides of the global [Config](config::Config) for this flowgraph.
+    pub config: HashMap<String, Value>,
+    pub blocks: Vec<BlockDescription>,
+    pub stream: Vec<StreamConnection>,
+    pub message: Vec<MessageConnection>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BlockDescription {
+    /// Unique id, also used as instance name of the block.
+    pub id: String,
+    /// Type name in the [BlockRegistry].
+    #[serde(rename = "type")]
+    pub block_type: String,
+    #[serde(default)]
+    pub params: HashMap<String, Value>,
+}
+
+/// Stream connection between two ports, given as `block.port`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct StreamConnection {
+    pub src: String,
+    pub dst: String,
+    /// Buffer implementation, defaults to the one of the platform.
+    pub buffer: Option<BufferKind>,
+    /// Minimum buffer size in bytes, defaults to the configured `buffer_size`.
+    pub buffer_size: Option<usize>,
+}
+
+/// Message connection between two ports, given as `block.port`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct MessageConnection {
+    pub src: String,
+    pub dst: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BufferKind {
+    Circular,
+    Slab,
+}
+
+impl FlowgraphDescription {
+    /// Loads a description, detecting the format from the file extension.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<FlowgraphDescription> {
+        let path = path.as_ref();
+        let mut c = ::config::Config::default();
+        c.merge(::config::File::from(path).required(true))
+            .and_then(|c| c.clone().try_into())
+            .with_context(|| format!("failed to load {}", path.display()))
+    }
+
+    pub fn from_str(s: &str, format: FileFormat) -> Result<FlowgraphDescription> {
+        let mut c = ::config::Config::default();
+        let d = c
+            .merge(::config::File::from_str(s, format))
+            .and_then(|c| c.clone().try_into())?;
+        Ok(d)
+    }
+
+    /// The global [Config] with the overrides of the description applied.
+    pub fn config(&self) -> Result<Config> {
+        let mut c = config::config().clone();
+        let mut keys: Vec<&String> = self.config.keys().collect();
+        keys.sort();
+        for k in keys {
+            c.set(k, self.config[k].clone())?;
+        }
+        c.validate()?;
+        Ok(c)
+    }
+
+    /// Instantiates the blocks and connections, and validates the resulting [Flowgraph].
+    pub fn build(&self, registry: &BlockRegistry) -> Result<Flowgraph> {
+        let mut fg = if self.config.is_empty() {
+            Flowgraph::new()
+        } else {
+            Flowgraph::with_config(self.config()?)
+        };
+        let buffer_size = fg.topology.as_ref().unwrap().config().buffer_size;
+
+        let mut ids = HashMap::new();
+        for b in self.blocks.iter() {
+            if ids.contains_key(&b.id) {
+                bail!("duplicate block id `{}`", b.id);
+            }
+            let params = BlockParams::new(b.params.clone());
+            let block = registry
+                .build(&b.block_type, &params)
+                .with_context(|| format!("invalid block `{}`", b.id))?;
+            let id = fg.add_block(block);
+            fg.topology
+                .as_mut()
+                .unwrap()
+                .block_mut(id)
+                .unwrap()
+                .set_instance_name(&b.id);
+            ids.insert(b.id.clone(), id);
+        }
+
+        for c in self.stream.iter() {
+            let (src, src_port) = endpoint(&ids, &c.src)?;
+            let (dst, dst_port) = endpoint(&ids, &c.dst)?;
+            let size = c.buffer_size.unwrap_or(buffer_size);
+
+            let res = match (c.buffer, c.buffer_size) {
+                (None, None) => fg.connect_stream(src, src_port, dst, dst_port),
+                #[cfg(not(target_arch = "wasm32"))]
+                (Some(BufferKind::Circular), _) | (None, Some(_)) => fg.connect_stream_with_type(
+                    src,
+                    src_port,
+                    dst,
+                    dst_port,
+                    Circular::with_size(size),
+                ),
+                #[cfg(target_arch = "wasm32")]
+                (Some(BufferKind::Circular), _) => bail!("circular buffers not supported"),
+                #[cfg(target_arch = "wasm32")]
+                (None, Some(_)) => {
+                    fg.connect_stream_with_type(src, src_port, dst, dst_port, Slab::with_size(size))
+                }
+                (Some(BufferKind::Slab), _) => {
+                    fg.connect_stream_with_type(src, src_port, dst, dst_port, Slab::with_size(size))
+                }
+            };
+            res.with_context(|| format!("invalid stream connection {} -> {}", c.src, c.dst))?;
+        }
+
+        for c in self.message.iter() {
+            let (src, src_port) = endpoint(&ids, &c.src)?;
+            let (dst, dst_port) = endpoint(&ids, &c.dst)?;
+            fg.connect_message(src, src_port, dst, dst_port)
+                .with_context(|| format!("invalid message connection {} -> {}", c.src, c.dst))?;
+        }
+
+        fg.topology.as_ref().unwrap().validate()?;
+        Ok(fg)
+    }
+}
+
+fn endpoint<'a>(ids: &HashMap<String, usize>, s: &'a str) -> Result<(usize, &'a str)> {
+    let (block, port) = s
+        .rsplit_once('.')
+        .with_context(|| format!("invalid endpoint `{}`, expected `block.port`", s))?;
+    let id = ids
+        .get(block)
+        .with_context(|| format!("unknown block `{}` in `{}`", block, s))?;
+    Ok((*id, port))
+}
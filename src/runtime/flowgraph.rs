@@ -4,6 +4,7 @@ use futures::SinkExt;
 use std::cmp::{Eq, PartialEq};
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::sync::Arc;
 
 use crate::anyhow::Result;
 #[cfg(not(target_arch = "wasm32"))]
@@ -12,6 +13,7 @@ use crate::runtime::buffer::circular::Circular;
 use crate::runtime::buffer::slab::Slab;
 use crate::runtime::buffer::BufferBuilder;
 use crate::runtime::buffer::BufferWriter;
+use crate::runtime::config::Config;
 use crate::runtime::AsyncKernel;
 use crate::runtime::AsyncMessage;
 use crate::runtime::Block;
@@ -35,6 +37,17 @@ impl Flowgraph {
         }
     }
 
+    /// Creates a new [Flowgraph] that runs with the given [Config], overriding the one of the [Runtime](crate::runtime::Runtime).
+    pub fn with_config(config: Config) -> Flowgraph {
+        let mut fg = Flowgraph::new();
+        fg.set_config(config);
+        fg
+    }
+
+    pub fn set_config(&mut self, config: Config) {
+        self.topology.as_mut().unwrap().set_config(Arc::new(config));
+    }
+
     pub fn add_block(&mut self, block: Block) -> usize {
         self.topology.as_mut().unwrap().add_block(block)
     }
@@ -82,6 +95,16 @@ impl Flowgraph {
             .connect_message(src_block, src_port, dst_block, dst_port)
     }
 
+    /// Renders the [Flowgraph] in the Graphviz DOT language, see [Topology::to_dot].
+    pub fn to_dot(&self) -> String {
+        self.topology.as_ref().unwrap().to_dot()
+    }
+
+    /// Renders the [Flowgraph] as Mermaid flowchart, see [Topology::to_mermaid].
+    pub fn to_mermaid(&self) -> String {
+        self.topology.as_ref().unwrap().to_mermaid()
+    }
+
     pub fn block_async<T: AsyncKernel + 'static>(&self, id: usize) -> Option<&T> {
         self.topology
             .as_ref()
@@ -182,4 +205,32 @@ impl BufferBuilder for DefaultBuffer {
     ) -> BufferWriter {
         Slab::new().build(item_size, writer_inbox, writer_output_id)
     }
+    #[cfg(not(target_arch = "wasm32"))]
+    fn build_with_config(
+        &self,
+        config: &Config,
+        item_size: usize,
+        writer_inbox: Sender<AsyncMessage>,
+        writer_output_id: usize,
+    ) -> BufferWriter {
+        Circular::with_size(config.buffer_size).build(item_size, writer_inbox, writer_output_id)
+    }
+    #[cfg(target_arch = "wasm32")]
+    fn build_with_config(
+        &self,
+        config: &Config,
+        item_size: usize,
+        writer_inbox: Sender<AsyncMessage>,
+        writer_output_id: usize,
+    ) -> BufferWriter {
+        Slab::with_size(config.buffer_size).build(item_size, writer_inbox, writer_output_id)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    fn describe(&self, config: &Config) -> Option<String> {
+        Some(format!("{:?}", Circular::with_size(config.buffer_size)))
+    }
+    #[cfg(target_arch = "wasm32")]
+    fn describe(&self, config: &Config) -> Option<String> {
+        Some(format!("{:?}", Slab::with_size(config.buffer_size)))
+    }
 }
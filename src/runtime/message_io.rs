@@ -127,6 +127,7 @@ impl<T: Send + ?Sized> AsyncMessageInput<T> {
 pub struct MessageOutput {
     name: String,
     handlers: Vec<(usize, Sender<AsyncMessage>)>,
+    n_posted: u64,
 }
 
 impl MessageOutput {
@@ -134,6 +135,7 @@ impl MessageOutput {
         MessageOutput {
             name: name.to_string(),
             handlers: Vec::new(),
+            n_posted: 0,
         }
     }
 
@@ -141,6 +143,10 @@ impl MessageOutput {
         &self.name
     }
 
+    pub fn n_posted(&self) -> u64 {
+        self.n_posted
+    }
+
     pub fn connect(&mut self, port: usize, sender: Sender<AsyncMessage>) {
         self.handlers.push((port, sender));
     }
@@ -152,6 +158,7 @@ impl MessageOutput {
     }
 
     pub async fn post(&mut self, p: Pmt) {
+        self.n_posted += 1;
         for (port_id, sender) in self.handlers.iter_mut() {
             sender
                 .send(AsyncMessage::Call {
@@ -193,6 +200,10 @@ impl<T: Send + ?Sized> MessageIo<T> {
         &self.inputs[id]
     }
 
+    pub fn input_names(&self) -> Vec<String> {
+        self.inputs.iter().map(|x| x.name().to_string()).collect()
+    }
+
     pub fn outputs(&self) -> &Vec<MessageOutput> {
         &self.outputs
     }
@@ -0,0 +1,57 @@
+use crate::runtime::Block;
+
+/// Snapshot of the counters of a stream port.
+#[derive(Clone, Debug)]
+pub struct PortMetrics {
+    pub name: String,
+    /// Items consumed (inputs) or produced (outputs).
+    pub items: u64,
+    /// Items waiting in the buffer of an input, if the buffer supports it.
+    pub buffered: Option<usize>,
+}
+
+/// Snapshot of the counters of a running block.
+#[derive(Clone, Debug)]
+pub struct BlockMetrics {
+    pub instance_name: String,
+    pub type_name: String,
+    pub work_calls: u64,
+    pub messages_received: u64,
+    pub messages_sent: u64,
+    pub inputs: Vec<PortMetrics>,
+    pub outputs: Vec<PortMetrics>,
+}
+
+impl BlockMetrics {
+    pub(crate) fn new(block: &mut Block, work_calls: u64, messages_received: u64) -> BlockMetrics {
+        let inputs = block
+            .stream_inputs_mut()
+            .iter_mut()
+            .map(|i| PortMetrics {
+                name: i.name().to_string(),
+                items: i.n_consumed(),
+                buffered: i.n_buffered(),
+            })
+            .collect();
+        let outputs = block
+            .stream_outputs()
+            .iter()
+            .map(|o| PortMetrics {
+                name: o.name().to_string(),
+                items: o.n_produced(),
+                buffered: None,
+            })
+            .collect();
+        let messages_sent = block.message_outputs().iter().map(|o| o.n_posted()).sum();
+
+        BlockMetrics {
+            instance_name: block.instance_name().unwrap_or_default().to_string(),
+            type_name: block.type_name().to_string(),
+            work_calls,
+            messages_received,
+            messages_sent,
+            inputs,
+            outputs,
+        }
+    }
+}
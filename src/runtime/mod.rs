@@ -18,8 +18,11 @@ mod logging;
 #[path = "logging_wasm.rs"]
 mod logging;
 
+mod description;
 mod flowgraph;
 mod message_io;
+mod metrics;
+mod registry;
 #[allow(clippy::module_inception)]
 mod runtime;
 pub mod scheduler;
@@ -34,6 +37,11 @@ pub use block::SyncKernel;
 pub use block::WorkIo;
 pub use block_meta::BlockMeta;
 pub use block_meta::BlockMetaBuilder;
+pub use description::BlockDescription;
+pub use description::BufferKind;
+pub use description::FlowgraphDescription;
+pub use description::MessageConnection;
+pub use description::StreamConnection;
 pub use flowgraph::Flowgraph;
 pub use flowgraph::FlowgraphHandle;
 pub use futuresdr_pmt::Pmt;
@@ -41,6 +49,10 @@ pub use message_io::MessageInput;
 pub use message_io::MessageIo;
 pub use message_io::MessageIoBuilder;
 pub use message_io::MessageOutput;
+pub use metrics::BlockMetrics;
+pub use metrics::PortMetrics;
+pub use registry::BlockParams;
+pub use registry::BlockRegistry;
 pub(crate) use runtime::run_block;
 pub use runtime::Runtime;
 pub use runtime::RuntimeBuilder;
@@ -106,4 +118,7 @@ pub enum AsyncMessage {
         data: Pmt,
         tx: oneshot::Sender<Pmt>,
     },
+    Metrics {
+        tx: oneshot::Sender<BlockMetrics>,
+    },
 }
@@ -0,0 +1,105 @@
+use serde::de::DeserializeOwned;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::anyhow::{anyhow, bail, Context, Result};
+use crate::runtime::config::Value;
+use crate::runtime::Block;
+
+type Constructor = Box<dyn Fn(&BlockParams) -> Result<Block> + Send + Sync>;
+
+/// Constructors of blocks, keyed by type name.
+///
+/// Used to instantiate blocks from a
+/// [FlowgraphDescription](crate::runtime::FlowgraphDescription). The built-in
+/// blocks are registered in [blocks::registry](crate::blocks::registry).
+#[derive(Default)]
+pub struct BlockRegistry {
+    constructors: BTreeMap<String, Constructor>,
+}
+
+impl BlockRegistry {
+    pub fn new() -> BlockRegistry {
+        BlockRegistry::default()
+    }
+
+    /// Registers a constructor, replacing any previous one with the same name.
+    pub fn register<F>(&mut self, type_name: &str, constructor: F)
+    where
+        F: Fn(&BlockParams) -> Result<Block> + Send + Sync + 'static,
+    {
+        self.constructors
+            .insert(type_name.to_string(), Box::new(constructor));
+    }
+
+    pub fn contains(&self, type_name: &str) -> bool {
+        self.constructors.contains_key(type_name)
+    }
+
+    /// Registered type names in alphabetical order.
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(|k| k.as_str())
+    }
+
+    /// Instantiates a block, failing on unknown types, invalid parameters, or
+    /// parameters that the constructor did not use.
+    pub fn build(&self, type_name: &str, params: &BlockParams) -> Result<Block> {
+        let constructor = self
+            .constructors
+            .get(type_name)
+            .with_context(|| format!("unknown block type `{}`", type_name))?;
+        let block = constructor(params)?;
+        params.check_unused()?;
+        Ok(block)
+    }
+}
+
+/// Parameters of a block in a flowgraph description.
+#[derive(Debug, Default)]
+pub struct BlockParams {
+    values: HashMap<String, Value>,
+    used: RefCell<HashSet<String>>,
+}
+
+impl BlockParams {
+    pub fn new(values: HashMap<String, Value>) -> BlockParams {
+        BlockParams {
+            values,
+            used: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// Gets an optional parameter, failing if it is set but cannot be deserialized.
+    pub fn get_opt<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>> {
+        match self.values.get(name) {
+            Some(v) => {
+                self.used.borrow_mut().insert(name.to_string());
+                v.clone()
+                    .try_into::<T>()
+                    .map(Some)
+                    .map_err(|e| anyhow!("invalid parameter `{}`: {}", name, e))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Gets a mandatory parameter.
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> Result<T> {
+        self.get_opt(name)?
+            .with_context(|| format!("missing parameter `{}`", name))
+    }
+
+    pub fn get_or<T: DeserializeOwned>(&self, name: &str, default: T) -> Result<T> {
+        Ok(self.get_opt(name)?.unwrap_or(default))
+    }
+
+    fn check_unused(&self) -> Result<()> {
+        let used = self.used.borrow();
+        let mut unused: Vec<&String> = self.values.keys().filter(|k| !used.contains(*k)).collect();
+        if !unused.is_empty() {
+            unused.sort();
+            bail!("unknown parameters {:?}", unused);
+        }
+        Ok(())
+    }
+}
@@ -7,11 +7,17 @@ use futures::future::join_all;
 use futures::future::Either;
 use futures::prelude::*;
 use futures::FutureExt;
+use std::sync::Arc;
 #[cfg(target_arch = "wasm32")]
 type Task<T> = crate::runtime::scheduler::wasm::TaskHandle<T>;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::anyhow::bail;
 use crate::anyhow::{Context, Result};
 use crate::runtime::config;
+use crate::runtime::config::Config;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::runtime::config::SchedulerKind;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::runtime::ctrl_port;
 use crate::runtime::scheduler::Scheduler;
@@ -21,6 +27,7 @@ use crate::runtime::scheduler::SmolScheduler;
 use crate::runtime::scheduler::WasmScheduler;
 use crate::runtime::AsyncMessage;
 use crate::runtime::Block;
+use crate::runtime::BlockMetrics;
 use crate::runtime::Flowgraph;
 use crate::runtime::FlowgraphHandle;
 use crate::runtime::WorkIo;
@@ -30,16 +37,42 @@ use crate::runtime::WorkIo;
 /// [Runtime]s are generic over the scheduler used to run the [Flowgraph].
 pub struct Runtime<S> {
     scheduler: S,
+    config: Arc<Config>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl Runtime<SmolScheduler> {
     /// Constructs a new [Runtime] using [SmolScheduler::default()] for the [Scheduler].
+    ///
+    /// The `scheduler` of the global [Config] is not considered, other schedulers are set up
+    /// through [Runtime::with_scheduler].
     pub fn new() -> Runtime<SmolScheduler> {
-        RuntimeBuilder {
-            scheduler: SmolScheduler::default(),
+        let rt = RuntimeBuilder::new(SmolScheduler::default()).build();
+        if rt.config.scheduler != SchedulerKind::Smol {
+            warn!(
+                "config selects the {:?} scheduler, Runtime::new uses Smol",
+                rt.config.scheduler
+            );
+        }
+        rt
+    }
+
+    /// Constructs a new [Runtime] with an explicit [Config] instead of the global one.
+    ///
+    /// The [SmolScheduler] is set up with the thread settings of the [Config]. Fails for an
+    /// invalid [Config] or if it selects another scheduler, which has to be constructed through
+    /// the [RuntimeBuilder].
+    pub fn with_config(config: Config) -> Result<Runtime<SmolScheduler>> {
+        config.validate()?;
+        if config.scheduler != SchedulerKind::Smol {
+            bail!(
+                "config selects the {:?} scheduler, Runtime::with_config only supports Smol",
+                config.scheduler
+            );
         }
-        .build()
+        Ok(RuntimeBuilder::new(SmolScheduler::from_config(&config))
+            .config(config)
+            .build())
     }
 }
 
@@ -53,10 +86,7 @@ impl Default for Runtime<SmolScheduler> {
 #[cfg(target_arch = "wasm32")]
 impl Runtime<WasmScheduler> {
     pub fn new() -> Runtime<WasmScheduler> {
-        RuntimeBuilder {
-            scheduler: WasmScheduler::default(),
-        }
-        .build()
+        RuntimeBuilder::new(WasmScheduler::default()).build()
     }
 }
 
@@ -70,7 +100,12 @@ impl Default for Runtime<WasmScheduler> {
 impl<S: Scheduler> Runtime<S> {
     /// Create a [Runtime] with a given [Scheduler]
     pub fn with_scheduler(scheduler: S) -> Runtime<S> {
-        RuntimeBuilder { scheduler }.build()
+        RuntimeBuilder::new(scheduler).build()
+    }
+
+    /// The [Config] used for [Flowgraph]s that do not bring their own.
+    pub fn config(&self) -> &Config {
+        &self.config
     }
 
     pub fn spawn<T: Send + 'static>(
@@ -104,12 +139,16 @@ impl<S: Scheduler> Runtime<S> {
     }
 
     pub fn start(&self, fg: Flowgraph) -> (Task<Result<Flowgraph>>, FlowgraphHandle) {
-        let queue_size = config::config().queue_size;
+        let queue_size = match fg.topology.as_ref().and_then(|t| t.config.as_ref()) {
+            Some(c) => c.queue_size,
+            None => self.config.queue_size,
+        };
         let (fg_inbox, fg_inbox_rx) = channel::<AsyncMessage>(queue_size);
 
         let task = self.scheduler.spawn(run_flowgraph(
             fg,
             self.scheduler.clone(),
+            self.config.clone(),
             fg_inbox.clone(),
             fg_inbox_rx,
         ));
@@ -131,13 +170,36 @@ impl<S: Scheduler> Runtime<S> {
 
 pub struct RuntimeBuilder<S> {
     scheduler: S,
+    config: Option<Config>,
 }
 
 impl<S: Scheduler> RuntimeBuilder<S> {
+    pub fn new(scheduler: S) -> RuntimeBuilder<S> {
+        RuntimeBuilder {
+            scheduler,
+            config: None,
+        }
+    }
+
+    /// Use an explicit [Config] instead of the global one. This also sets the log level.
+    #[must_use]
+    pub fn config(mut self, config: Config) -> RuntimeBuilder<S> {
+        self.config = Some(config);
+        self
+    }
+
     pub fn build(self) -> Runtime<S> {
         crate::runtime::init();
+        let config = match self.config {
+            Some(c) => {
+                log::set_max_level(c.log_level);
+                Arc::new(c)
+            }
+            None => Arc::new(config::config().clone()),
+        };
         Runtime {
             scheduler: self.scheduler,
+            config,
         }
     }
 }
@@ -145,6 +207,7 @@ impl<S: Scheduler> RuntimeBuilder<S> {
 async fn run_flowgraph<S: Scheduler>(
     mut fg: Flowgraph,
     scheduler: S,
+    config: Arc<Config>,
     mut main_channel: Sender<AsyncMessage>,
     mut main_rx: Receiver<AsyncMessage>,
 ) -> Result<Flowgraph> {
@@ -152,6 +215,16 @@ async fn run_flowgraph<S: Scheduler>(
     let mut topology = fg.topology.take().context("flowgraph not initialized")?;
     topology.validate()?;
 
+    // flowgraphs without their own config inherit the one of the runtime
+    let inherit_config = topology.config.is_none();
+    if inherit_config {
+        topology.set_config(config);
+    }
+
+    // render before the blocks are handed to the scheduler
+    #[cfg(not(target_arch = "wasm32"))]
+    let dot = topology.to_dot();
+
     let mut inboxes = scheduler.run_topology(&mut topology, &main_channel);
 
     debug!("connect stream io");
@@ -160,7 +233,7 @@ async fn run_flowgraph<S: Scheduler>(
         debug_assert!(!v.is_empty());
 
         let src_inbox = inboxes[*src].as_ref().unwrap().clone();
-        let mut writer = buffer_builder.build(src_inbox, *src_port);
+        let mut writer = buffer_builder.build(topology.config(), src_inbox, *src_port);
 
         for (dst, dst_port) in v.iter() {
             let dst_inbox = inboxes[*dst].as_ref().unwrap().clone();
@@ -250,9 +323,9 @@ async fn run_flowgraph<S: Scheduler>(
             .expect("main inbox exceeded capacity during startup");
     }
 
-    // Start Control Port
+    // register with control port, until the flowgraph finishes or fails
     #[cfg(not(target_arch = "wasm32"))]
-    ctrl_port::start_control_port(inboxes.clone()).await;
+    let registration = ctrl_port::register(inboxes.clone(), dot);
 
     // main loop
     loop {
@@ -289,6 +362,8 @@ async fn run_flowgraph<S: Scheduler>(
             }
             AsyncMessage::BlockDone { id, block } => {
                 *topology.blocks.get_mut(id).unwrap() = Some(block);
+                #[cfg(not(target_arch = "wasm32"))]
+                registration.set_terminating();
 
                 active_blocks -= 1;
             }
@@ -296,6 +371,9 @@ async fn run_flowgraph<S: Scheduler>(
         }
     }
 
+    if inherit_config {
+        topology.config = None;
+    }
     fg.topology = Some(topology);
     Ok(fg)
 }
@@ -312,6 +390,8 @@ pub(crate) async fn run_block(
         finished: false,
         block_on: None,
     };
+    let mut work_calls = 0u64;
+    let mut messages_received = 0u64;
 
     // setup phase
     loop {
@@ -360,6 +440,7 @@ pub(crate) async fn run_block(
                     work_io.finished = true;
                 }
                 Some(Some(AsyncMessage::Call { port_id, data })) => {
+                    messages_received += 1;
                     if block.message_input_is_async(port_id) {
                         block.call_async_handler(port_id, data).await?;
                     } else {
@@ -367,6 +448,7 @@ pub(crate) async fn run_block(
                     }
                 }
                 Some(Some(AsyncMessage::Callback { port_id, data, tx })) => {
+                    messages_received += 1;
                     let res = {
                         if block.message_input_is_async(port_id) {
                             block.call_async_handler(port_id, data).await?
@@ -377,6 +459,11 @@ pub(crate) async fn run_block(
 
                     tx.send(res).unwrap();
                 }
+                Some(Some(AsyncMessage::Metrics { tx })) => {
+                    let _ = tx.send(BlockMetrics::new(&mut block, work_calls, messages_received));
+                    // a scrape does not warrant a call to work
+                    continue;
+                }
                 Some(Some(AsyncMessage::Terminate)) => work_io.finished = true,
                 Some(Some(t)) => warn!("block unhandled message in main loop {:?}", t),
                 _ => break,
@@ -445,6 +532,7 @@ pub(crate) async fn run_block(
 
         // ================== work
         work_io.call_again = false;
+        work_calls += 1;
         match &mut block {
             Block::Sync(b) => b.work(&mut work_io)?,
             Block::Async(b) => b.work(&mut work_io).await?,
@@ -17,6 +17,7 @@ use std::task::{Poll, Waker};
 use std::thread;
 
 use crate::runtime::config;
+use crate::runtime::config::Config;
 use crate::runtime::run_block;
 use crate::runtime::scheduler::Scheduler;
 use crate::runtime::AsyncMessage;
@@ -49,15 +50,26 @@ impl Drop for FlowSchedulerInner {
 
 impl FlowScheduler {
     pub fn new() -> FlowScheduler {
+        Self::from_config(config::config())
+    }
+
+    /// Creates a [FlowScheduler] with the thread settings of the [Config].
+    pub fn from_config(config: &Config) -> FlowScheduler {
         let executor = Arc::new(FlowExecutor::new());
         let mut workers = Vec::new();
 
         let core_ids = core_affinity::get_core_ids().unwrap();
-        debug!("flowsched: core ids {}", core_ids.len());
+        let n_workers = config.scheduler_threads.unwrap_or(core_ids.len());
+        let pin_workers = config.scheduler_pin_threads;
+        debug!(
+            "flowsched: core ids {}, workers {}",
+            core_ids.len(),
+            n_workers
+        );
 
-        let barrier = Arc::new(Barrier::new(core_ids.len() + 1));
+        let barrier = Arc::new(Barrier::new(n_workers + 1));
 
-        for id in core_ids {
+        for id in core_ids.into_iter().cycle().take(n_workers) {
             let b = barrier.clone();
             let e = executor.clone();
             let (sender, receiver) = oneshot::channel::<()>();
@@ -65,8 +77,10 @@ impl FlowScheduler {
             let handle = thread::Builder::new()
                 .name(format!("flow-{}", id.id))
                 .spawn(move || {
-                    debug!("starting executor thread on core id {}", id.id);
-                    // core_affinity::set_for_current(id);
+                    if pin_workers {
+                        debug!("starting executor thread on core id {}", id.id);
+                        core_affinity::set_for_current(id);
+                    }
                     async_io::block_on(e.run(async {
                         b.wait().await;
                         receiver.await
@@ -110,7 +124,7 @@ impl Scheduler for FlowScheduler {
         for _ in 0..=max {
             inboxes.insert(None);
         }
-        let queue_size = config::config().queue_size;
+        let queue_size = topology.config().queue_size;
 
         let n_blocks = topology.blocks.len();
         let n_cores = self.inner.workers.len();
@@ -10,6 +10,7 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 
 use crate::runtime::config;
+use crate::runtime::config::Config;
 use crate::runtime::run_block;
 use crate::runtime::scheduler::Scheduler;
 use crate::runtime::AsyncMessage;
@@ -82,6 +83,14 @@ impl SmolScheduler {
             inner: Arc::new(SmolSchedulerInner { id, workers }),
         }
     }
+
+    /// Creates a [SmolScheduler] with the thread settings of the [Config].
+    pub fn from_config(config: &Config) -> SmolScheduler {
+        let n_executors = config
+            .scheduler_threads
+            .unwrap_or_else(|| core_affinity::get_core_ids().map(|c| c.len()).unwrap_or(1));
+        Self::new(n_executors, config.scheduler_pin_threads)
+    }
 }
 
 impl Scheduler for SmolScheduler {
@@ -95,7 +104,7 @@ impl Scheduler for SmolScheduler {
         for _ in 0..=max {
             inboxes.insert(None);
         }
-        let queue_size = config::config().queue_size;
+        let queue_size = topology.config().queue_size;
 
         // spawn block executors
         for (id, block_o) in topology.blocks.iter_mut() {
@@ -141,8 +150,7 @@ impl Scheduler for SmolScheduler {
 
 impl Default for SmolScheduler {
     fn default() -> Self {
-        let n_executors = core_affinity::get_core_ids().map(|c| c.len()).unwrap_or(1);
-        Self::new(n_executors, false)
+        Self::from_config(config::config())
     }
 }
 
@@ -8,7 +8,6 @@ use std::fmt;
 use std::sync::{Arc, Mutex};
 use std::thread;
 
-use crate::runtime::config;
 use crate::runtime::run_block;
 use crate::runtime::scheduler::Scheduler;
 use crate::runtime::AsyncMessage;
@@ -78,7 +77,7 @@ impl Scheduler for TpbScheduler {
         for _ in 0..=max {
             inboxes.insert(None);
         }
-        let queue_size = config::config().queue_size;
+        let queue_size = topology.config().queue_size;
 
         assert!(topology.blocks.len() < 490); // default upper-limit of thread pool size of unblock crate is 500
 
@@ -7,7 +7,6 @@ use futures_lite::FutureExt;
 use slab::Slab;
 use std::pin::Pin;
 
-use crate::runtime::config;
 use crate::runtime::run_block;
 use crate::runtime::scheduler::Scheduler;
 use crate::runtime::AsyncMessage;
@@ -33,7 +32,7 @@ impl Scheduler for WasmScheduler {
         for _ in 0..=max {
             inboxes.insert(None);
         }
-        let queue_size = config::config().queue_size;
+        let queue_size = topology.config().queue_size;
 
         // spawn block executors
         for (id, block_o) in topology.blocks.iter_mut() {
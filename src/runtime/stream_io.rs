@@ -6,24 +6,44 @@ use crate::runtime::buffer::BufferReader;
 use crate::runtime::buffer::BufferWriter;
 use crate::runtime::AsyncMessage;
 
+/// Stream input port.
+///
+/// Items can be vectors of `vector_len` elements. The buffer then only holds whole vectors,
+/// i.e., slices contain `vector_len` elements per item, and
+/// [consume](StreamInput::consume) counts vectors.
 #[derive(Debug)]
 pub struct StreamInput {
     name: String,
     item_size: usize,
+    vector_len: usize,
     reader: Option<BufferReader>,
+    n_consumed: u64,
 }
 
 impl StreamInput {
     pub fn new(name: &str, item_size: usize) -> StreamInput {
+        Self::new_vector(name, item_size, 1)
+    }
+
+    /// Input with items that are vectors of `vector_len` elements of size `item_size`.
+    pub fn new_vector(name: &str, item_size: usize, vector_len: usize) -> StreamInput {
+        assert!(vector_len > 0, "vector length has to be positive");
         StreamInput {
             name: name.to_string(),
             item_size,
+            vector_len,
             reader: None,
+            n_consumed: 0,
         }
     }
 
+    /// Size of an item in bytes, i.e., of the whole vector.
     pub fn item_size(&self) -> usize {
-        self.item_size
+        self.item_size * self.vector_len
+    }
+
+    pub fn vector_len(&self) -> usize {
+        self.vector_len
     }
 
     pub fn name(&self) -> &str {
@@ -39,6 +59,19 @@ impl StreamInput {
             return;
         }
         self.reader.as_mut().unwrap().consume(amount);
+        self.n_consumed += amount as u64;
+    }
+
+    pub fn n_consumed(&self) -> u64 {
+        self.n_consumed
+    }
+
+    /// Number of items available in the buffer, if it is a host buffer.
+    pub fn n_buffered(&mut self) -> Option<usize> {
+        match self.reader.as_mut() {
+            Some(BufferReader::Host(r)) => Some(r.bytes().1 / self.item_size()),
+            _ => None,
+        }
     }
 
     pub fn slice<T>(&mut self) -> &'static mut [T] {
@@ -71,24 +104,43 @@ impl StreamInput {
     }
 }
 
+/// Stream output port.
+///
+/// Like for the [StreamInput], items can be vectors, in which case
+/// [produce](StreamOutput::produce) counts vectors.
 #[derive(Debug)]
 pub struct StreamOutput {
     name: String,
     item_size: usize,
+    vector_len: usize,
     writer: Option<BufferWriter>,
+    n_produced: u64,
 }
 
 impl StreamOutput {
     pub fn new(name: &str, item_size: usize) -> StreamOutput {
+        Self::new_vector(name, item_size, 1)
+    }
+
+    /// Output with items that are vectors of `vector_len` elements of size `item_size`.
+    pub fn new_vector(name: &str, item_size: usize, vector_len: usize) -> StreamOutput {
+        assert!(vector_len > 0, "vector length has to be positive");
         StreamOutput {
             name: name.to_string(),
             item_size,
+            vector_len,
             writer: None,
+            n_produced: 0,
         }
     }
 
+    /// Size of an item in bytes, i.e., of the whole vector.
     pub fn item_size(&self) -> usize {
-        self.item_size
+        self.item_size * self.vector_len
+    }
+
+    pub fn vector_len(&self) -> usize {
+        self.vector_len
     }
 
     pub fn name(&self) -> &str {
@@ -120,7 +172,12 @@ impl StreamOutput {
         if amount == 0 {
             return;
         }
-        self.writer.as_mut().unwrap().produce(amount)
+        self.writer.as_mut().unwrap().produce(amount);
+        self.n_produced += amount as u64;
+    }
+
+    pub fn n_produced(&self) -> u64 {
+        self.n_produced
     }
 
     pub fn slice<T>(&mut self) -> &'static mut [T] {
@@ -243,6 +300,32 @@ impl StreamIoBuilder {
         self
     }
 
+    /// Input with vectors of `vector_len` elements of size `item_size` as items.
+    #[must_use]
+    pub fn add_vector_input(
+        mut self,
+        name: &str,
+        item_size: usize,
+        vector_len: usize,
+    ) -> StreamIoBuilder {
+        self.inputs
+            .push(StreamInput::new_vector(name, item_size, vector_len));
+        self
+    }
+
+    /// Output with vectors of `vector_len` elements of size `item_size` as items.
+    #[must_use]
+    pub fn add_vector_output(
+        mut self,
+        name: &str,
+        item_size: usize,
+        vector_len: usize,
+    ) -> StreamIoBuilder {
+        self.outputs
+            .push(StreamOutput::new_vector(name, item_size, vector_len));
+        self
+    }
+
     pub fn build(self) -> StreamIo {
         StreamIo::new(self.inputs, self.outputs)
     }
@@ -268,4 +351,14 @@ mod tests {
         assert_eq!(o.name(), "foo");
         assert_eq!(o.item_size(), 4);
     }
+
+    #[test]
+    fn vector_items() {
+        let i = StreamInput::new_vector("foo", 8, 16);
+        assert_eq!(i.item_size(), 128);
+        assert_eq!(i.vector_len(), 16);
+
+        let o = StreamOutput::new("foo", 4);
+        assert_eq!(o.vector_len(), 1);
+    }
 }
@@ -4,13 +4,17 @@ use std::collections::HashMap;
 use crate::anyhow::{bail, Context, Result};
 use crate::runtime::buffer::BufferBuilder;
 use crate::runtime::buffer::BufferWriter;
+use crate::runtime::config;
+use crate::runtime::config::Config;
 use crate::runtime::AsyncMessage;
 use crate::runtime::Block;
 use slab::Slab;
 use std::any::{Any, TypeId};
 use std::cmp::{Eq, PartialEq};
 use std::fmt::Debug;
+use std::fmt::Write;
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 pub trait BufferBuilderKey: Debug + Send + Sync {
     fn eq(&self, other: &dyn BufferBuilderKey) -> bool;
@@ -51,12 +55,24 @@ pub struct BufferBuilderEntry {
 impl BufferBuilderEntry {
     pub(crate) fn build(
         &self,
+        config: &Config,
         writer_inbox: Sender<AsyncMessage>,
         writer_output_id: usize,
     ) -> BufferWriter {
+        self.builder.builder().build_with_config(
+            config,
+            self.item_size,
+            writer_inbox,
+            writer_output_id,
+        )
+    }
+
+    // the buffer that is built for the config, not the builder that was requested
+    fn describe(&self, config: &Config) -> String {
         self.builder
             .builder()
-            .build(self.item_size, writer_inbox, writer_output_id)
+            .describe(config)
+            .unwrap_or_else(|| format!("{:?}", self.builder))
     }
 }
 
@@ -82,6 +98,7 @@ pub struct Topology {
     pub(crate) stream_edges: HashMap<(usize, usize, BufferBuilderEntry), Vec<(usize, usize)>>,
     // src blk, src port, dst blk, dst port
     pub(crate) message_edges: Vec<(usize, usize, usize, usize)>,
+    pub(crate) config: Option<Arc<Config>>,
 }
 
 impl Topology {
@@ -91,9 +108,22 @@ impl Topology {
             blocks: Slab::new(),
             stream_edges: HashMap::new(),
             message_edges: Vec::new(),
+            config: None,
+        }
+    }
+
+    /// The [Config] used to run the [Topology], falling back to the global configuration.
+    pub fn config(&self) -> &Config {
+        match &self.config {
+            Some(c) => c,
+            None => config::config(),
         }
     }
 
+    pub fn set_config(&mut self, config: Arc<Config>) {
+        self.config = Some(config);
+    }
+
     pub fn block_id(&self, name: &str) -> Option<usize> {
         for (i, b) in self.blocks.iter() {
             if b.as_ref()?.instance_name()? == name {
@@ -318,6 +348,150 @@ impl Topology {
     pub fn block_mut(&mut self, id: usize) -> Option<&mut Block> {
         self.blocks.get_mut(id).and_then(|v| v.as_mut())
     }
+
+    /// Renders the [Topology] in the Graphviz DOT language.
+    ///
+    /// Blocks show their stream ports with item sizes and their message ports.
+    /// Stream edges are labeled with the buffer that is built, message edges are dashed.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "digraph flowgraph {{");
+        let _ = writeln!(out, "    rankdir=LR;");
+        let _ = writeln!(out, "    node [shape=record];");
+
+        for (id, b) in self.blocks.iter() {
+            let b = match b {
+                Some(b) => b,
+                None => continue,
+            };
+
+            let mut inputs: Vec<String> = b
+                .stream_inputs()
+                .iter()
+                .enumerate()
+                .map(|(i, p)| format!("<si{}> {} [{}]", i, dot_record(p.name()), p.item_size()))
+                .collect();
+            inputs.extend(
+                b.message_input_names()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, n)| format!("<mi{}> {}", i, dot_record(n))),
+            );
+
+            let mut outputs: Vec<String> = b
+                .stream_outputs()
+                .iter()
+                .enumerate()
+                .map(|(i, p)| format!("<so{}> {} [{}]", i, dot_record(p.name()), p.item_size()))
+                .collect();
+            outputs.extend(
+                b.message_outputs()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| format!("<mo{}> {}", i, dot_record(p.name()))),
+            );
+
+            let _ = writeln!(
+                out,
+                "    b{} [label=\"{{{{{}}}|{}\\n({})|{{{}}}}}\"];",
+                id,
+                inputs.join("|"),
+                dot_record(b.instance_name().unwrap_or_default()),
+                dot_record(b.type_name()),
+                outputs.join("|")
+            );
+        }
+
+        for (src, src_port, dst, dst_port, buffer) in self.sorted_stream_edges() {
+            let _ = writeln!(
+                out,
+                "    b{}:so{} -> b{}:si{} [label=\"{}\"];",
+                src,
+                src_port,
+                dst,
+                dst_port,
+                dot_string(&buffer.describe(self.config()))
+            );
+        }
+
+        for (src, src_port, dst, dst_port) in self.message_edges.iter() {
+            let _ = writeln!(
+                out,
+                "    b{}:mo{} -> b{}:mi{} [style=dashed];",
+                src, src_port, dst, dst_port
+            );
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the [Topology] as Mermaid flowchart.
+    ///
+    /// Mermaid has no ports, so port names and item sizes are part of the edge labels.
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "flowchart LR");
+
+        for (id, b) in self.blocks.iter() {
+            if let Some(b) = b {
+                let _ = writeln!(
+                    out,
+                    "    b{}[\"{}<br/>({})\"]",
+                    id,
+                    mermaid_string(b.instance_name().unwrap_or_default()),
+                    mermaid_string(b.type_name())
+                );
+            }
+        }
+
+        for (src, src_port, dst, dst_port, buffer) in self.sorted_stream_edges() {
+            let output = self.blocks[src].as_ref().unwrap().stream_output(src_port);
+            let input = self.blocks[dst].as_ref().unwrap().stream_input(dst_port);
+            let _ = writeln!(
+                out,
+                "    b{} -->|\"{} -> {} [{}], {}\"| b{}",
+                src,
+                mermaid_string(output.name()),
+                mermaid_string(input.name()),
+                output.item_size(),
+                mermaid_string(&buffer.describe(self.config())),
+                dst
+            );
+        }
+
+        for (src, src_port, dst, dst_port) in self.message_edges.iter() {
+            let output = self.blocks[*src]
+                .as_ref()
+                .unwrap()
+                .message_output(*src_port);
+            let input = &self.blocks[*dst].as_ref().unwrap().message_input_names()[*dst_port];
+            let _ = writeln!(
+                out,
+                "    b{} -.->|\"{} -> {}\"| b{}",
+                src,
+                mermaid_string(output.name()),
+                mermaid_string(input),
+                dst
+            );
+        }
+
+        out
+    }
+
+    // src blk, src port, dst blk, dst port, buffer; sorted to get a stable output
+    fn sorted_stream_edges(&self) -> Vec<(usize, usize, usize, usize, &BufferBuilderEntry)> {
+        let mut edges: Vec<_> = self
+            .stream_edges
+            .iter()
+            .flat_map(|((src, src_port, buffer), v)| {
+                v.iter()
+                    .map(move |(dst, dst_port)| (*src, *src_port, *dst, *dst_port, buffer))
+            })
+            .collect();
+        edges.sort_by_key(|e| (e.0, e.1, e.2, e.3));
+        edges
+    }
 }
 
 impl Default for Topology {
@@ -325,3 +499,23 @@ impl Default for Topology {
         Topology::new()
     }
 }
+
+fn dot_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// record labels also treat braces, bars, and angle brackets as syntax
+fn dot_record(s: &str) -> String {
+    let mut out = String::new();
+    for c in s.chars() {
+        if matches!(c, '\\' | '"' | '{' | '}' | '|' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn mermaid_string(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::AgcBuilder;
+use futuresdr::blocks::FeedForwardAgcBuilder;
+use futuresdr::blocks::HeadBuilder;
+use futuresdr::blocks::SignalSourceBuilder;
+use futuresdr::blocks::ThrottleBuilder;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::Waveform;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+
+mod common;
+use common::run;
+
+fn tone(n: usize, amplitude: f32) -> impl Iterator<Item = Complex32> {
+    (0..n).map(move |i| Complex32::from_polar(amplitude, 0.1 * i as f32))
+}
+
+#[test]
+fn agc_attack_decay() -> Result<()> {
+    let input: Vec<Complex32> = tone(2000, 0.1).chain(tone(2000, 2.0)).collect();
+    let v: Vec<Complex32> = run(
+        input,
+        AgcBuilder::<Complex32>::new()
+            .attack(0.1)
+            .decay(0.1)
+            .build(),
+    )?;
+
+    assert_eq!(v.len(), 4000);
+    // the gain rises slowly to 10, but falls quickly to 0.5
+    assert!(v[1000..2000].iter().all(|x| (x.norm() - 1.0).abs() < 1e-3));
+    assert!(v[2000].norm() > 1.5);
+    assert!(v[2050..].iter().all(|x| (x.norm() - 1.0).abs() < 1e-3));
+
+    Ok(())
+}
+
+#[test]
+fn agc_max_gain() -> Result<()> {
+    let v: Vec<f32> = run(
+        vec![-1e-3f32; 5000],
+        AgcBuilder::<f32>::new()
+            .reference(0.5)
+            .max_gain(100.0)
+            .decay(0.5)
+            .build(),
+    )?;
+    assert!(v.iter().all(|x| *x < 0.0 && *x >= -0.1));
+    assert_eq!(*v.last().unwrap(), -0.1);
+
+    let v: Vec<f32> = run(
+        vec![-1e-1f32; 5000],
+        AgcBuilder::<f32>::new()
+            .reference(0.5)
+            .max_gain(100.0)
+            .decay(0.5)
+            .build(),
+    )?;
+    assert!((v.last().unwrap() + 0.5).abs() < 1e-4);
+
+    Ok(())
+}
+
+#[test]
+fn feed_forward_agc() -> Result<()> {
+    let input: Vec<Complex32> = tone(500, 0.2).chain(tone(500, 3.0)).collect();
+    let v: Vec<Complex32> = run(
+        input.clone(),
+        FeedForwardAgcBuilder::<Complex32>::new(32)
+            .reference(2.0)
+            .build(),
+    )?;
+
+    // all samples pass, each scaled by the peak in its window
+    assert_eq!(v.len(), input.len());
+    for (n, (y, x)) in v.iter().zip(&input).enumerate() {
+        let gain = if n < 500 - 31 { 10.0 } else { 2.0 / 3.0 };
+        assert!((y - x * gain).norm() < 1e-4);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn feed_forward_agc_window() -> Result<()> {
+    // spans several calls to work, so the window has to be kept across them
+    let mut state = 1u32;
+    let input: Vec<f32> = (0..50_000)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1 << 24) as f32 - 0.5
+        })
+        .collect();
+    let n = 100;
+    let v: Vec<f32> = run(input.clone(), FeedForwardAgcBuilder::<f32>::new(n).build())?;
+
+    assert_eq!(v.len(), input.len());
+    for (j, (y, x)) in v.iter().zip(&input).enumerate() {
+        let end = std::cmp::min(j + n, input.len());
+        let peak = input[j..end].iter().map(|x| x.abs()).fold(0.0, f32::max);
+        assert_eq!(*y, x * (1.0 / peak));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn agc_gain_callback() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(
+        SignalSourceBuilder::<Complex32>::new(100_000.0)
+            .waveform(Waveform::ComplexExp)
+            .amplitude(0.25)
+            .build(),
+    );
+    let throttle = fg.add_block(ThrottleBuilder::new(8, 100_000.0).build());
+    let agc = AgcBuilder::<Complex32>::new().decay(0.2).build();
+    let gain = agc.message_input_name_to_id("gain").unwrap();
+    let agc = fg.add_block(agc);
+    let head = fg.add_block(HeadBuilder::new(8, 200_000).build());
+    let snk = fg.add_block(VectorSinkBuilder::<Complex32>::new().build());
+
+    fg.connect_stream(src, "out", throttle, "in")?;
+    fg.connect_stream(throttle, "out", agc, "in")?;
+    fg.connect_stream(agc, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start(fg);
+
+    std::thread::sleep(Duration::from_millis(500));
+    let g = async_io::block_on(handle.callback(agc, gain, Pmt::Null))?;
+    match g {
+        Pmt::Double(g) => assert!((g - 4.0).abs() < 1e-3),
+        _ => panic!("unexpected gain {:?}", g),
+    }
+
+    async_io::block_on(task)?;
+
+    Ok(())
+}
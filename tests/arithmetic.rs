@@ -0,0 +1,152 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::Add;
+use futuresdr::blocks::AddConst;
+use futuresdr::blocks::ComplexToArg;
+use futuresdr::blocks::ComplexToImag;
+use futuresdr::blocks::ComplexToMag;
+use futuresdr::blocks::ComplexToMagSquared;
+use futuresdr::blocks::ComplexToReal;
+use futuresdr::blocks::Conjugate;
+use futuresdr::blocks::Divide;
+use futuresdr::blocks::FloatToComplex;
+use futuresdr::blocks::Multiply;
+use futuresdr::blocks::MultiplyConst;
+use futuresdr::blocks::Subtract;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+mod common;
+use common::run;
+use common::run_inputs;
+
+#[test]
+fn n_inputs() -> Result<()> {
+    let a: Vec<f32> = (0..10000).map(|x| x as f32).collect();
+    let b: Vec<f32> = a.iter().map(|x| x * 2.0).collect();
+    let c: Vec<f32> = vec![1.0; 10000];
+
+    let v: Vec<f32> = run_inputs(vec![a.clone(), b.clone(), c.clone()], Add::<f32>::new(3))?;
+    assert_eq!(v.len(), 10000);
+    for (i, y) in v.iter().enumerate() {
+        assert_eq!(*y, 3.0 * i as f32 + 1.0);
+    }
+
+    let v: Vec<f32> = run_inputs(vec![b, a, c], Subtract::<f32>::new(3))?;
+    for (i, y) in v.iter().enumerate() {
+        assert_eq!(*y, i as f32 - 1.0);
+    }
+
+    // stops with the shortest input
+    let v: Vec<f64> = run_inputs(
+        vec![vec![24.0, 8.0, 1.0], vec![2.0, 4.0], vec![3.0, 2.0, 1.0]],
+        Divide::<f64>::new(3),
+    )?;
+    assert_eq!(v, vec![4.0, 1.0]);
+
+    Ok(())
+}
+
+#[test]
+fn item_types() -> Result<()> {
+    let v: Vec<i16> = run_inputs(
+        vec![
+            vec![30000i16, -30000, 7, i16::MIN],
+            vec![10000, 10000, 0, -1],
+        ],
+        Add::<i16>::new(2),
+    )?;
+    assert_eq!(v, vec![i16::MAX, -20000, 7, i16::MIN]);
+
+    let v: Vec<i16> = run_inputs(
+        vec![vec![300i16, 9, 7, i16::MIN], vec![200, -2, 0, -1]],
+        Divide::<i16>::new(2),
+    )?;
+    assert_eq!(v, vec![1, -4, 0, i16::MAX]);
+
+    let v: Vec<Complex32> = run_inputs(
+        vec![
+            vec![Complex32::new(1.0, 2.0), Complex32::new(0.0, 1.0)],
+            vec![Complex32::new(3.0, -1.0), Complex32::new(0.0, 1.0)],
+        ],
+        Multiply::<Complex32>::new(2),
+    )?;
+    assert_eq!(v, vec![Complex32::new(5.0, 5.0), Complex32::new(-1.0, 0.0)]);
+
+    Ok(())
+}
+
+#[test]
+fn complex_lanes() -> Result<()> {
+    // whole chunks of lanes and a remainder
+    let a: Vec<Complex32> = (0..10001)
+        .map(|i| Complex32::from_polar(1.0, 0.01 * i as f32))
+        .collect();
+    let b: Vec<Complex32> = (0..10001).map(|i| Complex32::new(i as f32, -1.0)).collect();
+
+    let v: Vec<Complex32> = run_inputs(vec![a.clone(), b.clone()], Multiply::<Complex32>::new(2))?;
+    assert_eq!(v.len(), a.len());
+    for ((y, a), b) in v.iter().zip(&a).zip(&b) {
+        assert_eq!(*y, a * b);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn constants() -> Result<()> {
+    let v: Vec<f32> = run(vec![1.0f32, -2.0], AddConst::<f32>::new(0.5))?;
+    assert_eq!(v, vec![1.5, -1.5]);
+
+    let v: Vec<Complex32> = run(
+        vec![Complex32::new(1.0, 1.0)],
+        MultiplyConst::<Complex32>::new(Complex32::new(0.0, 2.0)),
+    )?;
+    assert_eq!(v, vec![Complex32::new(-2.0, 2.0)]);
+
+    let v: Vec<i16> = run(vec![20000i16, -3], MultiplyConst::<i16>::new(2))?;
+    assert_eq!(v, vec![i16::MAX, -6]);
+
+    Ok(())
+}
+
+#[test]
+fn complex() -> Result<()> {
+    let input = vec![Complex32::new(3.0, -4.0), Complex32::new(0.0, 1.0)];
+
+    let v: Vec<Complex32> = run(input.clone(), Conjugate::new())?;
+    assert_eq!(v, vec![Complex32::new(3.0, 4.0), Complex32::new(0.0, -1.0)]);
+    let v: Vec<f32> = run(input.clone(), ComplexToMag::new())?;
+    assert_eq!(v, vec![5.0, 1.0]);
+    let v: Vec<f32> = run(input.clone(), ComplexToMagSquared::new())?;
+    assert_eq!(v, vec![25.0, 1.0]);
+    let v: Vec<f32> = run(input.clone(), ComplexToArg::new())?;
+    assert!((v[0] + 0.9273).abs() < 1e-4);
+    assert!((v[1] - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    let re: Vec<f32> = run(input.clone(), ComplexToReal::new())?;
+    assert_eq!(re, vec![3.0, 0.0]);
+    let im: Vec<f32> = run(input.clone(), ComplexToImag::new())?;
+    assert_eq!(im, vec![-4.0, 1.0]);
+
+    let mut fg = Flowgraph::new();
+    let src_re = fg.add_block(VectorSourceBuilder::<f32>::new(re).build());
+    let src_im = fg.add_block(VectorSourceBuilder::<f32>::new(im).build());
+    let f2c = fg.add_block(FloatToComplex::new());
+    let snk = fg.add_block(VectorSinkBuilder::<Complex32>::new().build());
+    fg.connect_stream(src_re, "out", f2c, "re")?;
+    fg.connect_stream(src_im, "out", f2c, "im")?;
+    fg.connect_stream(f2c, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let v = fg
+        .block_async::<VectorSink<Complex32>>(snk)
+        .unwrap()
+        .items();
+    assert_eq!(v, &input);
+
+    Ok(())
+}
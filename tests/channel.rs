@@ -0,0 +1,193 @@
+use std::f64::consts::PI;
+
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::ChannelModelBuilder;
+use futuresdr::blocks::NoiseSourceBuilder;
+use futuresdr::blocks::NoiseType;
+use futuresdr::num_complex::Complex32;
+
+mod common;
+use common::run;
+use common::run_source;
+
+const FS: f64 = 48000.0;
+
+fn mean_power(v: &[f32]) -> (f64, f64) {
+    let n = v.len() as f64;
+    let mean = v.iter().map(|x| *x as f64).sum::<f64>() / n;
+    let power = v.iter().map(|x| (*x as f64).powi(2)).sum::<f64>() / n;
+    (mean, power)
+}
+
+#[test]
+fn noise_distributions() -> Result<()> {
+    let n = 200_000;
+    for (kind, amp, power) in [
+        (NoiseType::Uniform, 2.0, 4.0 / 3.0),
+        (NoiseType::Gaussian, 0.5, 0.25),
+        (NoiseType::Laplacian, 2.0, 4.0),
+    ] {
+        let v = run_source::<f32>(
+            NoiseSourceBuilder::<f32>::new(kind)
+                .amplitude(amp)
+                .seed(1)
+                .build(),
+            n,
+        )?;
+        assert_eq!(v.len(), n as usize);
+        let (m, p) = mean_power(&v);
+        assert!(m.abs() < 0.02 * amp as f64);
+        assert!((p / power - 1.0).abs() < 0.03);
+
+        let v = run_source::<Complex32>(
+            NoiseSourceBuilder::<Complex32>::new(kind)
+                .amplitude(amp)
+                .seed(1)
+                .build(),
+            n,
+        )?;
+        let p = v.iter().map(|x| x.norm_sqr() as f64).sum::<f64>() / n as f64;
+        let want = if kind == NoiseType::Uniform {
+            2.0 * power
+        } else {
+            power
+        };
+        assert!((p / want - 1.0).abs() < 0.03);
+    }
+
+    let v = run_source::<f32>(
+        NoiseSourceBuilder::<f32>::new(NoiseType::Uniform)
+            .amplitude(2.0)
+            .build(),
+        10_000,
+    )?;
+    assert!(v.iter().all(|x| (-2.0..2.0).contains(x)));
+
+    Ok(())
+}
+
+#[test]
+fn noise_impulse() -> Result<()> {
+    let v = run_source::<Complex32>(
+        NoiseSourceBuilder::<Complex32>::new(NoiseType::Impulse)
+            .amplitude(3.0)
+            .probability(0.05)
+            .seed(2)
+            .build(),
+        100_000,
+    )?;
+    let impulses: Vec<&Complex32> = v.iter().filter(|x| x.norm() > 0.0).collect();
+    assert!((impulses.len() as f64 / 5000.0 - 1.0).abs() < 0.1);
+    assert!(impulses.iter().all(|x| (x.norm() - 3.0).abs() < 1e-5));
+
+    Ok(())
+}
+
+#[test]
+fn noise_seed() -> Result<()> {
+    let gen = |seed| {
+        run_source::<f32>(
+            NoiseSourceBuilder::<f32>::new(NoiseType::Gaussian)
+                .seed(seed)
+                .build(),
+            1000,
+        )
+    };
+    assert_eq!(gen(7)?, gen(7)?);
+    assert_ne!(gen(7)?, gen(8)?);
+
+    Ok(())
+}
+
+#[test]
+fn channel_passthrough() -> Result<()> {
+    let input: Vec<Complex32> = (0..10_000)
+        .map(|i| Complex32::new(i as f32, -(i as f32)))
+        .collect();
+    let v: Vec<Complex32> = run(input.clone(), ChannelModelBuilder::new(FS).build())?;
+    assert_eq!(v, input);
+
+    Ok(())
+}
+
+#[test]
+fn channel_awgn() -> Result<()> {
+    let input = vec![Complex32::new(2.0, 0.0); 100_000];
+    let v: Vec<Complex32> = run(
+        input,
+        ChannelModelBuilder::new(FS)
+            .snr(10.0)
+            .signal_power(4.0)
+            .seed(3)
+            .build(),
+    )?;
+    let p = v
+        .iter()
+        .map(|x| (x - Complex32::new(2.0, 0.0)).norm_sqr() as f64)
+        .sum::<f64>()
+        / v.len() as f64;
+    assert!((p / 0.4 - 1.0).abs() < 0.03);
+
+    Ok(())
+}
+
+#[test]
+fn channel_cfo_multipath() -> Result<()> {
+    let taps = [Complex32::new(1.0, 0.0), Complex32::new(0.0, 0.5)];
+    let mut input = vec![Complex32::new(0.0, 0.0); 100];
+    input[0] = Complex32::new(1.0, 0.0);
+    let v: Vec<Complex32> = run(
+        input.clone(),
+        ChannelModelBuilder::new(FS).taps(&taps).build(),
+    )?;
+    assert_eq!(&v[0..2], &taps);
+    assert!(v[2..].iter().all(|x| x.norm() == 0.0));
+
+    let v: Vec<Complex32> = run(
+        vec![Complex32::new(1.0, 0.0); 1000],
+        ChannelModelBuilder::new(FS).cfo(-1000.0).build(),
+    )?;
+    for (n, y) in v.iter().enumerate() {
+        let want = Complex32::from_polar(1.0, (-2.0 * PI * 1000.0 * n as f64 / FS) as f32);
+        assert!((y - want).norm() < 1e-4);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn channel_sco() -> Result<()> {
+    let f = 100.0;
+    let sco = 1e-3;
+    let input: Vec<Complex32> = (0..20_000)
+        .map(|n| Complex32::from_polar(1.0, (2.0 * PI * f * n as f64 / FS) as f32))
+        .collect();
+    let v: Vec<Complex32> = run(input, ChannelModelBuilder::new(FS).sco(sco).build())?;
+
+    // the receiver takes more samples of the same signal
+    assert!((v.len() as f64 - 20_000.0 * (1.0 + sco)).abs() < 2.0);
+    for (n, y) in v.iter().enumerate() {
+        let t = n as f64 / (1.0 + sco);
+        let want = Complex32::from_polar(1.0, (2.0 * PI * f * t / FS) as f32);
+        assert!((y - want).norm() < 1e-3);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn channel_phase_noise() -> Result<()> {
+    let v: Vec<Complex32> = run(
+        vec![Complex32::new(1.0, 0.0); 10_000],
+        ChannelModelBuilder::new(FS)
+            .phase_noise(0.01)
+            .seed(4)
+            .build(),
+    )?;
+    assert!(v.iter().all(|x| (x.norm() - 1.0).abs() < 1e-5));
+    // random walk with a standard deviation of about one radian after 10k samples
+    let spread = v.iter().map(|x| x.arg().abs()).fold(0.0f32, f32::max);
+    assert!(spread > 0.1);
+
+    Ok(())
+}
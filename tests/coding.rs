@@ -0,0 +1,314 @@
+use futures::FutureExt;
+use std::future::Future;
+use std::pin::Pin;
+
+use futuresdr::anyhow::Result;
+use futuresdr::async_trait::async_trait;
+use futuresdr::blocks::ConvolutionalEncoder;
+use futuresdr::blocks::Deframer;
+use futuresdr::blocks::Framer;
+use futuresdr::blocks::ReedSolomonDecoder;
+use futuresdr::blocks::ReedSolomonEncoder;
+use futuresdr::blocks::ViterbiDecoder;
+use futuresdr::blocks::Whitener;
+use futuresdr::dsp::ConvolutionalCode;
+use futuresdr::dsp::Crc;
+use futuresdr::dsp::Lfsr;
+use futuresdr::dsp::ReedSolomon;
+use futuresdr::runtime::AsyncKernel;
+use futuresdr::runtime::Block;
+use futuresdr::runtime::BlockMeta;
+use futuresdr::runtime::BlockMetaBuilder;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::MessageIo;
+use futuresdr::runtime::MessageIoBuilder;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+use futuresdr::runtime::StreamIo;
+use futuresdr::runtime::StreamIoBuilder;
+use futuresdr::runtime::WorkIo;
+
+// posts the messages and finishes
+struct Messages {
+    messages: Vec<Pmt>,
+}
+
+impl Messages {
+    fn block(messages: Vec<Pmt>) -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("Messages").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new().add_output("out").build(),
+            Messages { messages },
+        )
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for Messages {
+    async fn work(
+        &mut self,
+        io: &mut WorkIo,
+        _sio: &mut StreamIo,
+        mio: &mut MessageIo<Self>,
+        _meta: &mut BlockMeta,
+    ) -> Result<()> {
+        for m in self.messages.drain(..) {
+            mio.post(0, m).await;
+        }
+        io.finished = true;
+        Ok(())
+    }
+}
+
+// applies a function to each blob, e.g., to add errors
+struct MapBlob {
+    f: Box<dyn FnMut(Vec<u8>) -> Vec<u8> + Send>,
+}
+
+impl MapBlob {
+    fn block(f: impl FnMut(Vec<u8>) -> Vec<u8> + Send + 'static) -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("MapBlob").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_output("out")
+                .add_async_input("in", Self::handler)
+                .build(),
+            MapBlob { f: Box::new(f) },
+        )
+    }
+
+    fn handler<'a>(
+        &'a mut self,
+        mio: &'a mut MessageIo<Self>,
+        _meta: &'a mut BlockMeta,
+        p: Pmt,
+    ) -> Pin<Box<dyn Future<Output = Result<Pmt>> + Send + 'a>> {
+        async move {
+            if let Pmt::Blob(data) = p {
+                mio.post(0, Pmt::Blob((self.f)(data))).await;
+            }
+            Ok(Pmt::Null)
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for MapBlob {}
+
+// stores received messages
+struct Collector {
+    messages: Vec<Pmt>,
+}
+
+impl Collector {
+    fn block() -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("Collector").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_sync_input(
+                    "in",
+                    |block: &mut Collector,
+                     _mio: &mut MessageIo<Collector>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        block.messages.push(p);
+                        Ok(Pmt::Null)
+                    },
+                )
+                .build(),
+            Collector {
+                messages: Vec::new(),
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for Collector {}
+
+fn lcg(n: usize, seed: u32) -> Vec<u8> {
+    let mut x = seed;
+    (0..n)
+        .map(|_| {
+            x = x.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (x >> 16) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn crc() {
+    assert_eq!(Crc::Crc16.checksum(b"123456789"), 0x29b1);
+    assert_eq!(Crc::Crc32.checksum(b"123456789"), 0xcbf4_3926);
+
+    for crc in [Crc::Crc16, Crc::Crc32] {
+        let mut data = b"hello world".to_vec();
+        crc.append(&mut data);
+        assert_eq!(data.len(), 11 + crc.size());
+        assert!(crc.check(&data));
+        data[3] ^= 0x10;
+        assert!(!crc.check(&data));
+    }
+}
+
+#[test]
+fn pn9() {
+    let mut lfsr = Lfsr::pn9();
+    let bytes: Vec<u8> = (0..4).map(|_| lfsr.next_byte()).collect();
+    assert_eq!(bytes, vec![0xff, 0xe1, 0x1d, 0x9a]);
+
+    // period of 511 bits
+    let mut lfsr = Lfsr::pn9();
+    let bits: Vec<u8> = (0..1022).map(|_| lfsr.next_bit()).collect();
+    assert_eq!(&bits[0..511], &bits[511..1022]);
+
+    let data = lcg(100, 1);
+    let mut whitened = data.clone();
+    lfsr.reset();
+    lfsr.apply(&mut whitened);
+    assert_ne!(whitened, data);
+    lfsr.reset();
+    lfsr.apply(&mut whitened);
+    assert_eq!(whitened, data);
+}
+
+#[test]
+fn viterbi() {
+    let code = ConvolutionalCode::k7();
+    let bits: Vec<u8> = lcg(200, 2).iter().map(|x| x & 1).collect();
+    let mut coded = code.encode(&bits);
+    assert_eq!(coded.len(), code.coded_len(200));
+    assert_eq!(code.decode(&coded), bits);
+
+    // spread bit errors
+    for i in (5..coded.len()).step_by(23) {
+        coded[i] ^= 1;
+    }
+    assert_eq!(code.decode(&coded), bits);
+}
+
+#[test]
+fn reed_solomon() {
+    let rs = ReedSolomon::new(16);
+    let data = lcg(100, 3);
+    let codeword = rs.encode(&data);
+    assert_eq!(codeword.len(), 116);
+    assert_eq!(&codeword[..100], &data[..]);
+
+    let mut c = codeword.clone();
+    assert_eq!(rs.decode(&mut c), Some(0));
+
+    for (i, k) in [0, 7, 30, 55, 80, 99, 105, 115].iter().enumerate() {
+        c[*k] ^= i as u8 + 1;
+    }
+    assert_eq!(rs.decode(&mut c), Some(8));
+    assert_eq!(c, codeword);
+
+    for k in [1, 10, 20, 30, 40, 50, 60, 70, 110] {
+        c[k] ^= 0x55;
+    }
+    assert_eq!(rs.decode(&mut c), None);
+
+    // full-length code with a different field and first root
+    let rs = ReedSolomon::with_params(0x187, 112, 32);
+    let data = lcg(223, 4);
+    let codeword = rs.encode(&data);
+    let mut c = codeword.clone();
+    for k in (0..255).step_by(16) {
+        c[k] ^= 0xa5;
+    }
+    assert_eq!(rs.decode(&mut c), Some(16));
+    assert_eq!(c, codeword);
+}
+
+#[test]
+fn packet_chain() -> Result<()> {
+    let payloads: Vec<Vec<u8>> = vec![lcg(10, 5), lcg(300, 6), lcg(1, 7), lcg(64, 8)];
+    let messages = payloads.iter().map(|p| Pmt::Blob(p.clone())).collect();
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(Messages::block(messages));
+    let framer = fg.add_block(Framer::new(Some(Crc::Crc32)));
+    let rs_enc = fg.add_block(ReedSolomonEncoder::new(16));
+    let whitener = fg.add_block(Whitener::new(Lfsr::pn9()));
+    let conv_enc = fg.add_block(ConvolutionalEncoder::new(ConvolutionalCode::k7()));
+    // bit errors for all frames, the third frame is destroyed
+    let mut n = 0;
+    let channel = fg.add_block(MapBlob::block(move |mut data| {
+        n += 1;
+        for i in (3..data.len()).step_by(17) {
+            data[i] ^= 0x40;
+        }
+        if n == 3 {
+            for b in data.iter_mut().skip(2) {
+                *b = !*b;
+            }
+        }
+        data
+    }));
+    let viterbi = fg.add_block(ViterbiDecoder::new(ConvolutionalCode::k7()));
+    let dewhitener = fg.add_block(Whitener::new(Lfsr::pn9()));
+    let rs_dec = fg.add_block(ReedSolomonDecoder::new(16));
+    let deframer = fg.add_block(Deframer::new(Some(Crc::Crc32)));
+    let collector = fg.add_block(Collector::block());
+
+    fg.connect_message(src, "out", framer, "in")?;
+    fg.connect_message(framer, "out", rs_enc, "in")?;
+    fg.connect_message(rs_enc, "out", whitener, "in")?;
+    fg.connect_message(whitener, "out", conv_enc, "in")?;
+    fg.connect_message(conv_enc, "out", channel, "in")?;
+    fg.connect_message(channel, "out", viterbi, "in")?;
+    fg.connect_message(viterbi, "out", dewhitener, "in")?;
+    fg.connect_message(dewhitener, "out", rs_dec, "in")?;
+    fg.connect_message(rs_dec, "out", deframer, "in")?;
+    fg.connect_message(deframer, "out", collector, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let received = &fg.block_async::<Collector>(collector).unwrap().messages;
+    let expected: Vec<Pmt> = [0, 1, 3]
+        .iter()
+        .map(|i| Pmt::Blob(payloads[*i].clone()))
+        .collect();
+    assert_eq!(received, &expected);
+
+    Ok(())
+}
+
+#[test]
+fn deframer_drops_invalid_frames() -> Result<()> {
+    let mut good = vec![0, 3, 1, 2, 3];
+    Crc::Crc16.append(&mut good);
+    let mut bad_crc = good.clone();
+    bad_crc[3] ^= 1;
+    let mut padded = good.clone();
+    padded.extend_from_slice(&[0, 0, 0]);
+    let messages = vec![
+        Pmt::Blob(good),
+        Pmt::Blob(bad_crc),
+        Pmt::Blob(vec![0, 200, 1, 2, 3]),
+        Pmt::Blob(padded),
+        Pmt::U32(1),
+    ];
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(Messages::block(messages));
+    let deframer = fg.add_block(Deframer::new(Some(Crc::Crc16)));
+    let collector = fg.add_block(Collector::block());
+    fg.connect_message(src, "out", deframer, "in")?;
+    fg.connect_message(deframer, "out", collector, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let received = &fg.block_async::<Collector>(collector).unwrap().messages;
+    assert_eq!(
+        received,
+        &vec![Pmt::Blob(vec![1, 2, 3]), Pmt::Blob(vec![1, 2, 3])]
+    );
+
+    Ok(())
+}
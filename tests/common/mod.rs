@@ -0,0 +1,87 @@
+// Flowgraphs shared by the integration tests, not every test uses all of them.
+#![allow(dead_code)]
+
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::HeadBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::runtime::Block;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+/// Runs `input` through `block` and returns the output.
+pub fn run<A, B>(input: Vec<A>, block: Block) -> Result<Vec<B>>
+where
+    A: Clone + std::fmt::Debug + Send + Sync + 'static,
+    B: Clone + std::fmt::Debug + Send + Sync + 'static,
+{
+    run_chain(input, vec![block])
+}
+
+/// Runs `input` through the `blocks`, connected one after the other.
+pub fn run_chain<A, B>(input: Vec<A>, blocks: Vec<Block>) -> Result<Vec<B>>
+where
+    A: Clone + std::fmt::Debug + Send + Sync + 'static,
+    B: Clone + std::fmt::Debug + Send + Sync + 'static,
+{
+    let mut fg = Flowgraph::new();
+
+    let mut prev = fg.add_block(VectorSourceBuilder::<A>::new(input).build());
+    for b in blocks {
+        let b = fg.add_block(b);
+        fg.connect_stream(prev, "out", b, "in")?;
+        prev = b;
+    }
+    let snk = fg.add_block(VectorSinkBuilder::<B>::new().build());
+    fg.connect_stream(prev, "out", snk, "in")?;
+
+    items(Runtime::new().run(fg)?, snk)
+}
+
+/// Runs the `inputs` through `block`, connected to the ports `in0`, `in1`, ...
+pub fn run_inputs<A, B>(inputs: Vec<Vec<A>>, block: Block) -> Result<Vec<B>>
+where
+    A: Clone + std::fmt::Debug + Send + Sync + 'static,
+    B: Clone + std::fmt::Debug + Send + Sync + 'static,
+{
+    let mut fg = Flowgraph::new();
+
+    let block = fg.add_block(block);
+    for (k, input) in inputs.into_iter().enumerate() {
+        let src = fg.add_block(VectorSourceBuilder::<A>::new(input).build());
+        fg.connect_stream(src, "out", block, &format!("in{}", k))?;
+    }
+    let snk = fg.add_block(VectorSinkBuilder::<B>::new().build());
+    fg.connect_stream(block, "out", snk, "in")?;
+
+    items(Runtime::new().run(fg)?, snk)
+}
+
+/// Returns the first `n_items` of the source `src`.
+pub fn run_source<A>(src: Block, n_items: u64) -> Result<Vec<A>>
+where
+    A: Clone + std::fmt::Debug + Send + Sync + 'static,
+{
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(src);
+    let head = fg.add_block(HeadBuilder::new(std::mem::size_of::<A>(), n_items).build());
+    let snk = fg.add_block(VectorSinkBuilder::<A>::new().build());
+
+    fg.connect_stream(src, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+
+    items(Runtime::new().run(fg)?, snk)
+}
+
+fn items<B>(fg: Flowgraph, snk: usize) -> Result<Vec<B>>
+where
+    B: Clone + std::fmt::Debug + Send + Sync + 'static,
+{
+    Ok(fg
+        .block_async::<VectorSink<B>>(snk)
+        .unwrap()
+        .items()
+        .clone())
+}
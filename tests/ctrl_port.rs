@@ -0,0 +1,69 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::HeadBuilder;
+use futuresdr::blocks::NullSinkBuilder;
+use futuresdr::blocks::NullSourceBuilder;
+use futuresdr::blocks::ThrottleBuilder;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+const BIND: &str = "127.0.0.1:26127";
+
+fn flowgraphs() -> Option<String> {
+    let mut stream = TcpStream::connect(BIND).ok()?;
+    stream
+        .write_all(b"GET /api/fg/ HTTP/1.0\r\nHost: localhost\r\n\r\n")
+        .ok()?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+    Some(response.rsplit("\r\n\r\n").next()?.to_string())
+}
+
+fn wait_for(ids: &str) -> bool {
+    for _ in 0..200 {
+        if flowgraphs().as_deref() == Some(ids) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
+
+fn throttled(n_items: u64) -> Result<Flowgraph> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(NullSourceBuilder::new(4).build());
+    let throttle = fg.add_block(ThrottleBuilder::new(4, 100_000.0).build());
+    let head = fg.add_block(HeadBuilder::new(4, n_items).build());
+    let snk = fg.add_block(NullSinkBuilder::new(4).build());
+
+    fg.connect_stream(src, "out", throttle, "in")?;
+    fg.connect_stream(throttle, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+
+    Ok(fg)
+}
+
+#[test]
+fn concurrent_flowgraphs() -> Result<()> {
+    std::env::set_var("FUTURESDR_CTRLPORT_ENABLE", "true");
+    std::env::set_var("FUTURESDR_CTRLPORT_BIND", BIND);
+
+    let rt = Runtime::new();
+
+    let (short, _) = rt.start(throttled(20_000)?);
+    assert!(wait_for("[0]"), "first flowgraph not listed");
+    let (long, _) = rt.start(throttled(100_000)?);
+    assert!(wait_for("[0,1]"), "both flowgraphs not listed");
+
+    async_io::block_on(short)?;
+    assert_eq!(flowgraphs().as_deref(), Some("[1]"));
+
+    async_io::block_on(long)?;
+    assert_eq!(flowgraphs().as_deref(), Some("[]"));
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use std::f64::consts::PI;
+
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::AmDemod;
+use futuresdr::blocks::FmDeemphasis;
+use futuresdr::blocks::QuadratureDemod;
+use futuresdr::blocks::Sideband;
+use futuresdr::blocks::SsbDemodBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::blocks::WavSinkBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+mod common;
+use common::run;
+
+const FS: f64 = 48000.0;
+
+fn tone(freq: f64, n: usize) -> Vec<Complex32> {
+    (0..n)
+        .map(|i| Complex32::from_polar(1.0, (2.0 * PI * freq * i as f64 / FS) as f32))
+        .collect()
+}
+
+fn peak(v: &[f32]) -> f32 {
+    v.iter().fold(0.0f32, |a, x| a.max(x.abs()))
+}
+
+#[test]
+fn fm_demod() -> Result<()> {
+    let deviation = 5000.0;
+    let message: Vec<f64> = (0..10_000)
+        .map(|n| (2.0 * PI * 1000.0 * n as f64 / FS).sin())
+        .collect();
+
+    let mut phase = 0.0;
+    let input: Vec<Complex32> = message
+        .iter()
+        .map(|m| {
+            phase += 2.0 * PI * deviation * m / FS;
+            Complex32::from_polar(1.0, phase as f32)
+        })
+        .collect();
+
+    let v: Vec<f32> = run(input, QuadratureDemod::fm(deviation, FS))?;
+    assert_eq!(v.len(), message.len());
+    for (y, m) in v.iter().zip(&message) {
+        assert!((*y as f64 - m).abs() < 1e-3);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn fm_deemphasis() -> Result<()> {
+    let tau = 75e-6;
+    let gain = |freq: f64| -> Result<f32> {
+        let input: Vec<f32> = tone(freq, 20_000).iter().map(|x| x.re).collect();
+        let v: Vec<f32> = run(input, FmDeemphasis::new(FS, tau))?;
+        Ok(peak(&v[10_000..]))
+    };
+
+    assert!((gain(0.0)? - 1.0).abs() < 1e-3);
+    // corner frequency
+    assert!((gain(1.0 / (2.0 * PI * tau))? - 0.5f32.sqrt()).abs() < 1e-2);
+    assert!(gain(15000.0)? < 0.16);
+
+    Ok(())
+}
+
+#[test]
+fn am_demod() -> Result<()> {
+    let input: Vec<Complex32> = tone(5000.0, 1000)
+        .iter()
+        .enumerate()
+        .map(|(n, x)| x * (1.0 + 0.5 * (0.01 * n as f32).sin()))
+        .collect();
+    let v: Vec<f32> = run(input, AmDemod::new())?;
+    for (n, y) in v.iter().enumerate() {
+        assert!((y - (1.0 + 0.5 * (0.01 * n as f32).sin())).abs() < 1e-5);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn ssb_demod() -> Result<()> {
+    for (sideband, sign) in [(Sideband::Upper, 1.0), (Sideband::Lower, -1.0)] {
+        let demod = || SsbDemodBuilder::new(FS).sideband(sideband).build();
+
+        let v: Vec<f32> = run(tone(sign * 1000.0, 20_000), demod())?;
+        assert!((peak(&v[1000..]) - 1.0).abs() < 1e-2);
+
+        // the other sideband is suppressed
+        let v: Vec<f32> = run(tone(-sign * 1000.0, 20_000), demod())?;
+        assert!(peak(&v[1000..]) < 1e-2);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn wav_sink() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("futuresdr-wav-{}.wav", std::process::id()));
+    let file = path.to_str().unwrap();
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSourceBuilder::<f32>::new(vec![0.0, 0.5, -1.0, 2.0]).build());
+    let snk = fg.add_block(WavSinkBuilder::new(file, 8000).channels(2).build());
+    fg.connect_stream(src, "out", snk, "in")?;
+    Runtime::new().run(fg)?;
+
+    let wav = std::fs::read(&path)?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(wav.len(), 44 + 8);
+    assert_eq!(&wav[0..4], b"RIFF");
+    assert_eq!(u32::from_le_bytes(wav[4..8].try_into()?), 36 + 8);
+    assert_eq!(u16::from_le_bytes(wav[22..24].try_into()?), 2);
+    assert_eq!(u32::from_le_bytes(wav[24..28].try_into()?), 8000);
+    assert_eq!(u32::from_le_bytes(wav[40..44].try_into()?), 8);
+    let samples: Vec<i16> = wav[44..]
+        .chunks(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    assert_eq!(samples, vec![0, 16384, -32767, 32767]);
+
+    Ok(())
+}
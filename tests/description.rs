@@ -0,0 +1,152 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::runtime::config::FileFormat;
+use futuresdr::runtime::config::SchedulerKind;
+use futuresdr::runtime::FlowgraphDescription;
+use futuresdr::runtime::Runtime;
+
+const TOML: &str = r#"
+[[blocks]]
+id = "src"
+type = "VectorSource"
+
+[[blocks]]
+id = "copy"
+type = "Copy"
+params = { item = "u32" }
+
+[[blocks]]
+id = "head"
+type = "Head"
+params = { item_size = 4, n_items = 3 }
+
+[[blocks]]
+id = "snk"
+type = "VectorSink"
+
+[[stream]]
+src = "src.out"
+dst = "copy.in"
+
+[[stream]]
+src = "copy.out"
+dst = "head.in"
+buffer = "slab"
+buffer_size = 4096
+
+[[stream]]
+src = "head.out"
+dst = "snk.in"
+"#;
+
+#[test]
+fn description_toml() -> Result<()> {
+    let mut registry = blocks::registry();
+    registry.register("VectorSource", |_| {
+        Ok(VectorSourceBuilder::<u32>::new(vec![1, 2, 3, 4, 5]).build())
+    });
+    registry.register(
+        "VectorSink",
+        |_| Ok(VectorSinkBuilder::<u32>::new().build()),
+    );
+
+    let desc = FlowgraphDescription::from_str(TOML, FileFormat::Toml)?;
+    let fg = desc.build(&registry)?;
+    let fg = Runtime::new().run(fg)?;
+
+    let snk = fg.block_async::<VectorSink<u32>>(3).unwrap();
+    assert_eq!(snk.items(), &vec![1, 2, 3]);
+
+    Ok(())
+}
+
+#[test]
+fn description_json_yaml() -> Result<()> {
+    let registry = blocks::registry();
+
+    let json = r#"{
+        "config": { "queue_size": 64 },
+        "blocks": [
+            { "id": "src", "type": "MessageBurst", "params": { "message": { "U32": 7 }, "n_messages": 10 } },
+            { "id": "snk", "type": "MessageSink" }
+        ],
+        "message": [ { "src": "src.out", "dst": "snk.in" } ]
+    }"#;
+    let fg = FlowgraphDescription::from_str(json, FileFormat::Json)?.build(&registry)?;
+    Runtime::new().run(fg)?;
+
+    let yaml = r#"
+blocks:
+  - id: src
+    type: NullSource
+    params: { item: c32 }
+  - id: head
+    type: Head
+    params: { item: c32, n_items: 1000 }
+  - id: snk
+    type: NullSink
+    params: { item: c32 }
+stream:
+  - { src: src.out, dst: head.in }
+  - { src: head.out, dst: snk.in }
+"#;
+    let fg = FlowgraphDescription::from_str(yaml, FileFormat::Yaml)?.build(&registry)?;
+    Runtime::new().run(fg)?;
+
+    Ok(())
+}
+
+#[test]
+fn description_config() -> Result<()> {
+    let toml =
+        "[config]\nscheduler = \"flow\"\nscheduler_threads = 2\nscheduler_pin_threads = true\n";
+    let c = FlowgraphDescription::from_str(toml, FileFormat::Toml)?.config()?;
+    assert_eq!(c.scheduler, SchedulerKind::Flow);
+    assert_eq!(c.scheduler_threads, Some(2));
+    assert!(c.scheduler_pin_threads);
+
+    let c = FlowgraphDescription::from_str("", FileFormat::Toml)?.config()?;
+    assert_eq!(c.scheduler_threads, None);
+
+    let toml = "[config]\nscheduler_threads = 0\n";
+    assert!(FlowgraphDescription::from_str(toml, FileFormat::Toml)?
+        .config()
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn description_invalid() -> Result<()> {
+    let registry = blocks::registry();
+    let build = |s: &str| {
+        FlowgraphDescription::from_str(s, FileFormat::Toml)
+            .and_then(|d| d.build(&registry))
+            .map(|_| ())
+            .unwrap_err()
+    };
+
+    let e = build("[[blocks]]\nid = \"a\"\ntype = \"Foo\"\n");
+    assert!(format!("{:#}", e).contains("unknown block type `Foo`"));
+
+    let e = build(
+        "[[blocks]]\nid = \"a\"\ntype = \"NullSink\"\nparams = { item_size = 4, rate = 2 }\n",
+    );
+    assert!(format!("{:#}", e).contains("rate"));
+
+    let e = build("[[blocks]]\nid = \"a\"\ntype = \"Head\"\nparams = { item_size = 4 }\n");
+    assert!(format!("{:#}", e).contains("missing parameter `n_items`"));
+
+    let e = build(
+        "[[blocks]]\nid = \"a\"\ntype = \"SymbolSync\"\nparams = { sps = 4.0, phases = 0 }\n",
+    );
+    assert!(format!("{:#}", e).contains("filter phase"));
+
+    let e = build("[[blocks]]\nid = \"a\"\ntype = \"NullSink\"\nparams = { item_size = 4 }\n");
+    assert!(format!("{:#}", e).contains("stream input port"));
+
+    Ok(())
+}
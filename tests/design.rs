@@ -0,0 +1,146 @@
+use futuresdr::dsp::design;
+use futuresdr::dsp::Window;
+
+const FS: f64 = 48000.0;
+
+fn gain(taps: &[f32], f: f64) -> f64 {
+    design::frequency_response(taps, f, FS).norm()
+}
+
+fn db(taps: &[f32], f: f64) -> f64 {
+    20.0 * gain(taps, f).log10()
+}
+
+#[test]
+fn lowpass_reference() {
+    // ideal taps 1/pi, 1/2, 1/pi, normalized to unity gain
+    let taps = design::lowpass(3, FS / 4.0, FS, Window::Rectangular);
+    assert!((taps[0] - 0.280_05).abs() < 1e-5);
+    assert!((taps[1] - 0.439_90).abs() < 1e-5);
+    assert!((taps[2] - 0.280_05).abs() < 1e-5);
+}
+
+#[test]
+fn windowed_sinc() {
+    let taps = design::lowpass(101, 6000.0, FS, Window::Hamming);
+    assert!((gain(&taps, 0.0) - 1.0).abs() < 1e-6);
+    assert!((gain(&taps, 3000.0) - 1.0).abs() < 1e-2);
+    assert!(db(&taps, 9000.0) < -45.0);
+    assert!(db(&taps, 20000.0) < -45.0);
+
+    let taps = design::highpass(101, 6000.0, FS, Window::Hamming);
+    assert!((gain(&taps, FS / 2.0) - 1.0).abs() < 1e-6);
+    assert!((gain(&taps, 12000.0) - 1.0).abs() < 1e-2);
+    assert!(gain(&taps, 0.0) < 1e-6);
+    assert!(db(&taps, 3000.0) < -45.0);
+
+    let taps = design::bandpass(101, 6000.0, 12000.0, FS, Window::Hamming);
+    assert!((gain(&taps, 9000.0) - 1.0).abs() < 1e-6);
+    assert!(db(&taps, 0.0) < -45.0);
+    assert!(db(&taps, 18000.0) < -45.0);
+
+    let taps = design::bandstop(101, 6000.0, 12000.0, FS, Window::Hamming);
+    assert!((gain(&taps, 0.0) - 1.0).abs() < 1e-6);
+    assert!((gain(&taps, 20000.0) - 1.0).abs() < 1e-2);
+    assert!(gain(&taps, 9000.0) < 1e-5);
+}
+
+#[test]
+fn kaiser() {
+    let (n, window) = design::kaiser_order(60.0, 1000.0, FS);
+    assert_eq!(n, 175);
+    match window {
+        Window::Kaiser(beta) => assert!((beta - 5.653).abs() < 1e-3),
+        _ => panic!("expected a Kaiser window"),
+    }
+
+    let taps = design::lowpass(n, 6000.0, FS, window);
+    for f in (0..5500).step_by(100) {
+        assert!((gain(&taps, f as f64) - 1.0).abs() < 2e-3);
+    }
+    for f in (6500..24000).step_by(100) {
+        assert!(db(&taps, f as f64) < -59.0);
+    }
+}
+
+#[test]
+fn root_raised_cosine() {
+    let sps = 4;
+    let h = design::root_raised_cosine(16 * sps + 1, sps as f64, 0.35);
+    assert_eq!(h.len(), 65);
+
+    // matched filter output is a raised cosine without intersymbol interference
+    let mut rc = vec![0.0f32; 2 * h.len() - 1];
+    for (i, a) in h.iter().enumerate() {
+        for (j, b) in h.iter().enumerate() {
+            rc[i + j] += a * b;
+        }
+    }
+    let center = h.len() - 1;
+    assert!((rc[center] - 1.0).abs() < 1e-5);
+    for k in 1..8 {
+        assert!(rc[center + k * sps].abs() < 1e-2);
+        assert!(rc[center - k * sps].abs() < 1e-2);
+    }
+}
+
+#[test]
+fn gaussian() {
+    let sps = 8;
+    let h = design::gaussian(4 * sps + 1, sps as f64, 0.5);
+    let sum: f32 = h.iter().sum();
+    assert!((sum - 1.0).abs() < 1e-5);
+
+    // exp(-2 pi^2 BT^2 / ln(2)) one symbol from the peak
+    let m = h.len() / 2;
+    assert!((h[m + sps] / h[m] - 8.1e-4).abs() < 1e-5);
+    assert!((h[m - sps] - h[m + sps]).abs() < 1e-9);
+}
+
+#[test]
+fn parks_mcclellan() {
+    let fs = 1.0;
+    let taps =
+        design::parks_mcclellan(61, &[(0.0, 0.1), (0.15, 0.5)], &[1.0, 0.0], &[1.0, 1.0], fs)
+            .unwrap();
+
+    let freqs = |a: f64, b: f64| (0..=100).map(move |i| a + (b - a) * i as f64 / 100.0);
+    let pass = freqs(0.0, 0.1)
+        .map(|f| (design::frequency_response(&taps, f, fs).norm() - 1.0).abs())
+        .fold(0.0, f64::max);
+    let stop = freqs(0.15, 0.5)
+        .map(|f| design::frequency_response(&taps, f, fs).norm())
+        .fold(0.0, f64::max);
+
+    // equal ripple in both bands, close to the Kaiser estimate of ~51 dB
+    assert!(pass < 5e-3);
+    assert!((pass - stop).abs() < 0.05 * pass);
+
+    // weighted bandpass with an even number of taps
+    let taps = design::parks_mcclellan(
+        32,
+        &[(0.0, 0.1), (0.2, 0.35), (0.425, 0.5)],
+        &[0.0, 1.0, 0.0],
+        &[10.0, 1.0, 10.0],
+        fs,
+    )
+    .unwrap();
+    assert_eq!(taps.len(), 32);
+    for (a, b) in taps.iter().zip(taps.iter().rev()) {
+        assert!((a - b).abs() < 1e-6);
+    }
+    let pass = freqs(0.2, 0.35)
+        .map(|f| (design::frequency_response(&taps, f, fs).norm() - 1.0).abs())
+        .fold(0.0, f64::max);
+    let stop = freqs(0.0, 0.1)
+        .chain(freqs(0.425, 0.5))
+        .map(|f| design::frequency_response(&taps, f, fs).norm())
+        .fold(0.0, f64::max);
+    assert!((pass - 10.0 * stop).abs() < 0.05 * pass);
+
+    assert!(
+        design::parks_mcclellan(31, &[(0.0, 0.2), (0.1, 0.5)], &[1.0, 0.0], &[1.0, 1.0], fs)
+            .is_err()
+    );
+    assert!(design::parks_mcclellan(31, &[(0.0, 0.2)], &[1.0, 0.0], &[1.0], fs).is_err());
+}
@@ -0,0 +1,177 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::FftBuilder;
+use futuresdr::blocks::FftDirection;
+use futuresdr::blocks::FftNormalization;
+use futuresdr::blocks::StreamToVector;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::blocks::VectorToStream;
+use futuresdr::dsp::Window;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+
+mod common;
+use common::run_chain;
+
+fn tone(len: usize, bin: usize) -> Vec<Complex32> {
+    (0..len)
+        .map(|i| {
+            Complex32::from_polar(
+                1.0,
+                2.0 * std::f32::consts::PI * (bin * i) as f32 / len as f32,
+            )
+        })
+        .collect()
+}
+
+fn peak(v: &[Complex32]) -> usize {
+    v.iter()
+        .enumerate()
+        .max_by(|a, b| a.1.norm().partial_cmp(&b.1.norm()).unwrap())
+        .unwrap()
+        .0
+}
+
+#[test]
+fn fft_tone() -> Result<()> {
+    let mut input = tone(64, 3);
+    input.extend(tone(64, 3));
+    // partial frame is dropped
+    input.extend(tone(10, 3));
+
+    let v: Vec<Complex32> = run_chain(input.clone(), vec![FftBuilder::new().fft_size(64).build()])?;
+    assert_eq!(v.len(), 128);
+    assert_eq!(peak(&v[0..64]), 3);
+    assert_eq!(peak(&v[64..128]), 3);
+    assert!((v[3].norm() - 64.0).abs() < 1e-3);
+
+    let v: Vec<Complex32> = run_chain(
+        input,
+        vec![FftBuilder::new()
+            .fft_size(64)
+            .shift(true)
+            .normalization(FftNormalization::Full)
+            .window(Window::Hann)
+            .build()],
+    )?;
+    assert_eq!(peak(&v[0..64]), 32 + 3);
+    // coherent gain of the (symmetric) Hann window
+    assert!((v[32 + 3].norm() - 0.5 * 63.0 / 64.0).abs() < 1e-2);
+
+    Ok(())
+}
+
+#[test]
+fn fft_round_trip() -> Result<()> {
+    let input: Vec<Complex32> = (0..256)
+        .map(|i| Complex32::new((i as f32 * 0.3).sin(), (i as f32 * 0.7).cos()))
+        .collect();
+
+    let v: Vec<Complex32> = run_chain(
+        input.clone(),
+        vec![
+            FftBuilder::new()
+                .fft_size(128)
+                .normalization(FftNormalization::Sqrt)
+                .build(),
+            FftBuilder::new()
+                .fft_size(128)
+                .direction(FftDirection::Inverse)
+                .normalization(FftNormalization::Sqrt)
+                .build(),
+        ],
+    )?;
+
+    assert_eq!(v.len(), input.len());
+    for (have, want) in v.iter().zip(input) {
+        assert!((have - want).norm() < 1e-4);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn fft_shift_odd() -> Result<()> {
+    let len = 7;
+    let input: Vec<Complex32> = (0..3 * len)
+        .map(|i| Complex32::new((i as f32 * 0.3).sin(), (i as f32 * 0.7).cos()))
+        .collect();
+
+    let shifted = |direction| {
+        FftBuilder::new()
+            .fft_size(len)
+            .direction(direction)
+            .shift(true)
+            .normalization(FftNormalization::Sqrt)
+            .build()
+    };
+    let v: Vec<Complex32> = run_chain(
+        input.clone(),
+        vec![
+            shifted(FftDirection::Forward),
+            shifted(FftDirection::Inverse),
+        ],
+    )?;
+    assert_eq!(v.len(), input.len());
+    for (have, want) in v.iter().zip(input) {
+        assert!((have - want).norm() < 1e-4);
+    }
+
+    // DC in the center of the input
+    let mut input = vec![Complex32::new(0.0, 0.0); len];
+    input[len / 2] = Complex32::new(1.0, 0.0);
+    let v: Vec<Complex32> = run_chain(input, vec![shifted(FftDirection::Inverse)])?;
+    for x in v {
+        assert!((x - Complex32::new(1.0 / (len as f32).sqrt(), 0.0)).norm() < 1e-5);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn fft_vector() -> Result<()> {
+    let mut input = tone(64, 3);
+    input.extend(tone(64, 5));
+    input.extend(tone(10, 3));
+
+    let v: Vec<Complex32> = run_chain(
+        input,
+        vec![
+            StreamToVector::new(8, 64),
+            FftBuilder::new().fft_size(64).vector(true).build(),
+            VectorToStream::new(8, 64),
+        ],
+    )?;
+    assert_eq!(v.len(), 128);
+    assert_eq!(peak(&v[0..64]), 3);
+    assert_eq!(peak(&v[64..128]), 5);
+
+    // frames are vector items
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(tone(64, 3)).build());
+    let fft = fg.add_block(FftBuilder::new().fft_size(64).vector(true).build());
+    assert!(fg.connect_stream(src, "out", fft, "in").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn windows() {
+    let w = Window::Hann.taps(5);
+    assert!(w[0].abs() < 1e-6 && w[4].abs() < 1e-6);
+    assert!((w[2] - 1.0).abs() < 1e-6);
+
+    let w = Window::Hamming.taps(5);
+    assert!((w[0] - 0.08).abs() < 1e-6);
+
+    let w = Window::BlackmanHarris.taps(5);
+    assert!((w[0] - 6e-5).abs() < 1e-6);
+    assert!((w[2] - 1.0).abs() < 1e-6);
+
+    for x in Window::Kaiser(0.0).taps(7) {
+        assert!((x - 1.0).abs() < 1e-6);
+    }
+    let w = Window::Kaiser(8.6).taps(9);
+    assert!((w[4] - 1.0).abs() < 1e-6);
+    // I0(0) / I0(8.6)
+    assert!((w[0] - 1.0 / 750.461).abs() < 1e-6);
+}
@@ -1,11 +1,19 @@
 use futuresdr::anyhow::Result;
 use futuresdr::blocks::Fir;
+use futuresdr::blocks::FirBuilder;
+use futuresdr::blocks::MessageBurstBuilder;
+use futuresdr::blocks::Source;
 use futuresdr::blocks::VectorSink;
 use futuresdr::blocks::VectorSinkBuilder;
 use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
 use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Pmt;
 use futuresdr::runtime::Runtime;
 
+mod common;
+use common::run;
+
 #[test]
 fn fir_f32() -> Result<()> {
     let mut fg = Flowgraph::new();
@@ -33,3 +41,90 @@ fn fir_f32() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn fir_complex() -> Result<()> {
+    let orig: Vec<Complex32> = (0..20)
+        .map(|i| Complex32::new(i as f32, -(i as f32) * 0.5))
+        .collect();
+    let real_taps: Vec<f32> = (0..11).map(|i| i as f32 * 0.25 - 1.0).collect();
+    let complex_taps: Vec<Complex32> = (0..11)
+        .map(|i| Complex32::new(i as f32 * 0.1, 1.0 - i as f32 * 0.2))
+        .collect();
+
+    let v: Vec<Complex32> = run(
+        orig.clone(),
+        FirBuilder::<Complex32, f32>::new(&real_taps).build(),
+    )?;
+    assert_eq!(v.len(), orig.len() + 1 - real_taps.len());
+    for (k, have) in v.iter().enumerate() {
+        let want: Complex32 = real_taps
+            .iter()
+            .enumerate()
+            .map(|(t, x)| orig[k + t] * x)
+            .sum();
+        assert!((have - want).norm() < 1e-3);
+    }
+
+    let v: Vec<Complex32> = run(orig.clone(), Fir::new(&complex_taps))?;
+    assert_eq!(v.len(), orig.len() + 1 - complex_taps.len());
+    for (k, have) in v.iter().enumerate() {
+        let want: Complex32 = complex_taps
+            .iter()
+            .enumerate()
+            .map(|(t, x)| orig[k + t] * x)
+            .sum();
+        assert!((have - want).norm() < 1e-3);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn fir_set_taps() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(Source::new(|| 1.0f32));
+    let fir = fg.add_block(Fir::new(&[1.0f32, 1.0, 1.0]));
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+    // terminates the filter after posting the taps
+    let taps = fg.add_block(MessageBurstBuilder::new(Pmt::VecF32(vec![0.5; 33]), 1).build());
+
+    fg.connect_stream(src, "out", fir, "in")?;
+    fg.connect_stream(fir, "out", snk, "in")?;
+    fg.connect_message(taps, "out", fir, "taps")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.block_async::<VectorSink<f32>>(snk).unwrap();
+    for v in snk.items() {
+        assert!((v - 3.0).abs() < 1e-5 || (v - 16.5).abs() < 1e-5);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn fir_decimation() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let orig: Vec<f32> = (0..20).map(|i| i as f32).collect();
+
+    let src = fg.add_block(VectorSourceBuilder::<f32>::new(orig).build());
+    let fir = fg.add_block(
+        FirBuilder::<f32, f32>::new(&[1.0, 1.0])
+            .decimation(3)
+            .build(),
+    );
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+
+    fg.connect_stream(src, "out", fir, "in")?;
+    fg.connect_stream(fir, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.block_async::<VectorSink<f32>>(snk).unwrap();
+    assert_eq!(snk.items(), &vec![1.0, 7.0, 13.0, 19.0, 25.0, 31.0, 37.0]);
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ use futuresdr::blocks::HeadBuilder;
 use futuresdr::blocks::NullSourceBuilder;
 use futuresdr::blocks::VectorSink;
 use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::runtime::config::Config;
 use futuresdr::runtime::scheduler::FlowScheduler;
 use futuresdr::runtime::Flowgraph;
 use futuresdr::runtime::Runtime;
@@ -38,3 +39,25 @@ fn flowgraph_flow() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn flow_config_threads() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let null_source = fg.add_block(NullSourceBuilder::new(4).build());
+    let head = fg.add_block(HeadBuilder::new(4, 100_000).build());
+    let vect_sink = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+
+    fg.connect_stream(null_source, "out", head, "in")?;
+    fg.connect_stream(head, "out", vect_sink, "in")?;
+
+    let mut config = Config::default();
+    config.scheduler_threads = Some(2);
+    config.scheduler_pin_threads = true;
+    fg = Runtime::with_scheduler(FlowScheduler::from_config(&config)).run(fg)?;
+
+    let snk = fg.block_async::<VectorSink<f32>>(vect_sink).unwrap();
+    assert_eq!(snk.items().len(), 100_000);
+
+    Ok(())
+}
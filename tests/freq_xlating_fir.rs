@@ -0,0 +1,95 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::FreqXlatingFirBuilder;
+use futuresdr::blocks::MessageBurstBuilder;
+use futuresdr::blocks::Source;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::dsp::design;
+use futuresdr::dsp::Window;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+
+const FS: f64 = 48000.0;
+
+// tone with amplitude 1 at 6 kHz and tone with amplitude 0.5 at -12 kHz
+fn sample(i: usize) -> Complex32 {
+    let t = i as f64 / FS;
+    let a = Complex32::from_polar(1.0, (2.0 * std::f64::consts::PI * 6000.0 * t) as f32);
+    let b = Complex32::from_polar(0.5, (-2.0 * std::f64::consts::PI * 12000.0 * t) as f32);
+    a + b
+}
+
+#[test]
+fn freq_xlating_fir() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let input: Vec<Complex32> = (0..20_000).map(sample).collect();
+    let taps = design::lowpass(101, 2000.0, FS, Window::Hamming);
+
+    let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(input).build());
+    let fir = fg.add_block(
+        FreqXlatingFirBuilder::new(&taps, FS)
+            .center_freq(6000.0)
+            .decimation(4)
+            .build(),
+    );
+    let snk = fg.add_block(VectorSinkBuilder::<Complex32>::new().build());
+
+    fg.connect_stream(src, "out", fir, "in")?;
+    fg.connect_stream(fir, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.block_async::<VectorSink<Complex32>>(snk).unwrap();
+    let v = snk.items();
+
+    assert_eq!(v.len(), (20_000 - 101) / 4 + 1);
+    // the 6 kHz tone ends up at DC
+    for w in v.windows(2) {
+        assert!((w[0].norm() - 1.0).abs() < 1e-2);
+        assert!((w[1] - w[0]).norm() < 1e-2);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn freq_xlating_fir_retune() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let mut i = 0;
+    let src = fg.add_block(Source::new(move || {
+        i += 1;
+        sample(i)
+    }));
+    let taps = design::lowpass(101, 2000.0, FS, Window::Hamming);
+    let fir = fg.add_block(
+        FreqXlatingFirBuilder::new(&taps, FS)
+            .center_freq(6000.0)
+            .decimation(4)
+            .build(),
+    );
+    let snk = fg.add_block(VectorSinkBuilder::<Complex32>::new().build());
+    // terminates the filter after retuning
+    let freq = fg.add_block(MessageBurstBuilder::new(Pmt::Double(-12000.0), 1).build());
+
+    fg.connect_stream(src, "out", fir, "in")?;
+    fg.connect_stream(fir, "out", snk, "in")?;
+    fg.connect_message(freq, "out", fir, "freq")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.block_async::<VectorSink<Complex32>>(snk).unwrap();
+    // only outputs whose window straddles the retune have other magnitudes
+    let other = snk
+        .items()
+        .iter()
+        .filter(|x| (x.norm() - 1.0).abs() > 1e-2 && (x.norm() - 0.5).abs() > 1e-2)
+        .count();
+    assert!(other <= 101 / 4 + 1);
+
+    Ok(())
+}
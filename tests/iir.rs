@@ -0,0 +1,136 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::DcBlocker;
+use futuresdr::blocks::Iir;
+use futuresdr::blocks::SinglePoleIir;
+use futuresdr::dsp::design;
+use futuresdr::dsp::Biquad;
+use futuresdr::num_complex::Complex32;
+
+mod common;
+use common::run;
+
+const FS: f64 = 48000.0;
+
+fn db(sections: &[Biquad], f: f64) -> f64 {
+    20.0 * Biquad::cascade_response(sections, f, FS).norm().log10()
+}
+
+#[test]
+fn butterworth() {
+    for order in [1, 4, 5] {
+        let s = design::butterworth_lowpass(order, 1000.0, FS);
+        assert_eq!(s.len(), order.div_ceil(2));
+        assert!(db(&s, 0.0).abs() < 1e-4);
+        assert!((db(&s, 1000.0) + 3.0103).abs() < 1e-2);
+
+        let s = design::butterworth_highpass(order, 1000.0, FS);
+        assert!(db(&s, FS / 2.0).abs() < 1e-4);
+        assert!((db(&s, 1000.0) + 3.0103).abs() < 1e-2);
+    }
+
+    // 80 dB per decade, more due to the bilinear transform
+    let s = design::butterworth_lowpass(4, 1000.0, FS);
+    assert!(db(&s, 10000.0) < -80.0);
+    let s = design::butterworth_highpass(4, 1000.0, FS);
+    assert!(db(&s, 100.0) < -79.0);
+}
+
+#[test]
+fn chebyshev() {
+    for order in [4, 5] {
+        let s = design::chebyshev_lowpass(order, 1.0, 1000.0, FS);
+        for f in (0..1000).step_by(10) {
+            let g = db(&s, f as f64);
+            assert!(g < 1e-4 && g > -1.0 - 1e-3);
+        }
+        assert!((db(&s, 1000.0) + 1.0).abs() < 1e-2);
+        assert!(db(&s, 3000.0) < -40.0);
+
+        let s = design::chebyshev_highpass(order, 1.0, 1000.0, FS);
+        assert!((db(&s, 1000.0) + 1.0).abs() < 1e-2);
+        assert!(db(&s, 300.0) < -40.0);
+    }
+}
+
+#[test]
+fn iir_impulse_response() -> Result<()> {
+    let sections = design::butterworth_lowpass(3, 5000.0, FS);
+
+    let mut impulse = vec![0.0f32; 100];
+    impulse[0] = 1.0;
+    let v: Vec<f32> = run(impulse.clone(), Iir::<f32>::new(&sections))?;
+
+    // direct form I reference
+    let mut want: Vec<f64> = impulse.iter().map(|x| *x as f64).collect();
+    for s in &sections {
+        let x = want.clone();
+        for n in 0..x.len() {
+            let at = |v: &[f64], k: usize| if n >= k { v[n - k] } else { 0.0 };
+            want[n] = s.b0 as f64 * x[n] + s.b1 as f64 * at(&x, 1) + s.b2 as f64 * at(&x, 2)
+                - s.a1 as f64 * at(&want, 1)
+                - s.a2 as f64 * at(&want, 2);
+        }
+    }
+    assert_eq!(v.len(), want.len());
+    for (have, want) in v.iter().zip(&want) {
+        assert!((*have as f64 - want).abs() < 1e-5);
+    }
+
+    let input: Vec<Complex32> = impulse
+        .iter()
+        .map(|x| Complex32::new(*x, -2.0 * x))
+        .collect();
+    let c: Vec<Complex32> = run(input, Iir::<Complex32>::new(&sections))?;
+    for (c, r) in c.iter().zip(v) {
+        assert!((c - Complex32::new(r, -2.0 * r)).norm() < 1e-5);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn iir_denormals() -> Result<()> {
+    let sections = design::butterworth_lowpass(2, 100.0, FS);
+
+    let mut input = vec![0.0f32; 200_000];
+    input[0] = 1.0;
+    let v: Vec<f32> = run(input, Iir::<f32>::new(&sections))?;
+
+    // state is flushed to zero instead of decaying through denormals
+    assert!(v.iter().all(|x| *x == 0.0 || x.is_normal()));
+    assert_eq!(*v.last().unwrap(), 0.0);
+
+    Ok(())
+}
+
+#[test]
+fn single_pole() -> Result<()> {
+    let alpha = 0.1f32;
+    let v: Vec<f32> = run(vec![1.0f32; 50], SinglePoleIir::<f32>::new(alpha))?;
+    for (n, y) in v.iter().enumerate() {
+        assert!((y - (1.0 - (1.0 - alpha).powi(n as i32 + 1))).abs() < 1e-5);
+    }
+
+    let v: Vec<Complex32> = run(
+        vec![Complex32::new(2.0, -1.0); 500],
+        SinglePoleIir::<Complex32>::new(alpha),
+    )?;
+    assert!((v.last().unwrap() - Complex32::new(2.0, -1.0)).norm() < 1e-5);
+
+    Ok(())
+}
+
+#[test]
+fn dc_blocker() -> Result<()> {
+    let input: Vec<f32> = (0..10_000)
+        .map(|i| 3.0 + (2.0 * std::f32::consts::PI * 0.05 * i as f32).sin())
+        .collect();
+    let v: Vec<f32> = run(input.clone(), DcBlocker::<f32>::new(0.995))?;
+
+    // DC is removed, the tone passes
+    for (y, x) in v[5000..].iter().zip(&input[5000..]) {
+        assert!((y - (x - 3.0)).abs() < 0.05);
+    }
+
+    Ok(())
+}
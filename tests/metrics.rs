@@ -0,0 +1,77 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::HeadBuilder;
+use futuresdr::blocks::NullSinkBuilder;
+use futuresdr::blocks::NullSourceBuilder;
+use futuresdr::blocks::ThrottleBuilder;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+const BIND: &str = "127.0.0.1:26126";
+
+fn scrape(path: &str) -> Option<String> {
+    let mut stream = TcpStream::connect(BIND).ok()?;
+    stream
+        .write_all(format!("GET {} HTTP/1.0\r\nHost: localhost\r\n\r\n", path).as_bytes())
+        .ok()?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+    Some(response)
+}
+
+#[test]
+fn metrics_scrape() -> Result<()> {
+    std::env::set_var("FUTURESDR_CTRLPORT_ENABLE", "true");
+    std::env::set_var("FUTURESDR_CTRLPORT_BIND", BIND);
+    std::env::set_var("FUTURESDR_CTRLPORT_METRICS", "true");
+
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(NullSourceBuilder::new(4).build());
+    let throttle = fg.add_block(ThrottleBuilder::new(4, 100_000.0).build());
+    let head = fg.add_block(HeadBuilder::new(4, 200_000).build());
+    let snk = fg.add_block(NullSinkBuilder::new(4).build());
+
+    fg.connect_stream(src, "out", throttle, "in")?;
+    fg.connect_stream(throttle, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+
+    let rt = Runtime::new();
+    let (task, _) = rt.start(fg);
+
+    let mut body = None;
+    for _ in 0..50 {
+        std::thread::sleep(Duration::from_millis(20));
+        if let Some(r) = scrape("/metrics") {
+            if r.contains("futuresdr_block_work_calls_total") {
+                body = Some(r);
+                break;
+            }
+        }
+    }
+    let dot = scrape("/api/fg/0/dot/");
+    async_io::block_on(task)?;
+
+    let dot = dot.expect("no dot scraped");
+    assert!(dot.contains("text/vnd.graphviz"));
+    assert!(dot.contains("digraph flowgraph"));
+    assert!(dot.contains("Throttle_0"));
+
+    let body = body.expect("no metrics scraped");
+    assert!(body.contains("application/openmetrics-text"));
+    assert!(body.contains("futuresdr_flowgraph_blocks{fg=\"0\"} 4"));
+    assert!(body
+        .contains("futuresdr_flowgraph_state{fg=\"0\",futuresdr_flowgraph_state=\"running\"} 1"));
+    assert!(body.contains(
+        "futuresdr_flowgraph_state{fg=\"0\",futuresdr_flowgraph_state=\"terminating\"} 0"
+    ));
+    assert!(body.contains("name=\"Throttle_0\""));
+    assert!(body.contains("futuresdr_stream_output_items_total{fg=\"0\""));
+    assert!(body.contains("futuresdr_stream_input_buffered_items{fg=\"0\""));
+    assert!(body.trim_end().ends_with("# EOF"));
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::ChannelModelBuilder;
+use futuresdr::blocks::ChunksToSymbols;
+use futuresdr::blocks::ConstellationMapper;
+use futuresdr::blocks::ConstellationSlicer;
+use futuresdr::blocks::FskDemod;
+use futuresdr::blocks::FskModBuilder;
+use futuresdr::blocks::SymbolsToBits;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::dsp::Constellation;
+use futuresdr::dsp::Mapping;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Block;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+// pseudo-random bits
+fn bits(n: usize) -> Vec<u8> {
+    let mut x = 0x1234_5678u32;
+    (0..n)
+        .map(|_| {
+            x = x.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (x >> 16) as u8 & 1
+        })
+        .collect()
+}
+
+// runs the input through a chain of blocks
+fn chain<A, B>(input: Vec<A>, blocks: Vec<Block>) -> Result<Vec<B>>
+where
+    A: Copy + Send + Sync + std::fmt::Debug + 'static,
+    B: Copy + Send + Sync + std::fmt::Debug + 'static,
+{
+    let mut fg = Flowgraph::new();
+
+    let mut prev = fg.add_block(VectorSourceBuilder::<A>::new(input).build());
+    for b in blocks {
+        let b = fg.add_block(b);
+        fg.connect_stream(prev, "out", b, "in")?;
+        prev = b;
+    }
+    let snk = fg.add_block(VectorSinkBuilder::<B>::new().build());
+    fg.connect_stream(prev, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    Ok(fg
+        .block_async::<VectorSink<B>>(snk)
+        .unwrap()
+        .items()
+        .clone())
+}
+
+fn constellations() -> Vec<Constellation> {
+    vec![
+        Constellation::bpsk(),
+        Constellation::qpsk(),
+        Constellation::psk8(),
+        Constellation::qam16(),
+        Constellation::qam64(),
+    ]
+}
+
+#[test]
+fn constellation_gray() {
+    for c in constellations() {
+        let m = c.points().len();
+        assert_eq!(1 << c.bits_per_symbol(), m);
+
+        let energy: f32 = c.points().iter().map(|p| p.norm_sqr()).sum::<f32>() / m as f32;
+        assert!((energy - 1.0).abs() < 1e-5);
+
+        let d_min = (0..m)
+            .flat_map(|a| (0..m).filter(move |b| *b != a).map(move |b| (a, b)))
+            .map(|(a, b)| (c.map(a) - c.map(b)).norm())
+            .fold(f32::INFINITY, f32::min);
+        for a in 0..m {
+            assert_eq!(c.slice(c.map(a)), a);
+            // nearest neighbors differ in one bit
+            for b in 0..m {
+                if a != b && (c.map(a) - c.map(b)).norm() < d_min * 1.01 {
+                    assert_eq!((a ^ b).count_ones(), 1);
+                }
+            }
+        }
+    }
+
+    let natural = Constellation::psk(4, Mapping::Natural);
+    for k in 0..4 {
+        let angle = std::f32::consts::PI / 4.0 + k as f32 * std::f32::consts::PI / 2.0;
+        assert!((natural.map(k) - Complex32::from_polar(1.0, angle)).norm() < 1e-6);
+    }
+    assert_eq!(
+        Constellation::qam(16, Mapping::Natural).map(0b0111),
+        Constellation::qam16().map(0b0110)
+    );
+}
+
+#[test]
+fn constellation_loopback() -> Result<()> {
+    let input = bits(2400);
+    for c in constellations() {
+        let k = c.bits_per_symbol();
+        let v: Vec<u8> = chain(
+            input.clone(),
+            vec![
+                ConstellationMapper::new(c.clone()),
+                ChannelModelBuilder::new(1.0).snr(35.0).seed(1).build(),
+                ConstellationSlicer::new(c),
+            ],
+        )?;
+        assert_eq!(v.len(), input.len() / k * k);
+        assert_eq!(v, input);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn chunks_to_symbols() -> Result<()> {
+    let c = Constellation::psk8();
+    let chunks: Vec<u8> = bits(3000)
+        .chunks(3)
+        .map(|b| (b[0] << 2) | (b[1] << 1) | b[2])
+        .collect();
+
+    let symbols: Vec<Complex32> = chain(chunks.clone(), vec![ChunksToSymbols::new(c.clone())])?;
+    assert_eq!(symbols.len(), chunks.len());
+    for (s, x) in chunks.iter().zip(&symbols) {
+        assert_eq!(c.map(*s as usize), *x);
+    }
+
+    // slicing the symbols gives the same bits as unpacking the chunks
+    let sliced: Vec<u8> = chain(symbols, vec![ConstellationSlicer::new(c)])?;
+    let unpacked: Vec<u8> = chain(chunks, vec![SymbolsToBits::new(3)])?;
+    assert_eq!(sliced, unpacked);
+    assert_eq!(unpacked, bits(3000));
+
+    Ok(())
+}
+
+#[test]
+fn fsk_loopback() -> Result<()> {
+    let sps = 8;
+    let input = bits(1000);
+
+    for modulator in [
+        FskModBuilder::new(sps).modulation_index(1.0).build(),
+        FskModBuilder::new(sps).bt(0.5).build(),
+        FskModBuilder::new(sps)
+            .bt(0.3)
+            .modulation_index(0.32)
+            .build(),
+    ] {
+        let samples: Vec<Complex32> = chain(input.clone(), vec![modulator])?;
+        assert_eq!(samples.len(), input.len() * sps);
+        assert!(samples.iter().all(|x| (x.norm() - 1.0).abs() < 1e-5));
+
+        let v: Vec<u8> = chain(
+            samples,
+            vec![
+                ChannelModelBuilder::new(1.0).snr(20.0).seed(2).build(),
+                FskDemod::new(sps),
+            ],
+        )?;
+        assert_eq!(v, input);
+    }
+
+    Ok(())
+}
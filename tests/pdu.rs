@@ -0,0 +1,173 @@
+use futuresdr::anyhow::Result;
+use futuresdr::async_trait::async_trait;
+use futuresdr::blocks::HeadBuilder;
+use futuresdr::blocks::IdleBehavior;
+use futuresdr::blocks::PduLength;
+use futuresdr::blocks::PduToStreamBuilder;
+use futuresdr::blocks::StreamToPdu;
+use futuresdr::blocks::ThrottleBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::runtime::AsyncKernel;
+use futuresdr::runtime::Block;
+use futuresdr::runtime::BlockMeta;
+use futuresdr::runtime::BlockMetaBuilder;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::MessageIo;
+use futuresdr::runtime::MessageIoBuilder;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+use futuresdr::runtime::StreamIoBuilder;
+
+// stores received messages
+struct Collector {
+    messages: Vec<Pmt>,
+}
+
+impl Collector {
+    fn block() -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("Collector").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_sync_input(
+                    "in",
+                    |block: &mut Collector,
+                     _mio: &mut MessageIo<Collector>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        block.messages.push(p);
+                        Ok(Pmt::Null)
+                    },
+                )
+                .build(),
+            Collector {
+                messages: Vec::new(),
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for Collector {}
+
+fn stream_to_pdu<A>(input: Vec<A>, block: Block) -> Result<Vec<Pmt>>
+where
+    A: Copy + Send + Sync + std::fmt::Debug + 'static,
+{
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSourceBuilder::<A>::new(input).build());
+    let block = fg.add_block(block);
+    let collector = fg.add_block(Collector::block());
+    fg.connect_stream(src, "out", block, "in")?;
+    fg.connect_message(block, "out", collector, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    Ok(fg
+        .block_async::<Collector>(collector)
+        .unwrap()
+        .messages
+        .clone())
+}
+
+#[test]
+fn fixed_length() -> Result<()> {
+    let input: Vec<u8> = (0..10).collect();
+    let messages = stream_to_pdu(input, StreamToPdu::<u8>::new(PduLength::Fixed(4)))?;
+    assert_eq!(
+        messages,
+        vec![Pmt::Blob(vec![0, 1, 2, 3]), Pmt::Blob(vec![4, 5, 6, 7])]
+    );
+
+    let input: Vec<f32> = (0..6).map(|x| x as f32).collect();
+    let messages = stream_to_pdu(input, StreamToPdu::<f32>::new(PduLength::Fixed(3)))?;
+    assert_eq!(
+        messages,
+        vec![
+            Pmt::VecF32(vec![0.0, 1.0, 2.0]),
+            Pmt::VecF32(vec![3.0, 4.0, 5.0])
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn length_prefixed() -> Result<()> {
+    let input: Vec<u8> = vec![0, 3, 1, 2, 3, 0, 0, 0, 1, 9, 0, 5, 1];
+    let messages = stream_to_pdu(input, StreamToPdu::<u8>::new(PduLength::Prefixed))?;
+    assert_eq!(
+        messages,
+        vec![
+            Pmt::Blob(vec![1, 2, 3]),
+            Pmt::Blob(vec![]),
+            Pmt::Blob(vec![9])
+        ]
+    );
+
+    // invalid prefixes are skipped
+    let input: Vec<f32> = vec![2.0, 1.0, 2.0, -1.0, 0.5, 1.0, 7.0];
+    let messages = stream_to_pdu(input, StreamToPdu::<f32>::new(PduLength::Prefixed))?;
+    assert_eq!(
+        messages,
+        vec![Pmt::VecF32(vec![1.0, 2.0]), Pmt::VecF32(vec![7.0])]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn pdu_to_stream() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let pdu_to_stream = fg.add_block(PduToStreamBuilder::<u8>::new().prefix_length().build());
+    let head = fg.add_block(HeadBuilder::new(1, 9).build());
+    let snk = fg.add_block(VectorSinkBuilder::<u8>::new().build());
+    fg.connect_stream(pdu_to_stream, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start(fg);
+    async_io::block_on(async {
+        handle
+            .call(pdu_to_stream, 0, Pmt::Blob(vec![1, 2, 3]))
+            .await?;
+        handle.call(pdu_to_stream, 0, Pmt::U32(1)).await?;
+        handle.call(pdu_to_stream, 0, Pmt::Blob(vec![4, 5])).await
+    })?;
+    let fg = async_io::block_on(task)?;
+
+    let snk = fg.block_async::<VectorSink<u8>>(snk).unwrap();
+    assert_eq!(snk.items(), &vec![0, 3, 1, 2, 3, 0, 2, 4, 5]);
+
+    Ok(())
+}
+
+#[test]
+fn pdu_to_stream_zero_fill() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let pdu_to_stream = fg.add_block(
+        PduToStreamBuilder::<f32>::new()
+            .idle(IdleBehavior::ZeroFill)
+            .build(),
+    );
+    let throttle = fg.add_block(ThrottleBuilder::new(4, 100_000.0).build());
+    let head = fg.add_block(HeadBuilder::new(4, 20_000).build());
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+    fg.connect_stream(pdu_to_stream, "out", throttle, "in")?;
+    fg.connect_stream(throttle, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start(fg);
+    async_io::block_on(handle.call(pdu_to_stream, 0, Pmt::VecF32(vec![1.0, 2.0, 3.0])))?;
+    let fg = async_io::block_on(task)?;
+
+    let items = fg.block_async::<VectorSink<f32>>(snk).unwrap().items();
+    assert_eq!(items.len(), 20_000);
+    let data: Vec<f32> = items.iter().copied().filter(|x| *x != 0.0).collect();
+    assert_eq!(data, vec![1.0, 2.0, 3.0]);
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use std::f32::consts::PI;
+
+use futuresdr::anyhow::Result;
+use futuresdr::async_trait::async_trait;
+use futuresdr::blocks::PreambleDetectorBuilder;
+use futuresdr::blocks::PreambleSample;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::AsyncKernel;
+use futuresdr::runtime::Block;
+use futuresdr::runtime::BlockMeta;
+use futuresdr::runtime::BlockMetaBuilder;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::MessageIo;
+use futuresdr::runtime::MessageIoBuilder;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+use futuresdr::runtime::StreamIoBuilder;
+
+// stores received messages
+struct Collector {
+    messages: Vec<Pmt>,
+}
+
+impl Collector {
+    fn block() -> Block {
+        Block::new_async(
+            BlockMetaBuilder::new("Collector").build(),
+            StreamIoBuilder::new().build(),
+            MessageIoBuilder::new()
+                .add_sync_input(
+                    "in",
+                    |block: &mut Collector,
+                     _mio: &mut MessageIo<Collector>,
+                     _meta: &mut BlockMeta,
+                     p: Pmt| {
+                        block.messages.push(p);
+                        Ok(Pmt::Null)
+                    },
+                )
+                .build(),
+            Collector {
+                messages: Vec::new(),
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl AsyncKernel for Collector {}
+
+// offset, peak, and phase
+type Detection = (u64, f64, f64);
+
+// detections and forwarded items
+fn detect<A>(input: Vec<A>, detector: Block) -> Result<(Vec<Detection>, Vec<A>)>
+where
+    A: PreambleSample + std::fmt::Debug,
+{
+    let forward = detector.stream_outputs().len() == 1;
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSourceBuilder::<A>::new(input).build());
+    let detector = fg.add_block(detector);
+    let collector = fg.add_block(Collector::block());
+    fg.connect_stream(src, "out", detector, "in")?;
+    fg.connect_message(detector, "detection", collector, "in")?;
+    let snk = if forward {
+        let snk = fg.add_block(VectorSinkBuilder::<A>::new().build());
+        fg.connect_stream(detector, "out", snk, "in")?;
+        Some(snk)
+    } else {
+        None
+    };
+
+    fg = Runtime::new().run(fg)?;
+
+    let detections = fg
+        .block_async::<Collector>(collector)
+        .unwrap()
+        .messages
+        .iter()
+        .map(|m| match m {
+            Pmt::MapStrPmt(m) => match (&m["offset"], &m["peak"], &m["phase"]) {
+                (Pmt::U64(o), Pmt::Double(p), Pmt::Double(ph)) => (*o, *p, *ph),
+                _ => panic!("unexpected detection {:?}", m),
+            },
+            _ => panic!("unexpected message {:?}", m),
+        })
+        .collect();
+    let items = snk.map_or(Vec::new(), |snk| {
+        fg.block_async::<VectorSink<A>>(snk)
+            .unwrap()
+            .items()
+            .clone()
+    });
+
+    Ok((detections, items))
+}
+
+fn lcg(n: usize, seed: u32) -> Vec<u32> {
+    let mut x = seed;
+    (0..n)
+        .map(|_| {
+            x = x.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            x >> 16
+        })
+        .collect()
+}
+
+#[test]
+fn complex_preamble() -> Result<()> {
+    let preamble: Vec<Complex32> = lcg(64, 1)
+        .iter()
+        .map(|x| Complex32::from_polar(1.0, PI / 4.0 + (x % 4) as f32 * PI / 2.0))
+        .collect();
+    let mut input: Vec<Complex32> = lcg(4000, 2)
+        .chunks(2)
+        .map(|x| Complex32::new(x[0] as f32 / 32768.0 - 1.0, x[1] as f32 / 32768.0 - 1.0) * 0.1)
+        .collect();
+    for (offset, phase) in [(300, 0.7), (1500, -2.0)] {
+        for (k, p) in preamble.iter().enumerate() {
+            input[offset + k] += p * Complex32::from_polar(0.5, phase);
+        }
+    }
+
+    let (detections, _) = detect(
+        input.clone(),
+        PreambleDetectorBuilder::new(&preamble).build(),
+    )?;
+    assert_eq!(detections.len(), 2);
+    for ((offset, peak, phase), (o, ph)) in detections.iter().zip([(300, 0.7), (1500, -2.0)]) {
+        assert_eq!(*offset, o);
+        assert!(*peak > 0.9);
+        assert!((phase - ph).abs() < 0.05);
+    }
+
+    // bursts, starting with the preamble
+    let (detections, items) = detect(
+        input.clone(),
+        PreambleDetectorBuilder::new(&preamble).forward(100).build(),
+    )?;
+    assert_eq!(detections.len(), 2);
+    assert_eq!(items.len(), 200);
+    assert_eq!(&items[0..100], &input[300..400]);
+    assert_eq!(&items[100..200], &input[1500..1600]);
+
+    // a burst that is cut off by the end of the stream
+    let (_, items) = detect(
+        input[0..1600].to_vec(),
+        PreambleDetectorBuilder::new(&preamble).forward(100).build(),
+    )?;
+    assert_eq!(items.len(), 200);
+    assert_eq!(&items[100..200], &input[1500..1600]);
+
+    Ok(())
+}
+
+#[test]
+fn access_code() -> Result<()> {
+    let code: Vec<u8> = (0..32)
+        .rev()
+        .map(|i| (0x1ACF_FC1Du32 >> i) as u8 & 1)
+        .collect();
+    let mut input: Vec<u8> = lcg(2000, 3).iter().map(|x| (x & 1) as u8).collect();
+    input[500..532].copy_from_slice(&code);
+    // two bit errors
+    input[505] ^= 1;
+    input[520] ^= 1;
+    // inverted
+    for (k, c) in code.iter().enumerate() {
+        input[1500 + k] = c ^ 1;
+    }
+
+    let (detections, _) = detect(
+        input,
+        PreambleDetectorBuilder::new(&code).threshold(0.85).build(),
+    )?;
+    assert_eq!(detections.len(), 2);
+    assert_eq!(detections[0].0, 500);
+    assert_eq!(detections[0].1, 0.875);
+    assert_eq!(detections[0].2, 0.0);
+    assert_eq!(detections[1].0, 1500);
+    assert_eq!(detections[1].1, 1.0);
+    assert!((detections[1].2 - std::f64::consts::PI).abs() < 1e-6);
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::ArbitraryResamplerBuilder;
+use futuresdr::blocks::RationalResamplerBuilder;
+use futuresdr::num_complex::Complex32;
+
+mod common;
+use common::run;
+
+fn tone(len: usize, freq: f32) -> Vec<Complex32> {
+    (0..len)
+        .map(|i| Complex32::from_polar(1.0, 2.0 * std::f32::consts::PI * freq * i as f32))
+        .collect()
+}
+
+// checks for a tone with unit amplitude and the given frequency
+fn check_tone(v: &[Complex32], freq: f32) {
+    let rot = Complex32::from_polar(1.0, 2.0 * std::f32::consts::PI * freq);
+    for w in v.windows(2) {
+        assert!((w[0].norm() - 1.0).abs() < 1e-2);
+        assert!((w[1] - w[0] * rot).norm() < 1e-2);
+    }
+}
+
+#[test]
+fn rational() -> Result<()> {
+    let n = 30_000;
+
+    let v: Vec<Complex32> = run(
+        tone(n, 0.05),
+        RationalResamplerBuilder::<Complex32>::new(3, 2).build(),
+    )?;
+    assert!((v.len() as f32 - n as f32 * 1.5).abs() < 200.0);
+    check_tone(&v, 0.05 / 1.5);
+
+    // 6/4 is reduced to 3/2
+    let w: Vec<Complex32> = run(
+        tone(n, 0.05),
+        RationalResamplerBuilder::<Complex32>::new(6, 4).build(),
+    )?;
+    assert_eq!(v.len(), w.len());
+
+    let v: Vec<Complex32> = run(
+        tone(n, 0.05),
+        RationalResamplerBuilder::<Complex32>::new(1, 4).build(),
+    )?;
+    assert!((v.len() as f32 - n as f32 / 4.0).abs() < 200.0);
+    check_tone(&v, 0.2);
+
+    // aliased tone is suppressed
+    let v: Vec<Complex32> = run(
+        tone(n, 0.3),
+        RationalResamplerBuilder::<Complex32>::new(1, 4).build(),
+    )?;
+    for x in v {
+        assert!(x.norm() < 1e-3);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn arbitrary() -> Result<()> {
+    let n = 30_000;
+
+    let rate = 1.0001;
+    let v: Vec<Complex32> = run(
+        tone(n, 0.05),
+        ArbitraryResamplerBuilder::<Complex32>::new(rate).build(),
+    )?;
+    assert!((v.len() as f64 - n as f64 * rate).abs() < 100.0);
+    check_tone(&v, 0.05 / rate as f32);
+
+    let rate = std::f64::consts::FRAC_1_SQRT_2;
+    let v: Vec<Complex32> = run(
+        tone(n, 0.1),
+        ArbitraryResamplerBuilder::<Complex32>::new(rate).build(),
+    )?;
+    assert!((v.len() as f64 - n as f64 * rate).abs() < 100.0);
+    check_tone(&v, 0.1 / rate as f32);
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::HeadBuilder;
+use futuresdr::blocks::Selector;
+use futuresdr::blocks::Source;
+use futuresdr::blocks::StreamMux;
+use futuresdr::blocks::Valve;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+use std::mem;
+
+#[test]
+fn stream_mux() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let src0 = fg.add_block(VectorSourceBuilder::<u32>::new(vec![1, 2, 3, 4]).build());
+    let src1 =
+        fg.add_block(VectorSourceBuilder::<u32>::new(vec![10, 11, 12, 13, 14, 15, 16]).build());
+    let mux = fg.add_block(StreamMux::new(mem::size_of::<u32>(), vec![2, 3]));
+    let snk = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+    fg.connect_stream(src0, "out", mux, "in0")?;
+    fg.connect_stream(src1, "out", mux, "in1")?;
+    fg.connect_stream(mux, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    // terminates waiting for the third turn of `in0`
+    let v = fg.block_async::<VectorSink<u32>>(snk).unwrap().items();
+    assert_eq!(v, &vec![1, 2, 10, 11, 12, 3, 4, 13, 14, 15]);
+
+    Ok(())
+}
+
+#[test]
+fn selector_static() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let src0 = fg.add_block(VectorSourceBuilder::<u32>::new(vec![0; 10000]).build());
+    let src1 = fg.add_block(VectorSourceBuilder::<u32>::new(vec![1; 20000]).build());
+    let sel = fg.add_block(Selector::new(mem::size_of::<u32>(), 2, 2));
+    let snk0 = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+    let snk1 = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+    fg.connect_stream(src0, "out", sel, "in0")?;
+    fg.connect_stream(src1, "out", sel, "in1")?;
+    fg.connect_stream(sel, "out0", snk0, "in")?;
+    fg.connect_stream(sel, "out1", snk1, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let v = fg.block_async::<VectorSink<u32>>(snk0).unwrap().items();
+    assert_eq!(v, &vec![0; 10000]);
+    let v = fg.block_async::<VectorSink<u32>>(snk1).unwrap().items();
+    assert!(v.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn selector_switch() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let src0 = fg.add_block(Source::new(|| 0u32));
+    let src1 = fg.add_block(Source::new(|| 1u32));
+    let sel = fg.add_block(Selector::new(mem::size_of::<u32>(), 2, 2));
+    let head = fg.add_block(HeadBuilder::new(mem::size_of::<u32>(), 1000).build());
+    let snk0 = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+    let snk1 = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+    fg.connect_stream(src0, "out", sel, "in0")?;
+    fg.connect_stream(src1, "out", sel, "in1")?;
+    fg.connect_stream(sel, "out0", snk0, "in")?;
+    fg.connect_stream(sel, "out1", head, "in")?;
+    fg.connect_stream(head, "out", snk1, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start(fg);
+    // switch the input first, so that `out1` only gets items of `in1`
+    async_io::block_on(async {
+        handle.call(sel, 0, Pmt::U32(1)).await?;
+        handle.call(sel, 1, Pmt::U32(1)).await
+    })?;
+    let fg = async_io::block_on(task)?;
+
+    let v = fg.block_async::<VectorSink<u32>>(snk0).unwrap().items();
+    assert!(v.windows(2).all(|w| w[0] <= w[1]));
+    let v = fg.block_async::<VectorSink<u32>>(snk1).unwrap().items();
+    assert_eq!(v, &vec![1; 1000]);
+
+    Ok(())
+}
+
+#[test]
+fn valve() -> Result<()> {
+    for open in [false, true] {
+        let mut fg = Flowgraph::new();
+        let src = fg.add_block(VectorSourceBuilder::<u32>::new((0..10000).collect()).build());
+        let valve = fg.add_block(Valve::new(mem::size_of::<u32>(), open));
+        let snk = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+        fg.connect_stream(src, "out", valve, "in")?;
+        fg.connect_stream(valve, "out", snk, "in")?;
+
+        fg = Runtime::new().run(fg)?;
+
+        let v = fg.block_async::<VectorSink<u32>>(snk).unwrap().items();
+        if open {
+            assert_eq!(v, &(0..10000).collect::<Vec<u32>>());
+        } else {
+            assert!(v.is_empty());
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn valve_open() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let mut counter = 0u32;
+    let src = fg.add_block(Source::new(move || {
+        counter = counter.wrapping_add(1);
+        counter
+    }));
+    let valve = fg.add_block(Valve::new(mem::size_of::<u32>(), false));
+    let head = fg.add_block(HeadBuilder::new(mem::size_of::<u32>(), 1000).build());
+    let snk = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+    fg.connect_stream(src, "out", valve, "in")?;
+    fg.connect_stream(valve, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start(fg);
+    let state = async_io::block_on(async {
+        handle.call(valve, 0, Pmt::U32(1)).await?;
+        handle.callback(valve, 0, Pmt::Null).await
+    })?;
+    assert_eq!(state, Pmt::U32(1));
+    let fg = async_io::block_on(task)?;
+
+    // the items since opening are forwarded without gaps
+    let v = fg.block_async::<VectorSink<u32>>(snk).unwrap().items();
+    assert_eq!(v.len(), 1000);
+    assert!(v.windows(2).all(|w| w[1] == w[0] + 1));
+
+    Ok(())
+}
@@ -3,11 +3,17 @@ use std::iter::repeat_with;
 use futuresdr::anyhow::Result;
 use futuresdr::blocks::CopyBuilder;
 use futuresdr::blocks::HeadBuilder;
+use futuresdr::blocks::MessageBurstBuilder;
+use futuresdr::blocks::MessageSinkBuilder;
 use futuresdr::blocks::NullSourceBuilder;
 use futuresdr::blocks::VectorSink;
 use futuresdr::blocks::VectorSinkBuilder;
 use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::runtime::buffer::slab::Slab;
+use futuresdr::runtime::config::Config;
+use futuresdr::runtime::config::Value;
 use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Pmt;
 use futuresdr::runtime::Runtime;
 
 #[test]
@@ -109,3 +115,127 @@ fn fg_rand_vec_multi_snk() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn slab_termination() -> Result<()> {
+    // the source terminates before its downstream and the Head before its upstream, so the
+    // Slab buffers notify blocks that are already gone
+    let mut fg = Flowgraph::new();
+
+    let src = fg.add_block(VectorSourceBuilder::<u32>::new((0..10_000).collect()).build());
+    let copy = fg.add_block(CopyBuilder::new(4).build());
+    let head = fg.add_block(HeadBuilder::new(4, 3).build());
+    let snk = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+
+    fg.connect_stream_with_type(src, "out", copy, "in", Slab::new())?;
+    fg.connect_stream_with_type(copy, "out", head, "in", Slab::new())?;
+    fg.connect_stream_with_type(head, "out", snk, "in", Slab::new())?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.block_async::<VectorSink<u32>>(snk).unwrap();
+    assert_eq!(snk.items(), &vec![0, 1, 2]);
+
+    Ok(())
+}
+
+#[test]
+fn concurrent_flowgraphs() -> Result<()> {
+    let rt = Runtime::new();
+    let n_items = 100_000;
+
+    let mut handles = Vec::new();
+    let mut snks = Vec::new();
+    for _ in 0..2 {
+        let mut fg = Flowgraph::new();
+
+        let src = fg.add_block(NullSourceBuilder::new(4).build());
+        let head = fg.add_block(HeadBuilder::new(4, n_items).build());
+        let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+
+        fg.connect_stream(src, "out", head, "in")?;
+        fg.connect_stream(head, "out", snk, "in")?;
+
+        let (task, _) = rt.start(fg);
+        handles.push(task);
+        snks.push(snk);
+    }
+
+    for (task, snk) in handles.into_iter().zip(snks) {
+        let fg = async_io::block_on(task)?;
+        let snk = fg.block_async::<VectorSink<f32>>(snk).unwrap();
+        assert_eq!(snk.items().len(), n_items as usize);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn flowgraph_config() -> Result<()> {
+    let mut config = Config::default();
+    config.set("buffer_size", Value::from(4096))?;
+    config.set("queue_size", Value::from(64))?;
+
+    let mut fg = Flowgraph::with_config(config);
+
+    let head = fg.add_block(HeadBuilder::new(4, 100_000).build());
+    let null_source = fg.add_block(NullSourceBuilder::new(4).build());
+    let vect_sink = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+
+    fg.connect_stream(null_source, "out", head, "in")?;
+    fg.connect_stream(head, "out", vect_sink, "in")?;
+
+    let rt = Runtime::with_config(Config::default())?;
+    fg = rt.run(fg)?;
+
+    let snk = fg.block_async::<VectorSink<f32>>(vect_sink).unwrap();
+    assert_eq!(snk.items().len(), 100_000);
+
+    Ok(())
+}
+
+#[test]
+fn dot_and_mermaid() -> Result<()> {
+    let mut config = Config::default();
+    config.set("buffer_size", Value::from(16384))?;
+    let mut fg = Flowgraph::with_config(config);
+
+    let src = fg.add_block(NullSourceBuilder::new(4).build());
+    let head = fg.add_block(HeadBuilder::new(4, 10).build());
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+    let msg_src = fg.add_block(MessageBurstBuilder::new(Pmt::Null, 1).build());
+    let msg_snk = fg.add_block(MessageSinkBuilder::new().build());
+
+    fg.connect_stream(src, "out", head, "in")?;
+    fg.connect_stream_with_type(head, "out", snk, "in", Slab::with_size(4096))?;
+    fg.connect_message(msg_src, "out", msg_snk, "in")?;
+
+    let dot = fg.to_dot();
+    assert!(dot.starts_with("digraph"));
+    assert!(dot.contains("b1 [label=\"{{<si0> in [4]}|Head_0\\n(Head)|{<so0> out [4]}}\"];"));
+    assert!(dot.contains("b0:so0 -> b1:si0 [label=\"Circular { min_bytes: 16384 }\"];"));
+    assert!(dot.contains("b1:so0 -> b2:si0 [label=\"Slab { min_bytes: 4096 }\"];"));
+    assert!(dot.contains("b3:mo0 -> b4:mi0 [style=dashed];"));
+
+    let mermaid = fg.to_mermaid();
+    assert!(mermaid.starts_with("flowchart LR"));
+    assert!(mermaid.contains("b1[\"Head_0<br/>(Head)\"]"));
+    assert!(mermaid.contains("b0 -->|\"out -> in [4], Circular { min_bytes: 16384 }\"| b1"));
+    assert!(mermaid.contains("b3 -.->|\"out -> in\"| b4"));
+
+    Runtime::new().run(fg)?;
+    Ok(())
+}
+
+#[test]
+fn runtime_config() -> Result<()> {
+    let mut config = Config::default();
+    config.set("scheduler", Value::from("flow"))?;
+    assert!(Runtime::with_config(config).is_err());
+
+    let mut config = Config::default();
+    config.set("queue_size", Value::from(0))?;
+    assert!(Runtime::with_config(config).is_err());
+
+    Ok(())
+}
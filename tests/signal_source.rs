@@ -0,0 +1,151 @@
+use std::f64::consts::PI;
+
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::MessageBurstBuilder;
+use futuresdr::blocks::SignalSourceBuilder;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::Waveform;
+use futuresdr::dsp::Nco;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+
+mod common;
+use common::run_source;
+
+const FS: f64 = 48000.0;
+
+#[test]
+fn nco() {
+    let mut nco = Nco::new(1000.0, FS);
+    assert!((nco.freq(FS) - 1000.0).abs() < 1e-4);
+
+    nco.set_freq(-1000.0, FS);
+    assert!((nco.freq(FS) + 1000.0).abs() < 1e-4);
+    assert!((nco.angular_freq() + 2.0 * PI * 1000.0 / FS).abs() < 1e-8);
+
+    nco.set_phase(1.0);
+    assert!((nco.phase() - 1.0).abs() < 1e-8);
+    nco.adjust_phase(2.0 * PI);
+    assert!((nco.phase() - 1.0).abs() < 1e-8);
+
+    // stepping a full period returns exactly to the start
+    let mut a = Nco::new(FS / 64.0, FS);
+    let start = a.raw_phase();
+    let mut b = a;
+    for _ in 0..64 {
+        a.step();
+    }
+    b.step_n(64);
+    assert_eq!(a, b);
+    assert_eq!(a.raw_phase(), start);
+}
+
+#[test]
+fn sine() -> Result<()> {
+    let (f, amp, offset, phase) = (1234.0, 0.5, 0.25, 0.3);
+    let v = run_source::<f32>(
+        SignalSourceBuilder::<f32>::new(FS)
+            .frequency(f)
+            .amplitude(amp)
+            .offset(offset)
+            .phase(phase)
+            .build(),
+        10_000,
+    )?;
+
+    assert_eq!(v.len(), 10_000);
+    for (n, y) in v.iter().enumerate() {
+        let want = amp as f64 * (2.0 * PI * f * n as f64 / FS + phase).sin() + offset as f64;
+        assert!((*y as f64 - want).abs() < 1e-4);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn complex_exp() -> Result<()> {
+    let f = -3000.0;
+    let v = run_source::<Complex32>(
+        SignalSourceBuilder::<Complex32>::new(FS)
+            .waveform(Waveform::ComplexExp)
+            .frequency(f)
+            .amplitude(2.0)
+            .build(),
+        1000,
+    )?;
+
+    for (n, y) in v.iter().enumerate() {
+        let want = Complex32::from_polar(2.0, (2.0 * PI * f * n as f64 / FS) as f32);
+        assert!((y - want).norm() < 1e-4);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn waveforms() -> Result<()> {
+    // eight samples per period
+    let gen = |w| {
+        run_source::<f32>(
+            SignalSourceBuilder::<f32>::new(FS)
+                .waveform(w)
+                .frequency(FS / 8.0)
+                .build(),
+            8,
+        )
+    };
+
+    let check = |have: Vec<f32>, want: &[f32]| {
+        assert_eq!(have.len(), want.len());
+        for (h, w) in have.iter().zip(want) {
+            assert!((h - w).abs() < 1e-6);
+        }
+    };
+
+    check(
+        gen(Waveform::Square)?,
+        &[1.0, 1.0, 1.0, 1.0, -1.0, -1.0, -1.0, -1.0],
+    );
+    check(
+        gen(Waveform::Triangle)?,
+        &[1.0, 0.5, 0.0, -0.5, -1.0, -0.5, 0.0, 0.5],
+    );
+    check(
+        gen(Waveform::Sawtooth)?,
+        &[-1.0, -0.75, -0.5, -0.25, 0.0, 0.25, 0.5, 0.75],
+    );
+    check(gen(Waveform::Constant)?, &[1.0; 8]);
+
+    Ok(())
+}
+
+#[test]
+fn set_amplitude() -> Result<()> {
+    let mut fg = Flowgraph::new();
+
+    let f = 1000.0;
+    let src = fg.add_block(SignalSourceBuilder::<f32>::new(FS).frequency(f).build());
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+    let amp = fg.add_block(MessageBurstBuilder::new(Pmt::Double(0.0), 1).build());
+
+    fg.connect_stream(src, "out", snk, "in")?;
+    // the burst terminates the source after the message
+    fg.connect_message(amp, "out", src, "amplitude")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let snk = fg.block_async::<VectorSink<f32>>(snk).unwrap();
+    let v = snk.items();
+    // the amplitude changes once, from one to zero
+    let switch = v
+        .iter()
+        .enumerate()
+        .position(|(n, y)| (*y as f64 - (2.0 * PI * f * n as f64 / FS).sin()).abs() > 1e-4)
+        .unwrap_or(v.len());
+    assert!(v[switch..].iter().all(|y| *y == 0.0));
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::AverageMode;
+use futuresdr::blocks::FrameAverage;
+use futuresdr::blocks::LogPower;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::blocks::VectorToStream;
+use futuresdr::blocks::WelchPsdBuilder;
+use futuresdr::dsp::Window;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+mod common;
+use common::run;
+
+#[test]
+fn log_power() -> Result<()> {
+    let input = vec![
+        Complex32::new(1.0, 0.0),
+        Complex32::new(0.0, 0.1),
+        Complex32::new(0.0, 0.0),
+    ];
+    let v: Vec<f32> = run(input, LogPower::<Complex32>::new(0.0))?;
+    assert!(v[0].abs() < 1e-6);
+    assert!((v[1] + 20.0).abs() < 1e-4);
+    assert!(v[2].is_finite() && v[2] < -370.0);
+
+    let v: Vec<f32> = run(vec![100.0f32, 0.5], LogPower::<f32>::new(3.0))?;
+    assert!((v[0] - 23.0).abs() < 1e-4);
+    assert!((v[1] + 0.0103).abs() < 1e-3);
+
+    Ok(())
+}
+
+#[test]
+fn frame_average() -> Result<()> {
+    let input = vec![2.0, 4.0, 4.0, f32::NEG_INFINITY, 0.0, 12.0, 6.0, 0.0, 1.0];
+
+    let v: Vec<f32> = run(
+        input.clone(),
+        FrameAverage::new(2, AverageMode::Exponential(0.5), 1, false),
+    )?;
+    assert_eq!(v, vec![2.0, 4.0, 3.0, 4.0, 1.5, 8.0, 3.75, 4.0]);
+
+    // the partial frame at the end is dropped, the infinite sample is not part of the mean
+    let v: Vec<f32> = run(
+        input.clone(),
+        FrameAverage::new(2, AverageMode::Linear(2), 1, false),
+    )?;
+    assert_eq!(v, vec![3.0, 4.0, 3.0, 6.0]);
+
+    let v: Vec<f32> = run(input, FrameAverage::new(2, AverageMode::MaxHold, 2, false))?;
+    assert_eq!(v, vec![4.0, 4.0, 6.0, 12.0]);
+
+    Ok(())
+}
+
+#[test]
+fn welch_psd() -> Result<()> {
+    let fft_size = 64;
+    let sample_rate = 1000.0;
+    // tone with a power of 4 at bin 8
+    let input: Vec<Complex32> = (0..1024)
+        .map(|i| Complex32::from_polar(2.0, 2.0 * std::f32::consts::PI * 8.0 * i as f32 / 64.0))
+        .collect();
+
+    let v: Vec<f32> = run(
+        input,
+        WelchPsdBuilder::new(fft_size)
+            .overlap(48)
+            .segments(4)
+            .window(Window::Hamming)
+            .sample_rate(sample_rate)
+            .build(),
+    )?;
+    // 61 segments
+    assert_eq!(v.len(), 15 * fft_size);
+    for psd in v.chunks(fft_size) {
+        let peak = psd
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .unwrap()
+            .0;
+        assert_eq!(peak, fft_size / 2 + 8);
+        // total power
+        let power: f64 = psd.iter().map(|p| *p as f64).sum::<f64>() * sample_rate / fft_size as f64;
+        assert!((power - 4.0).abs() < 1e-3);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn spectrum_vector() -> Result<()> {
+    let fft_size = 64;
+    let input: Vec<Complex32> = (0..4096)
+        .map(|i| Complex32::from_polar(1.0, 0.3 * i as f32))
+        .collect();
+
+    let welch = || WelchPsdBuilder::new(fft_size).segments(2);
+    let scalar = {
+        let mut fg = Flowgraph::new();
+        let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(input.clone()).build());
+        let psd = fg.add_block(welch().build());
+        let avg = fg.add_block(FrameAverage::new(fft_size, AverageMode::MaxHold, 3, false));
+        let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+        fg.connect_stream(src, "out", psd, "in")?;
+        fg.connect_stream(psd, "out", avg, "in")?;
+        fg.connect_stream(avg, "out", snk, "in")?;
+        fg = Runtime::new().run(fg)?;
+        fg.block_async::<VectorSink<f32>>(snk)
+            .unwrap()
+            .items()
+            .clone()
+    };
+
+    // frames as vector items between the blocks
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(input).build());
+    let psd = fg.add_block(welch().vector(true).build());
+    let avg = fg.add_block(FrameAverage::new(fft_size, AverageMode::MaxHold, 3, true));
+    let v2s = fg.add_block(VectorToStream::new(std::mem::size_of::<f32>(), fft_size));
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+    fg.connect_stream(src, "out", psd, "in")?;
+    fg.connect_stream(psd, "out", avg, "in")?;
+    fg.connect_stream(avg, "out", v2s, "in")?;
+    fg.connect_stream(v2s, "out", snk, "in")?;
+    fg = Runtime::new().run(fg)?;
+
+    let v = fg.block_async::<VectorSink<f32>>(snk).unwrap().items();
+    assert_eq!(v.len(), 21 * fft_size);
+    assert_eq!(v, &scalar);
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use std::f32::consts::PI;
+
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::ChannelModelBuilder;
+use futuresdr::blocks::CostasLoop;
+use futuresdr::blocks::FrequencyEstimator;
+use futuresdr::blocks::HeadBuilder;
+use futuresdr::blocks::MessageSink;
+use futuresdr::blocks::MessageSinkBuilder;
+use futuresdr::blocks::NullSinkBuilder;
+use futuresdr::blocks::NullSourceBuilder;
+use futuresdr::blocks::SymbolSyncBuilder;
+use futuresdr::blocks::ThrottleBuilder;
+use futuresdr::blocks::TimingErrorDetector;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::dsp::design;
+use futuresdr::dsp::Constellation;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Block;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Pmt;
+use futuresdr::runtime::Runtime;
+
+// runs the input through a chain of blocks
+fn chain(input: Vec<Complex32>, blocks: Vec<Block>) -> Result<Vec<Complex32>> {
+    let mut fg = Flowgraph::new();
+
+    let mut prev = fg.add_block(VectorSourceBuilder::<Complex32>::new(input).build());
+    for b in blocks {
+        let b = fg.add_block(b);
+        fg.connect_stream(prev, "out", b, "in")?;
+        prev = b;
+    }
+    let snk = fg.add_block(VectorSinkBuilder::<Complex32>::new().build());
+    fg.connect_stream(prev, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    Ok(fg
+        .block_async::<VectorSink<Complex32>>(snk)
+        .unwrap()
+        .items()
+        .clone())
+}
+
+// pseudo-random symbols of the constellation
+fn symbols(c: &Constellation, n: usize) -> Vec<Complex32> {
+    let mut x = 0x1234_5678u32;
+    (0..n)
+        .map(|_| {
+            x = x.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            c.map((x >> 16) as usize % c.points().len())
+        })
+        .collect()
+}
+
+// raised-cosine shaped signal with `sps` samples per symbol
+fn shape(symbols: &[Complex32], sps: usize) -> Vec<Complex32> {
+    let rrc = design::root_raised_cosine(10 * sps + 1, sps as f64, 0.35);
+    let rc: Vec<f32> = (0..2 * rrc.len() - 1)
+        .map(|n| {
+            (0..rrc.len())
+                .filter(|k| n >= *k && n - k < rrc.len())
+                .map(|k| rrc[k] * rrc[n - k])
+                .sum()
+        })
+        .collect();
+
+    let mut out = vec![Complex32::new(0.0, 0.0); symbols.len() * sps + rc.len()];
+    for (k, s) in symbols.iter().enumerate() {
+        for (j, t) in rc.iter().enumerate() {
+            out[k * sps + j] += s * t;
+        }
+    }
+    out
+}
+
+// largest distance to the closest constellation point
+fn max_error(c: &Constellation, v: &[Complex32]) -> f32 {
+    v.iter()
+        .map(|x| (x - c.map(c.slice(*x))).norm())
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn costas_loop() -> Result<()> {
+    for (order, c) in [(2, Constellation::bpsk()), (4, Constellation::qpsk())] {
+        let input = symbols(&c, 5000);
+        let v = chain(
+            input.clone(),
+            vec![
+                ChannelModelBuilder::new(1.0)
+                    .cfo(0.002)
+                    .snr(25.0)
+                    .seed(1)
+                    .build(),
+                CostasLoop::new(order, 0.05),
+            ],
+        )?;
+        assert_eq!(v.len(), input.len());
+
+        // constant rotation by a multiple of 2 * pi / order after convergence
+        let rotation = (v[1000] / input[1000]).arg();
+        let ambiguity = (rotation / (2.0 * PI / order as f32)).round() * 2.0 * PI / order as f32;
+        let rotation = Complex32::from_polar(1.0, ambiguity);
+        for (y, x) in v.iter().zip(&input).skip(1000) {
+            assert!((y - x * rotation).norm() < 0.3);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn costas_loop_bandwidth() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(NullSourceBuilder::new(8).build());
+    let throttle = fg.add_block(ThrottleBuilder::new(8, 100_000.0).build());
+    let costas = CostasLoop::new(4, 0.05);
+    let port = costas.message_input_name_to_id("bandwidth").unwrap();
+    let costas = fg.add_block(costas);
+    let head = fg.add_block(HeadBuilder::new(8, 20_000).build());
+    let snk = fg.add_block(NullSinkBuilder::new(8).build());
+    fg.connect_stream(src, "out", throttle, "in")?;
+    fg.connect_stream(throttle, "out", costas, "in")?;
+    fg.connect_stream(costas, "out", head, "in")?;
+    fg.connect_stream(head, "out", snk, "in")?;
+
+    let rt = Runtime::new();
+    let (task, mut handle) = rt.start(fg);
+    let b = async_io::block_on(handle.callback(costas, port, Pmt::Double(0.01)))?;
+    assert_eq!(b, Pmt::Double(0.01));
+    let b = async_io::block_on(handle.callback(costas, port, Pmt::Null))?;
+    assert_eq!(b, Pmt::Double(0.01));
+    async_io::block_on(task)?;
+
+    Ok(())
+}
+
+#[test]
+fn symbol_sync() -> Result<()> {
+    let sps = 4;
+    let c = Constellation::qpsk();
+    let input = symbols(&c, 5000);
+
+    for detector in [
+        TimingErrorDetector::Gardner,
+        TimingErrorDetector::MuellerMuller,
+    ] {
+        let v = chain(
+            shape(&input, sps),
+            vec![
+                ChannelModelBuilder::new(1.0)
+                    .sco(5e-4)
+                    .snr(30.0)
+                    .signal_power(1.0 / sps as f64)
+                    .seed(2)
+                    .build(),
+                SymbolSyncBuilder::new(sps as f64)
+                    .detector(detector)
+                    .build(),
+            ],
+        )?;
+
+        // one output per symbol, including the filter transients
+        let expected = input.len() as f32 * 1.0005;
+        assert!((v.len() as f32 - expected).abs() < 20.0);
+        assert!(max_error(&c, &v[500..v.len() - 20]) < 0.3);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn frequency_estimator() -> Result<()> {
+    let c = Constellation::qpsk();
+    let input = symbols(&c, 2000);
+    let window = 256;
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(input.clone()).build());
+    let channel = fg.add_block(
+        ChannelModelBuilder::new(1e6)
+            .cfo(20e3)
+            .snr(20.0)
+            .seed(3)
+            .build(),
+    );
+    let estimator = fg.add_block(FrequencyEstimator::new(1e6, 4, window));
+    let snk = fg.add_block(VectorSinkBuilder::<Complex32>::new().build());
+    let msg = fg.add_block(MessageSinkBuilder::new().build());
+    fg.connect_stream(src, "out", channel, "in")?;
+    fg.connect_stream(channel, "out", estimator, "in")?;
+    fg.connect_stream(estimator, "out", snk, "in")?;
+    fg.connect_message(estimator, "freq", msg, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let v = fg
+        .block_async::<VectorSink<Complex32>>(snk)
+        .unwrap()
+        .items()
+        .clone();
+    assert_eq!(v.len(), input.len());
+    let n = fg.block_async::<MessageSink>(msg).unwrap().received();
+    assert_eq!(n as usize, input.len().div_ceil(window));
+
+    // the residual offset is small enough for a Costas loop
+    let drift = v
+        .iter()
+        .zip(&input)
+        .map(|(y, x)| y / x)
+        .collect::<Vec<_>>()
+        .windows(2)
+        .map(|w| (w[1] * w[0].conj()).arg())
+        .sum::<f32>()
+        / (v.len() - 1) as f32;
+    assert!(drift.abs() < 2.0 * PI * 200.0 / 1e6);
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::CopyBuilder;
+use futuresdr::blocks::Deinterleave;
+use futuresdr::blocks::Interleave;
+use futuresdr::blocks::StreamToVector;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::blocks::VectorToStream;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+#[test]
+fn vector_round_trip() -> Result<()> {
+    let input: Vec<u32> = (0..103).collect();
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSourceBuilder::<u32>::new(input.clone()).build());
+    let to_vector = fg.add_block(StreamToVector::new(4, 10));
+    // whole vectors as items
+    let copy = fg.add_block(CopyBuilder::new(40).build());
+    let to_stream = fg.add_block(VectorToStream::new(4, 10));
+    let snk = fg.add_block(VectorSinkBuilder::<u32>::new().build());
+    fg.connect_stream(src, "out", to_vector, "in")?;
+    fg.connect_stream(to_vector, "out", copy, "in")?;
+    fg.connect_stream(copy, "out", to_stream, "in")?;
+    fg.connect_stream(to_stream, "out", snk, "in")?;
+    assert!(fg.connect_stream(src, "out", to_stream, "in").is_err());
+
+    fg = Runtime::new().run(fg)?;
+
+    let items = fg.block_async::<VectorSink<u32>>(snk).unwrap().items();
+    // the partial vector is dropped
+    assert_eq!(items, &input[0..100]);
+
+    Ok(())
+}
+
+#[test]
+fn interleave() -> Result<()> {
+    let mut fg = Flowgraph::new();
+    let interleave = fg.add_block(Interleave::new(2, 3, 2));
+    let snk = fg.add_block(VectorSinkBuilder::<u16>::new().build());
+    for k in 0..3 {
+        let input: Vec<u16> = (0..7).map(|i| 100 * k + i).collect();
+        let src = fg.add_block(VectorSourceBuilder::<u16>::new(input).build());
+        fg.connect_stream(src, "out", interleave, &format!("in{}", k))?;
+    }
+    fg.connect_stream(interleave, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    let items = fg.block_async::<VectorSink<u16>>(snk).unwrap().items();
+    assert_eq!(
+        items,
+        &vec![0, 1, 100, 101, 200, 201, 2, 3, 102, 103, 202, 203, 4, 5, 104, 105, 204, 205]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn deinterleave() -> Result<()> {
+    let input: Vec<f32> = (0..20_000).map(|i| i as f32).collect();
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSourceBuilder::<f32>::new(input.clone()).build());
+    let deinterleave = fg.add_block(Deinterleave::new(4, 2, 3));
+    let interleave = fg.add_block(Interleave::new(4, 2, 3));
+    let snk0 = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+    let snk = fg.add_block(VectorSinkBuilder::<f32>::new().build());
+    let copy = fg.add_block(CopyBuilder::new(4).build());
+    fg.connect_stream(src, "out", deinterleave, "in")?;
+    fg.connect_stream(deinterleave, "out0", copy, "in")?;
+    fg.connect_stream(copy, "out", snk0, "in")?;
+    fg.connect_stream(deinterleave, "out1", interleave, "in1")?;
+    let src0 = fg.add_block(VectorSourceBuilder::<f32>::new(vec![0.0; 9999]).build());
+    fg.connect_stream(src0, "out", interleave, "in0")?;
+    fg.connect_stream(interleave, "out", snk, "in")?;
+
+    fg = Runtime::new().run(fg)?;
+
+    // 3333 full rounds of 6 items
+    let out0 = fg.block_async::<VectorSink<f32>>(snk0).unwrap().items();
+    assert_eq!(out0.len(), 9999);
+    for (r, c) in out0.chunks(3).enumerate() {
+        assert_eq!(c, &input[6 * r..6 * r + 3]);
+    }
+    let items = fg.block_async::<VectorSink<f32>>(snk).unwrap().items();
+    assert_eq!(items.len(), 19998);
+    for (r, c) in items.chunks(6).enumerate() {
+        assert_eq!(&c[0..3], &[0.0; 3]);
+        assert_eq!(&c[3..6], &input[6 * r + 3..6 * r + 6]);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use futuresdr::anyhow::Result;
+use futuresdr::blocks::ComplexToWire;
+use futuresdr::blocks::FileSinkBuilder;
+use futuresdr::blocks::FileSourceBuilder;
+use futuresdr::blocks::FloatToWire;
+use futuresdr::blocks::VectorSink;
+use futuresdr::blocks::VectorSinkBuilder;
+use futuresdr::blocks::VectorSourceBuilder;
+use futuresdr::blocks::WireToComplex;
+use futuresdr::blocks::WireToFloat;
+use futuresdr::num_complex::Complex;
+use futuresdr::num_complex::Complex32;
+use futuresdr::runtime::Flowgraph;
+use futuresdr::runtime::Runtime;
+
+mod common;
+use common::run;
+
+#[test]
+fn complex_i16() -> Result<()> {
+    let input = vec![
+        Complex32::new(0.5, -0.25),
+        Complex32::new(-1.0, 2.0),
+        Complex32::new(1.0 / 32768.0, 0.0),
+    ];
+
+    let v: Vec<Complex<i16>> = run(input.clone(), ComplexToWire::<i16>::new(32768.0, false))?;
+    assert_eq!(
+        v,
+        vec![
+            Complex::new(16384, -8192),
+            Complex::new(i16::MIN, i16::MAX),
+            Complex::new(1, 0),
+        ]
+    );
+
+    let v: Vec<Complex<i16>> = run(input, ComplexToWire::<i16>::new(32768.0, true))?;
+    assert_eq!(v[2], Complex::new(256, 0));
+
+    let v: Vec<Complex32> = run(v, WireToComplex::<i16>::new(32768.0, true))?;
+    assert_eq!(v[0], Complex32::new(0.5, -0.25));
+    assert_eq!(v[2], Complex32::new(1.0 / 32768.0, 0.0));
+
+    Ok(())
+}
+
+#[test]
+fn complex_8bit() -> Result<()> {
+    let v: Vec<Complex32> = run(
+        vec![Complex::new(0u8, 255u8), Complex::new(127, 128)],
+        WireToComplex::<u8>::new(128.0, false),
+    )?;
+    assert_eq!(
+        v,
+        vec![
+            Complex32::new(-127.5 / 128.0, 127.5 / 128.0),
+            Complex32::new(-0.5 / 128.0, 0.5 / 128.0),
+        ]
+    );
+
+    let v: Vec<Complex<u8>> = run(v, ComplexToWire::<u8>::new(128.0, false))?;
+    assert_eq!(v, vec![Complex::new(0, 255), Complex::new(127, 128)]);
+
+    let v: Vec<Complex<i8>> = run(
+        vec![Complex32::new(2.0, -0.5)],
+        ComplexToWire::<i8>::new(128.0, false),
+    )?;
+    assert_eq!(v, vec![Complex::new(127, -64)]);
+
+    Ok(())
+}
+
+#[test]
+fn real_i16() -> Result<()> {
+    let v: Vec<i16> = run(
+        vec![0.1f32, -0.2, 40.0],
+        FloatToWire::<i16>::new(100.0, false),
+    )?;
+    assert_eq!(v, vec![10, -20, 4000]);
+
+    let v: Vec<f32> = run(v, WireToFloat::<i16>::new(100.0, false))?;
+    for (x, y) in v.iter().zip([0.1, -0.2, 40.0]) {
+        assert!((x - y).abs() < 1e-6);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn big_endian_file() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("futuresdr-sc16-{}.bin", std::process::id()));
+    let file = path.to_str().unwrap();
+
+    // three big-endian SC16 samples and an incomplete one
+    let mut data = Vec::new();
+    for x in [1i16, -2, 16384, -16384, i16::MAX, i16::MIN] {
+        data.extend_from_slice(&x.to_be_bytes());
+    }
+    data.extend_from_slice(&[0x12, 0x34]);
+    std::fs::write(&path, &data)?;
+
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(FileSourceBuilder::new(4, file.to_string()).build());
+    let conv = fg.add_block(WireToComplex::<i16>::new(32768.0, true));
+    let snk = fg.add_block(VectorSinkBuilder::<Complex32>::new().build());
+    fg.connect_stream(src, "out", conv, "in")?;
+    fg.connect_stream(conv, "out", snk, "in")?;
+    fg = Runtime::new().run(fg)?;
+
+    let v = fg
+        .block_async::<VectorSink<Complex32>>(snk)
+        .unwrap()
+        .items()
+        .clone();
+    assert_eq!(
+        v,
+        vec![
+            Complex32::new(1.0 / 32768.0, -2.0 / 32768.0),
+            Complex32::new(0.5, -0.5),
+            Complex32::new(32767.0 / 32768.0, -1.0),
+        ]
+    );
+
+    // and back
+    let mut fg = Flowgraph::new();
+    let src = fg.add_block(VectorSourceBuilder::<Complex32>::new(v).build());
+    let conv = fg.add_block(ComplexToWire::<i16>::new(32768.0, true));
+    let snk = fg.add_block(FileSinkBuilder::new(4, file).build());
+    fg.connect_stream(src, "out", conv, "in")?;
+    fg.connect_stream(conv, "out", snk, "in")?;
+    Runtime::new().run(fg)?;
+
+    let written = std::fs::read(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(written, data[..12]);
+
+    Ok(())
+}